@@ -0,0 +1,330 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+use std::io::{BufRead, ErrorKind, Write};
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use dsi_progress_logger::*;
+use epserde::prelude::*;
+use lender::for_;
+use mem_dbg::*;
+use sux::{
+    dict::{EfDict, EfSeq, EfSeqDict},
+    prelude::*,
+    utils::LineLender,
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Builds and queries Elias–Fano representations of monotone sequences.", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Builds an Elias–Fano representation of a monotone sequence of integers.
+    Build {
+        /// A file containing a nondecreasing sequence of integers, or - for
+        /// standard input.
+        source: String,
+        /// A name for the ε-serde serialized Elias–Fano structure.
+        dest: String,
+        /// The source contains little-endian 64-bit integers instead of text
+        /// with one integer per line.
+        #[arg(short, long)]
+        binary: bool,
+        /// The selection structures to build.
+        #[arg(short, long, value_enum, default_value_t = Kind::SeqDict)]
+        kind: Kind,
+    },
+    /// Prints the values with given indices, one per line.
+    Get {
+        /// The ε-serde serialized Elias–Fano structure.
+        ef: String,
+        /// The indices of the values.
+        #[arg(required = true)]
+        indices: Vec<usize>,
+    },
+    /// Prints the indices of the given values, one per line, or - if a value
+    /// is not in the sequence.
+    IndexOf {
+        /// The ε-serde serialized Elias–Fano structure.
+        ef: String,
+        /// The values to look for.
+        #[arg(required = true)]
+        values: Vec<usize>,
+    },
+    /// Prints the index and the value of the successors (the first values
+    /// greater than or equal to the given ones), or - if there is no
+    /// successor.
+    Succ {
+        /// The ε-serde serialized Elias–Fano structure.
+        ef: String,
+        /// The values to look for.
+        #[arg(required = true)]
+        values: Vec<usize>,
+        /// Looks for strictly greater values.
+        #[arg(short, long)]
+        strict: bool,
+    },
+    /// Prints the index and the value of the predecessors (the last values
+    /// smaller than or equal to the given ones), or - if there is no
+    /// predecessor.
+    Pred {
+        /// The ε-serde serialized Elias–Fano structure.
+        ef: String,
+        /// The values to look for.
+        #[arg(required = true)]
+        values: Vec<usize>,
+        /// Looks for strictly smaller values.
+        #[arg(short, long)]
+        strict: bool,
+    },
+    /// Prints the number of values and the memory usage of the structure.
+    Stats {
+        /// The ε-serde serialized Elias–Fano structure.
+        ef: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Kind {
+    /// Selection on ones only: supports access (EfSeq).
+    Seq,
+    /// Selection on zeros only: supports indexing (EfDict).
+    Dict,
+    /// Selection on ones and zeros: supports access, indexing, successor,
+    /// and predecessor (EfSeqDict).
+    SeqDict,
+}
+
+/// Opens a file, or standard input if the name is -.
+fn open(source: &str) -> Result<Box<dyn BufRead>> {
+    Ok(if source == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = std::fs::File::open(source)?;
+        Box::new(std::io::BufReader::new(file))
+    })
+}
+
+/// Reads a nondecreasing sequence of integers, in text or binary form.
+fn read_values(source: &str, binary: bool) -> Result<Vec<usize>> {
+    let mut pl = ProgressLogger::default();
+    pl.display_memory(true).item_name("value");
+    pl.start("Reading the input file...");
+
+    let mut reader = open(source)?;
+    let mut values = Vec::new();
+    if binary {
+        let mut buffer = [0; 8];
+        loop {
+            let mut read = 0;
+            while read < buffer.len() {
+                match reader.read(&mut buffer[read..]) {
+                    Ok(0) => break,
+                    Ok(n) => read += n,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            if read == 0 {
+                break;
+            }
+            if read < buffer.len() {
+                bail!(
+                    "The input file ends with a partial record of {} bytes",
+                    read
+                );
+            }
+            values.push(u64::from_le_bytes(buffer) as usize);
+            pl.light_update();
+        }
+    } else {
+        for_![result in LineLender::new(reader) {
+            let line = result?.trim();
+            if !line.is_empty() {
+                values.push(line.parse()?);
+            }
+            pl.light_update();
+        }];
+    }
+
+    pl.done();
+
+    if let Some(i) = (1..values.len()).find(|&i| values[i] < values[i - 1]) {
+        bail!(
+            "The values provided are not monotone: {} < {} at index {}",
+            values[i],
+            values[i - 1],
+            i
+        );
+    }
+    Ok(values)
+}
+
+fn build(source: &str, dest: &str, binary: bool, kind: Kind) -> Result<()> {
+    let values = read_values(source, binary)?;
+    let mut efb = EliasFanoBuilder::new(values.len(), values.last().copied().unwrap_or(0));
+    for &value in &values {
+        efb.push(value);
+    }
+
+    let dst_file = std::fs::File::create(dest).expect("Cannot create destination file");
+    let mut dst_file = std::io::BufWriter::new(dst_file);
+    match kind {
+        Kind::Seq => efb.build_with_seq().serialize(&mut dst_file),
+        Kind::Dict => efb.build_with_dict().serialize(&mut dst_file),
+        Kind::SeqDict => efb.build_with_seq_and_dict().serialize(&mut dst_file),
+    }
+    .expect("Cannot serialize Elias–Fano structure");
+    Ok(())
+}
+
+fn get(ef: &impl IndexedSeq<Output = usize>, indices: &[usize]) -> Result<()> {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    for &index in indices {
+        if index >= ef.len() {
+            bail!("Index out of bounds: {} >= {}", index, ef.len());
+        }
+        writeln!(stdout, "{}", ef.get(index))?;
+    }
+    Ok(())
+}
+
+fn index_of(ef: &impl IndexedDict<Input = usize>, values: &[usize]) -> Result<()> {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    for &value in values {
+        match ef.index_of(value) {
+            Some(index) => writeln!(stdout, "{}", index)?,
+            None => writeln!(stdout, "-")?,
+        }
+    }
+    Ok(())
+}
+
+fn print_results(results: impl Iterator<Item = Option<(usize, usize)>>) -> Result<()> {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    for result in results {
+        match result {
+            Some((index, value)) => writeln!(stdout, "{}\t{}", index, value)?,
+            None => writeln!(stdout, "-")?,
+        }
+    }
+    Ok(())
+}
+
+fn stats(len: usize, ef: &impl MemDbg) -> Result<()> {
+    println!("Number of values: {}", len);
+    ef.mem_dbg(DbgFlags::default() | DbgFlags::FOLLOW_REFS | DbgFlags::PERCENTAGE)?;
+    Ok(())
+}
+
+/// Returns whether an error is an ε-serde type-hash mismatch, that is,
+/// whether the file contains a structure of a different type.
+fn is_wrong_type(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<deser::Error>(),
+        Some(deser::Error::WrongTypeHash { .. })
+    )
+}
+
+/// Memory-maps an Elias–Fano structure, trying all kinds, and executes a query
+/// command on it.
+///
+/// Only type-hash mismatches make us try the next kind; any other error is
+/// returned immediately.
+fn query(path: &str, command: &Command) -> Result<()> {
+    let flags = Flags::empty();
+    match <EfSeqDict>::mmap(path, flags) {
+        Ok(ef) => {
+            let ef = &*ef;
+            return match command {
+                Command::Get { indices, .. } => get(ef, indices),
+                Command::IndexOf { values, .. } => index_of(ef, values),
+                Command::Succ { values, strict, .. } => {
+                    print_results(values.iter().map(|&value| {
+                        if *strict {
+                            ef.succ_strict(value)
+                        } else {
+                            ef.succ(value)
+                        }
+                    }))
+                }
+                Command::Pred { values, strict, .. } => {
+                    print_results(values.iter().map(|&value| {
+                        if *strict {
+                            ef.pred_strict(value)
+                        } else {
+                            ef.pred(value)
+                        }
+                    }))
+                }
+                Command::Stats { .. } => stats(ef.len(), ef),
+                Command::Build { .. } => unreachable!(),
+            };
+        }
+        Err(e) if !is_wrong_type(&e) => return Err(e),
+        Err(_) => {}
+    }
+    match <EfSeq>::mmap(path, flags) {
+        Ok(ef) => {
+            return match command {
+                Command::Get { indices, .. } => get(&*ef, indices),
+                Command::Stats { .. } => stats(ef.len(), &*ef),
+                Command::IndexOf { .. } => {
+                    bail!("Indexing queries need a structure built with --kind dict or seq-dict")
+                }
+                _ => bail!(
+                    "Successor and predecessor queries need a structure built with --kind seq-dict"
+                ),
+            }
+        }
+        Err(e) if !is_wrong_type(&e) => return Err(e),
+        Err(_) => {}
+    }
+    match <EfDict>::mmap(path, flags) {
+        Ok(ef) => match command {
+            Command::IndexOf { values, .. } => index_of(&*ef, values),
+            Command::Stats { .. } => stats(ef.len(), &*ef),
+            Command::Get { .. } => {
+                bail!("Access queries need a structure built with --kind seq or seq-dict")
+            }
+            _ => bail!(
+                "Successor and predecessor queries need a structure built with --kind seq-dict"
+            ),
+        },
+        Err(e) if !is_wrong_type(&e) => Err(e),
+        Err(_) => bail!(
+            "{} is not an Elias–Fano structure of kind seq, dict, or seq-dict",
+            path
+        ),
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .try_init()?;
+
+    let args = Args::parse();
+
+    match &args.command {
+        Command::Build {
+            source,
+            dest,
+            binary,
+            kind,
+        } => build(source, dest, *binary, *kind),
+        Command::Get { ef, .. }
+        | Command::IndexOf { ef, .. }
+        | Command::Succ { ef, .. }
+        | Command::Pred { ef, .. }
+        | Command::Stats { ef } => query(ef, &args.command),
+    }
+}
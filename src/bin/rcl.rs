@@ -4,30 +4,111 @@
  *
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
-use std::{borrow::Borrow, io::BufRead};
+use std::{
+    borrow::Borrow,
+    io::{BufRead, Write},
+};
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
 use dsi_progress_logger::*;
-use epserde::ser::Serialize;
-use lender::for_;
+use epserde::prelude::*;
+use lender::{for_, IntoLender};
 use sux::{prelude::*, utils::LineLender};
 
 #[derive(Parser, Debug)]
-#[command(about = "Builds a rear-coded list starting from a list of UTF-8 encoded strings.", long_about = None)]
+#[command(about = "Builds and queries rear-coded lists of UTF-8 encoded strings.", long_about = None)]
 struct Args {
-    /// A file containing UTF-8 strings, one per line, or - for standard input.
-    source: String,
-    /// A name for the ε-serde serialized rear-coded list.
-    dest: String,
-    /// The number of strings in a block. Higher values provide more compression
-    /// at the expense of slower access.
-    #[arg(short = 'k', long, default_value_t = 8)]
-    k: usize,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Builds a rear-coded list starting from a list of UTF-8 encoded strings.
+    Build {
+        /// A file containing UTF-8 strings, one per line, or - for standard input.
+        source: String,
+        /// A name for the ε-serde serialized rear-coded list.
+        dest: String,
+        /// The number of strings in a block. Higher values provide more compression
+        /// at the expense of slower access.
+        #[arg(short = 'k', long, default_value_t = 8)]
+        k: usize,
+        /// The coder for the lengths preceding each suffix. The statistics
+        /// printed after reading the input show the space used by each coder.
+        #[arg(short, long, value_enum, default_value_t = Coder::Vbyte)]
+        coder: Coder,
+    },
+    /// Prints the strings with given indices, one per line.
+    Get {
+        /// The ε-serde serialized rear-coded list.
+        rcl: String,
+        /// The indices of the strings.
+        #[arg(required = true)]
+        indices: Vec<usize>,
+    },
+    /// Prints the indices of the given strings, one per line, or - if a
+    /// string is not in the list.
+    IndexOf {
+        /// The ε-serde serialized rear-coded list.
+        rcl: String,
+        /// The strings to look for.
+        #[arg(required = true)]
+        strings: Vec<String>,
+    },
+    /// Writes all strings to standard output, one per line.
+    Dump {
+        /// The ε-serde serialized rear-coded list.
+        rcl: String,
+        /// Compresses the output using zstd.
+        #[arg(short, long)]
+        zstd: bool,
+    },
+    /// Prints the statistics of the encoded strings.
+    Stats {
+        /// The ε-serde serialized rear-coded list.
+        rcl: String,
+    },
+    /// Checks that a rear-coded list contains exactly the strings of a file,
+    /// in the same order.
+    Verify {
+        /// The ε-serde serialized rear-coded list.
+        rcl: String,
+        /// A file containing UTF-8 strings, one per line, or - for standard input.
+        source: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Coder {
+    /// Variable-length byte code (VByteCoder).
+    Vbyte,
+    /// Two nibbles in a byte, with escape (NibbleCoder).
+    Nibble,
+    /// Single byte for the rear length, with escape (ByteEscapeCoder).
+    ByteEscape,
+}
+
+/// Opens a file, or standard input if the name is -.
+fn open(source: &str) -> Result<Box<dyn BufRead>> {
+    Ok(if source == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = std::fs::File::open(source)?;
+        Box::new(std::io::BufReader::new(file))
+    })
 }
 
-fn compress<BR: BufRead>(buf_read: BR, dest: impl Borrow<str>, k: usize) -> Result<()> {
-    let mut rclb = RearCodedListBuilder::new(k);
+fn compress<BR: BufRead, C: RearLengthCoder>(
+    buf_read: BR,
+    dest: impl Borrow<str>,
+    k: usize,
+) -> Result<()>
+where
+    RearCodedList<str, C>: Serialize,
+{
+    let mut rclb = RearCodedListBuilder::<str, C>::with_block_size(k);
 
     let mut pl = ProgressLogger::default();
     pl.display_memory(true);
@@ -58,6 +139,115 @@ fn compress<BR: BufRead>(buf_read: BR, dest: impl Borrow<str>, k: usize) -> Resu
     Ok(())
 }
 
+/// Returns whether an error is an ε-serde type-hash mismatch, that is,
+/// whether the file contains a structure of a different type.
+fn is_wrong_type(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<deser::Error>(),
+        Some(deser::Error::WrongTypeHash { .. })
+    )
+}
+
+/// Memory-maps a rear-coded list, trying all coders, and executes a query
+/// command on it.
+///
+/// Only type-hash mismatches make us try the next coder; any other error is
+/// returned immediately.
+fn query(path: &str, command: &Command) -> Result<()> {
+    let flags = Flags::empty();
+    match <RearCodedList<str, VByteCoder>>::mmap(path, flags) {
+        Ok(rcl) => return run(&rcl, command),
+        Err(e) if !is_wrong_type(&e) => return Err(e),
+        Err(_) => {}
+    }
+    match <RearCodedList<str, NibbleCoder>>::mmap(path, flags) {
+        Ok(rcl) => return run(&rcl, command),
+        Err(e) if !is_wrong_type(&e) => return Err(e),
+        Err(_) => {}
+    }
+    match <RearCodedList<str, ByteEscapeCoder>>::mmap(path, flags) {
+        Ok(rcl) => run(&rcl, command),
+        Err(e) if !is_wrong_type(&e) => Err(e),
+        Err(_) => bail!(
+            "{} is not a rear-coded list of strings using VByteCoder, NibbleCoder, or ByteEscapeCoder",
+            path
+        ),
+    }
+}
+
+fn run<C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>(
+    rcl: &RearCodedList<str, C, D, P>,
+    command: &Command,
+) -> Result<()> {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    match command {
+        Command::Build { .. } => unreachable!(),
+        Command::Get { indices, .. } => {
+            let mut cursor = rcl.cursor();
+            for &index in indices {
+                if index >= rcl.len() {
+                    bail!("Index out of bounds: {} >= {}", index, rcl.len());
+                }
+                writeln!(stdout, "{}", cursor.get(index))?;
+            }
+        }
+        Command::IndexOf { strings, .. } => {
+            for string in strings {
+                match rcl.index_of(string.as_str()) {
+                    Some(index) => writeln!(stdout, "{}", index)?,
+                    None => writeln!(stdout, "-")?,
+                }
+            }
+        }
+        Command::Dump { zstd, .. } => {
+            let mut out: Box<dyn Write> = if *zstd {
+                Box::new(zstd::Encoder::new(stdout, 0)?.auto_finish())
+            } else {
+                Box::new(stdout)
+            };
+            for_![string in rcl.into_lender() {
+                writeln!(out, "{}", string)?;
+            }];
+            out.flush()?;
+        }
+        Command::Stats { .. } => rcl.print_stats(),
+        Command::Verify { source, .. } => {
+            let mut pl = ProgressLogger::default();
+            pl.display_memory(true);
+            pl.start("Verifying the rear-coded list...");
+
+            let mut strings = rcl.into_lender();
+            let mut index = 0;
+            for_![result in LineLender::new(open(source)?) {
+                let line = result?;
+                match strings.next() {
+                    Some(string) if string == line => {}
+                    Some(string) => bail!(
+                        "String {} differs: {:?} in the list, {:?} in the source",
+                        index,
+                        string,
+                        line
+                    ),
+                    None => bail!("The list contains {} strings, but the source contains more", index),
+                }
+                index += 1;
+                pl.light_update();
+            }];
+            if index != rcl.len() {
+                bail!(
+                    "The list contains {} strings, but the source contains {}",
+                    rcl.len(),
+                    index
+                );
+            }
+
+            pl.done();
+            pl.info(format_args!("Verified {} strings", index));
+        }
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     env_logger::builder()
         .filter_level(log::LevelFilter::Info)
@@ -65,14 +255,25 @@ fn main() -> Result<()> {
 
     let args = Args::parse();
 
-    if args.source == "-" {
-        let stdin = std::io::stdin();
-        let stdin = stdin.lock();
-        compress(stdin, args.dest, args.k)?;
-    } else {
-        let file = std::fs::File::open(&args.source).expect("Cannot open source file");
-        let buf_ref = std::io::BufReader::new(file);
-        compress(buf_ref, args.dest, args.k)?;
+    match &args.command {
+        Command::Build {
+            source,
+            dest,
+            k,
+            coder,
+        } => {
+            let buf_read = open(source)?;
+            match coder {
+                Coder::Vbyte => compress::<_, VByteCoder>(buf_read, dest.as_str(), *k)?,
+                Coder::Nibble => compress::<_, NibbleCoder>(buf_read, dest.as_str(), *k)?,
+                Coder::ByteEscape => compress::<_, ByteEscapeCoder>(buf_read, dest.as_str(), *k)?,
+            }
+        }
+        Command::Get { rcl, .. }
+        | Command::IndexOf { rcl, .. }
+        | Command::Dump { rcl, .. }
+        | Command::Stats { rcl }
+        | Command::Verify { rcl, .. } => query(rcl, &args.command)?,
     }
 
     Ok(())
@@ -83,12 +83,17 @@ use crate::prelude::*;
 use crate::traits::bit_field_slice::{panic_if_out_of_bounds, panic_if_value};
 use anyhow::{bail, Result};
 use common_traits::*;
+use epserde::ser::{Serialize, SerializeInner, WriteWithNames};
+use epserde::traits::MaxSizeOf;
 use epserde::*;
 use mem_dbg::*;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
+use std::path::Path;
 use std::sync::atomic::*;
 
+use super::mmap_backend::MmapBackend;
+
 /// Convenient, [`vec!`]-like macro to initialize `usize`-based bit-field
 /// vectors.
 ///
@@ -255,6 +260,46 @@ impl<W: Word, B: AsRef<[W]>> BitFieldVec<W, B> {
     }
 }
 
+impl<W: Word + MaxSizeOf + SerializeInner> BitFieldVec<W, MmapBackend<W>>
+where
+    BitFieldVec<W, Vec<W>>: Serialize,
+{
+    /// Create a new zero-initialized vector of given bit width and length
+    /// whose values are stored in a writable memory mapping of the file at
+    /// `path`, which is created or truncated.
+    ///
+    /// After a call to [`flush`](BitFieldVec::flush), the file can be loaded
+    /// by ε-serde as a `BitFieldVec<W, Vec<W>>`. See the
+    /// documentation of [memory-mapped backends](super::mmap_backend) for
+    /// more details.
+    pub fn new_mmap(path: impl AsRef<Path>, bit_width: usize, len: usize) -> Result<Self> {
+        // We need at least one word to handle the case of bit width zero.
+        let n_of_words = Ord::max(1, (len * bit_width).div_ceil(W::BITS));
+        // The bit width, the mask, and the length follow the words
+        let trailer_size = 2 * size_of::<usize>() + W::BYTES;
+        let bits = MmapBackend::new::<BitFieldVec<W, Vec<W>>>(path, n_of_words, trailer_size)?;
+        Ok(Self {
+            bits,
+            bit_width,
+            mask: mask(bit_width),
+            len,
+        })
+    }
+
+    /// Writes in the backing file the ε-serde serialization of this vector as
+    /// a `BitFieldVec<W, Vec<W>>` and flushes the mapping.
+    ///
+    /// The vector can be further modified and flushed again.
+    pub fn flush(&mut self) -> Result<()> {
+        let (bit_width, mask, len) = (self.bit_width, self.mask, self.len);
+        self.bits.flush::<BitFieldVec<W, Vec<W>>>(|writer| {
+            writer.write("bit_width", &bit_width)?;
+            writer.write("mask", &mask)?;
+            writer.write("len", &len)
+        })
+    }
+}
+
 impl<W: Word> BitFieldVec<W, Vec<W>> {
     /// Create a new zero-initialized vector of given bit width and length.
     pub fn new(bit_width: usize, len: usize) -> Self {
@@ -0,0 +1,355 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Borrowed views on ranges of bits.
+//!
+//! A [`BitSlice`] is a view on a range of bits of a bit array starting at an
+//! arbitrary bit offset. It is usually obtained from a [`BitVec`] using
+//! [`BitVec::slice`], and it can be further sliced using [`BitSlice::slice`].
+//! Differently from a `BitVec<&[usize]>`, no copy is involved, and the range
+//! does not need to start on a word boundary.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sux::bit_vec;
+//! use sux::traits::{BitCount, BitLength};
+//!
+//! let b = bit_vec![0, 1, 0, 1, 1, 0, 1, 0];
+//! let s = b.slice(1..6);
+//! assert_eq!(s.len(), 5);
+//! assert_eq!(s.count_ones(), 3);
+//! assert_eq!(s[0], true);
+//! assert_eq!(s.iter_ones().collect::<Vec<_>>(), vec![0, 2, 3]);
+//! assert_eq!(s.get_bits(1, 3), 0b110);
+//! ```
+
+use super::BitVec;
+use crate::traits::rank_sel::*;
+use mem_dbg::*;
+use std::ops::{Bound, Index, RangeBounds};
+
+const BITS: usize = usize::BITS as usize;
+
+/// Returns a mask of `width` ≤ [`usize::BITS`] ones.
+#[inline(always)]
+fn mask(width: usize) -> usize {
+    if width == BITS {
+        usize::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Reads `width` ≤ [`usize::BITS`] bits of a bit array starting at bit
+/// position `pos`.
+///
+/// No bounds checking is performed.
+#[inline(always)]
+pub(crate) fn read_bits(words: &[usize], pos: usize, width: usize) -> usize {
+    debug_assert!(width <= BITS);
+    if width == 0 {
+        return 0;
+    }
+    let word = pos / BITS;
+    let bit = pos % BITS;
+    let mut value = words[word] >> bit;
+    if bit + width > BITS {
+        value |= words[word + 1] << (BITS - bit);
+    }
+    value & mask(width)
+}
+
+/// Writes the `width` ≤ [`usize::BITS`] lowest bits of `value` in a bit
+/// array starting at bit position `pos`.
+///
+/// No bounds checking is performed.
+#[inline(always)]
+pub(crate) fn write_bits(words: &mut [usize], pos: usize, width: usize, value: usize) {
+    debug_assert!(width <= BITS);
+    if width == 0 {
+        return;
+    }
+    let word = pos / BITS;
+    let bit = pos % BITS;
+    let mask = mask(width);
+    words[word] = (words[word] & !(mask << bit)) | ((value & mask) << bit);
+    if bit + width > BITS {
+        let high_mask = mask >> (BITS - bit);
+        words[word + 1] = (words[word + 1] & !high_mask) | ((value & mask) >> (BITS - bit));
+    }
+}
+
+/// Returns the start (included) and end (excluded) of a range of bits,
+/// panicking if the range is not within `len` bits.
+#[inline]
+pub(crate) fn bit_range(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("Bit range starts at {} but ends at {}", start, end);
+    }
+    if end > len {
+        panic!("Bit range end out of bounds: {} > {}", end, len);
+    }
+    (start, end)
+}
+
+/// A borrowed view on a range of bits of a bit array.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, Copy, MemDbg, MemSize)]
+pub struct BitSlice<'a> {
+    bits: &'a [usize],
+    /// The bit position of the first bit of the view.
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Creates a view on the `len` bits of `bits` starting at bit position
+    /// `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view is not within `bits`.
+    pub fn new(bits: &'a [usize], offset: usize, len: usize) -> Self {
+        if offset + len > bits.len() * BITS {
+            panic!(
+                "Bit range end out of bounds: {} > {}",
+                offset + len,
+                bits.len() * BITS
+            );
+        }
+        Self { bits, offset, len }
+    }
+
+    /// Returns the number of bits in the view.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the view contains no bits.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at position `index` in the view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`BitSlice::len`].
+    pub fn get(&self, index: usize) -> bool {
+        if index >= self.len {
+            panic!("Bit index out of bounds: {} >= {}", index, self.len);
+        }
+        unsafe { self.get_unchecked(index) }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be between 0 (included) and [`BitSlice::len`] (excluded).
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> bool {
+        let pos = self.offset + index;
+        (self.bits.get_unchecked(pos / BITS) >> (pos % BITS)) & 1 != 0
+    }
+
+    /// Returns the `width` ≤ [`usize::BITS`] bits starting at position `pos`
+    /// as the lowest bits of a word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than [`usize::BITS`] or if the bits are
+    /// not within the view.
+    pub fn get_bits(&self, pos: usize, width: usize) -> usize {
+        if width > BITS {
+            panic!("Bit width too large: {} > {}", width, BITS);
+        }
+        if pos + width > self.len {
+            panic!(
+                "Bit range end out of bounds: {} > {}",
+                pos + width,
+                self.len
+            );
+        }
+        read_bits(self.bits, self.offset + pos, width)
+    }
+
+    /// Returns a view on a range of bits of this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is not within the view.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> BitSlice<'a> {
+        let (start, end) = bit_range(range, self.len);
+        BitSlice {
+            bits: self.bits,
+            offset: self.offset + start,
+            len: end - start,
+        }
+    }
+
+    /// Returns an iterator over the bits of this view as booleans.
+    #[inline(always)]
+    pub fn iter(&self) -> BitSliceIterator<'a> {
+        BitSliceIterator {
+            slice: *self,
+            next_bit_pos: 0,
+        }
+    }
+
+    /// Returns an iterator over the positions of the ones in this view.
+    ///
+    /// The iterator reads a word at a time.
+    #[inline(always)]
+    pub fn iter_ones(&self) -> BitSliceOnesIterator<'a> {
+        BitSliceOnesIterator {
+            slice: *self,
+            word_pos: 0,
+            word: self.get_bits(0, BITS.min(self.len)),
+        }
+    }
+
+    /// Returns a copy of this view as a [`BitVec`].
+    pub fn to_bit_vec(&self) -> BitVec {
+        let mut bits = vec![0; self.len.div_ceil(BITS)];
+        for (i, word) in bits.iter_mut().enumerate() {
+            let pos = i * BITS;
+            *word = self.get_bits(pos, BITS.min(self.len - pos));
+        }
+        unsafe { BitVec::from_raw_parts(bits, self.len) }
+    }
+}
+
+impl<'a> BitLength for BitSlice<'a> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a> BitCount for BitSlice<'a> {
+    fn count_ones(&self) -> usize {
+        (0..self.len)
+            .step_by(BITS)
+            .map(|pos| self.get_bits(pos, BITS.min(self.len - pos)).count_ones() as usize)
+            .sum()
+    }
+}
+
+impl<'a> Index<usize> for BitSlice<'a> {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            false => &false,
+            true => &true,
+        }
+    }
+}
+
+impl<'a, 'b> PartialEq<BitSlice<'b>> for BitSlice<'a> {
+    fn eq(&self, other: &BitSlice<'b>) -> bool {
+        self.len == other.len
+            && (0..self.len).step_by(BITS).all(|pos| {
+                let width = BITS.min(self.len - pos);
+                self.get_bits(pos, width) == other.get_bits(pos, width)
+            })
+    }
+}
+
+impl<'a> Eq for BitSlice<'a> {}
+
+impl<'a> IntoIterator for BitSlice<'a> {
+    type IntoIter = BitSliceIterator<'a>;
+    type Item = bool;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &BitSlice<'a> {
+    type IntoIter = BitSliceIterator<'a>;
+    type Item = bool;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the bits of a [`BitSlice`] as booleans.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct BitSliceIterator<'a> {
+    slice: BitSlice<'a>,
+    next_bit_pos: usize,
+}
+
+impl<'a> Iterator for BitSliceIterator<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.next_bit_pos == self.slice.len {
+            return None;
+        }
+        let bit = unsafe { self.slice.get_unchecked(self.next_bit_pos) };
+        self.next_bit_pos += 1;
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len - self.next_bit_pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for BitSliceIterator<'a> {}
+
+/// An iterator over the positions of the ones in a [`BitSlice`].
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct BitSliceOnesIterator<'a> {
+    slice: BitSlice<'a>,
+    /// The position in the view of the first bit of `word`.
+    word_pos: usize,
+    /// The bits of the view starting at `word_pos` not returned yet.
+    word: usize,
+}
+
+impl<'a> Iterator for BitSliceOnesIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.word_pos += BITS;
+            if self.word_pos >= self.slice.len {
+                return None;
+            }
+            self.word = self
+                .slice
+                .get_bits(self.word_pos, BITS.min(self.slice.len - self.word_pos));
+        }
+        let res = self.word_pos + self.word.trailing_zeros() as usize;
+        // clear the lowest bit set
+        self.word &= self.word - 1;
+        Some(res)
+    }
+}
@@ -26,6 +26,20 @@
 //!
 //! It is possible to juggle between the three flavors using [`From`]/[`Into`].
 //!
+//! Bits are stored by default in `usize` words, but any unsigned [`Word`] type
+//! can be used: for example, `BitVec<Vec<u32>>` stores bits in `u32` words,
+//! and `AtomicBitVec<Vec<AtomicU32>>` is its atomic counterpart. Backends
+//! expose their words through the [`AsWords`]/[`AsWordsMut`] and
+//! [`AsAtomicWords`] traits. Constructors such as [`BitVec::new`] build
+//! `usize`-based bit vectors; bit vectors with other word types can be obtained
+//! using [`Default`], [`FromIterator`], [`BitVec::resize`], or
+//! [`BitVec::from_raw_parts`]. Since the backend type is part of the type of
+//! the bit vector, [ε-serde](epserde) records the word type, and a bit vector
+//! cannot be deserialized with a different word type.
+//!
+//! Borrowed views on arbitrary ranges of bits of a [`BitVec`] are available
+//! as [`BitSlice`]s using [`BitVec::slice`].
+//!
 //! # Examples
 //!
 //! ```rust
@@ -66,22 +80,38 @@
 //! // If we create an artifically dirty bit vector, everything still works.
 //! let ones = [usize::MAX; 2];
 //! assert_eq!(unsafe { BitVec::from_raw_parts(ones, 1) }.count_ones(), 1);
+//!
+//! // Bit vectors with other word types
+//! let mut b: BitVec<Vec<u8>> = BitVec::default();
+//! b.resize(10, true);
+//! b.set(3, false);
+//! assert_eq!(b.count_ones(), 9);
+//! assert_eq!(b.as_ref().len(), 2);
 //! ```
 
-use common_traits::{IntoAtomic, SelectInWord};
+use common_traits::{AsBytes, Atomic, AtomicInteger, IntoAtomic};
 #[allow(unused_imports)] // this is in the std prelude but not in no_std!
 use core::borrow::BorrowMut;
 use core::fmt;
+use epserde::ser::{Serialize, WriteWithNames};
+use epserde::traits::MaxSizeOf;
 use epserde::*;
 use mem_dbg::*;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 use std::{
-    ops::Index,
+    iter::FusedIterator,
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not, RangeBounds,
+    },
+    path::Path,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+use super::bit_slice::{bit_range, write_bits, BitSlice};
+use super::mmap_backend::MmapBackend;
 use crate::traits::rank_sel::*;
+use crate::traits::Word;
 
 const BITS: usize = usize::BITS as usize;
 
@@ -154,6 +184,17 @@ macro_rules! bit_vec {
     };
 }
 
+macro_rules! panic_if_different_lengths {
+    ($len: expr, $other_len: expr) => {
+        if $len != $other_len {
+            panic!(
+                "Bit vectors have different lengths: {} != {}",
+                $len, $other_len
+            )
+        }
+    };
+}
+
 macro_rules! panic_if_out_of_bounds {
     ($index: expr, $len: expr) => {
         if $index >= $len {
@@ -203,7 +244,7 @@ impl<B> BitVec<B> {
     }
 }
 
-impl<B: AsRef<[usize]>> BitVec<B> {
+impl<W: Word, B: AsWords<Word = W>> BitVec<B> {
     pub fn get(&self, index: usize) -> bool {
         panic_if_out_of_bounds!(index, self.len);
         unsafe { self.get_unchecked(index) }
@@ -213,13 +254,38 @@ impl<B: AsRef<[usize]>> BitVec<B> {
     ///
     /// `index` must be between 0 (included) and [`BitVec::len`] (excluded).
     pub unsafe fn get_unchecked(&self, index: usize) -> bool {
-        let word_index = index / BITS;
-        let word = self.bits.as_ref().get_unchecked(word_index);
-        (word >> (index % BITS)) & 1 != 0
+        let word_index = index / W::BITS;
+        let word = *self.bits.as_words().get_unchecked(word_index);
+        (word >> (index % W::BITS)) & W::ONE != W::ZERO
     }
 }
 
-impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
+impl<B: AsRef<[usize]>> BitVec<B> {
+    /// Returns the `width` ≤ [`usize::BITS`] bits starting at position `pos`
+    /// as the lowest bits of a word.
+    ///
+    /// The bits need not be aligned to a word boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than [`usize::BITS`] or if the bits are
+    /// not within the bit vector.
+    pub fn get_bits(&self, pos: usize, width: usize) -> usize {
+        self.slice(..).get_bits(pos, width)
+    }
+
+    /// Returns a borrowed view on a range of bits of the bit vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is not within the bit vector.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> BitSlice<'_> {
+        let (start, end) = bit_range(range, self.len);
+        BitSlice::new(self.bits.as_ref(), start, end - start)
+    }
+}
+
+impl<W: Word, B: AsWordsMut<Word = W>> BitVec<B> {
     pub fn set(&mut self, index: usize, value: bool) {
         panic_if_out_of_bounds!(index, self.len);
         unsafe { self.set_unchecked(index, value) }
@@ -230,15 +296,15 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
     /// `index` must be between 0 (included) and [`BitVec::len`] (excluded).
     #[inline(always)]
     pub unsafe fn set_unchecked(&mut self, index: usize, value: bool) {
-        let word_index = index / BITS;
-        let bit_index = index % BITS;
-        let bits = self.bits.as_mut();
+        let word_index = index / W::BITS;
+        let bit_index = index % W::BITS;
+        let bits = self.bits.as_words_mut();
         // TODO: no test?
         // For constant values, this should be inlined with no test.
         if value {
-            *bits.get_unchecked_mut(word_index) |= 1 << bit_index;
+            *bits.get_unchecked_mut(word_index) |= W::ONE << bit_index;
         } else {
-            *bits.get_unchecked_mut(word_index) &= !(1 << bit_index);
+            *bits.get_unchecked_mut(word_index) &= !(W::ONE << bit_index);
         }
     }
 
@@ -246,10 +312,10 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
     ///
     /// If the feature "rayon" is enabled, this method is computed in parallel.
     pub fn fill(&mut self, value: bool) {
-        let full_words = self.len() / BITS;
-        let residual = self.len % BITS;
-        let bits = self.bits.as_mut();
-        let word_value = if value { !0 } else { 0 };
+        let full_words = self.len() / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_words_mut();
+        let word_value = if value { !W::ZERO } else { W::ZERO };
 
         #[cfg(feature = "rayon")]
         {
@@ -264,7 +330,7 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
         }
 
         if residual != 0 {
-            let mask = (1 << residual) - 1;
+            let mask = (W::ONE << residual) - W::ONE;
             bits[full_words] = (bits[full_words] & !mask) | (word_value & mask);
         }
     }
@@ -273,9 +339,9 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
     ///
     /// If the feature "rayon" is enabled, this method is computed in parallel.
     pub fn flip(&mut self) {
-        let full_words = self.len() / BITS;
-        let residual = self.len % BITS;
-        let bits = self.bits.as_mut();
+        let full_words = self.len() / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_words_mut();
 
         #[cfg(feature = "rayon")]
         {
@@ -288,10 +354,179 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
         }
 
         if residual != 0 {
-            let mask = (1 << residual) - 1;
+            let mask = (W::ONE << residual) - W::ONE;
             bits[full_words] = (bits[full_words] & !mask) | (!bits[full_words] & mask);
         }
     }
+
+    /// Applies word by word a binary operation to this bit vector and
+    /// another bit vector of the same length, storing the result in this bit
+    /// vector.
+    ///
+    /// The bits of the backend outside the bit vector are not modified.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    fn apply_in_place<C: AsWords<Word = W>>(
+        &mut self,
+        other: &BitVec<C>,
+        op: impl Fn(W, W) -> W + Sync + Send,
+    ) {
+        panic_if_different_lengths!(self.len, other.len);
+        let full_words = self.len / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_words_mut();
+        let other = other.bits.as_words();
+
+        #[cfg(feature = "rayon")]
+        {
+            bits[..full_words]
+                .par_iter_mut()
+                .zip(other[..full_words].par_iter())
+                .for_each(|(x, &y)| *x = op(*x, y));
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            bits[..full_words]
+                .iter_mut()
+                .zip(other[..full_words].iter())
+                .for_each(|(x, &y)| *x = op(*x, y));
+        }
+
+        if residual != 0 {
+            let mask = (W::ONE << residual) - W::ONE;
+            let last_word = bits[full_words];
+            bits[full_words] = (last_word & !mask) | (op(last_word, other[full_words]) & mask);
+        }
+    }
+
+    /// Computes in place the intersection of this bit vector with another
+    /// bit vector of the same length.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn and_assign<C: AsWords<Word = W>>(&mut self, other: &BitVec<C>) {
+        self.apply_in_place(other, |x, y| x & y);
+    }
+
+    /// Computes in place the union of this bit vector with another bit
+    /// vector of the same length.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn or_assign<C: AsWords<Word = W>>(&mut self, other: &BitVec<C>) {
+        self.apply_in_place(other, |x, y| x | y);
+    }
+
+    /// Computes in place the symmetric difference of this bit vector with
+    /// another bit vector of the same length.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn xor_assign<C: AsWords<Word = W>>(&mut self, other: &BitVec<C>) {
+        self.apply_in_place(other, |x, y| x ^ y);
+    }
+
+    /// Computes in place the difference between this bit vector and another
+    /// bit vector of the same length, that is, clears the bits that are set
+    /// in `other`.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn and_not_assign<C: AsWords<Word = W>>(&mut self, other: &BitVec<C>) {
+        self.apply_in_place(other, |x, y| x & !y);
+    }
+}
+
+impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
+    /// Sets the `width` ≤ [`usize::BITS`] bits starting at position `pos` to
+    /// the lowest bits of `value`.
+    ///
+    /// The bits need not be aligned to a word boundary, and the other bits of
+    /// the bit vector are not modified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than [`usize::BITS`], if the bits are not
+    /// within the bit vector, or if `value` does not fit in `width` bits.
+    pub fn set_bits(&mut self, pos: usize, width: usize, value: usize) {
+        if width > BITS {
+            panic!("Bit width too large: {} > {}", width, BITS);
+        }
+        if pos + width > self.len {
+            panic!(
+                "Bit range end out of bounds: {} > {}",
+                pos + width,
+                self.len
+            );
+        }
+        if width < BITS && value >> width != 0 {
+            panic!("Value {} does not fit in {} bits", value, width);
+        }
+        write_bits(self.bits.as_mut(), pos, width, value);
+    }
+
+    /// Copies the bits of `src` into a range of bits of the bit vector.
+    ///
+    /// Bits are copied a word at a time; neither range needs to be aligned to
+    /// a word boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is not within the bit vector or if its length is
+    /// different from the length of `src`.
+    pub fn copy_from_slice(&mut self, dst_range: impl RangeBounds<usize>, src: &BitSlice) {
+        let (start, end) = bit_range(dst_range, self.len);
+        panic_if_different_lengths!(end - start, src.len());
+        let bits = self.bits.as_mut();
+        for pos in (0..src.len()).step_by(BITS) {
+            let width = BITS.min(src.len() - pos);
+            write_bits(bits, start + pos, width, src.get_bits(pos, width));
+        }
+    }
+}
+
+impl BitVec<MmapBackend> {
+    /// Creates a new zero-initialized bit vector of length `len` whose bits
+    /// are stored in a writable memory mapping of the file at `path`, which
+    /// is created or truncated.
+    ///
+    /// After a call to [`flush`](BitVec::flush), the file can be loaded by
+    /// ε-serde as a `BitVec<Vec<usize>>`. See the
+    /// documentation of [memory-mapped backends](super::mmap_backend) for
+    /// more details.
+    pub fn new_mmap(path: impl AsRef<Path>, len: usize) -> anyhow::Result<Self> {
+        let n_of_words = len.div_ceil(BITS);
+        let bits = MmapBackend::new::<BitVec<Vec<usize>>>(path, n_of_words, size_of::<usize>())?;
+        Ok(Self { bits, len })
+    }
+}
+
+impl<W: Word + MaxSizeOf> BitVec<MmapBackend<W>>
+where
+    BitVec<Vec<W>>: Serialize,
+{
+    /// Writes in the backing file the ε-serde serialization of this bit
+    /// vector as a `BitVec<Vec<W>>` and flushes the mapping.
+    ///
+    /// The bit vector can be further modified and flushed again.
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        let len = self.len;
+        self.bits
+            .flush::<BitVec<Vec<W>>>(|writer| writer.write("len", &len))
+    }
 }
 
 impl BitVec<Vec<usize>> {
@@ -324,21 +559,25 @@ impl BitVec<Vec<usize>> {
             len: 0,
         }
     }
+}
 
+impl<W: Word> BitVec<Vec<W>> {
     pub fn capacity(&self) -> usize {
-        self.bits.capacity() * BITS
+        self.bits.capacity() * W::BITS
     }
 
     pub fn push(&mut self, b: bool) {
-        if self.bits.len() * BITS == self.len {
-            self.bits.push(0);
+        if self.bits.len() * W::BITS == self.len {
+            self.bits.push(W::ZERO);
         }
-        let word_index = self.len / BITS;
-        let bit_index = self.len % BITS;
+        let word_index = self.len / W::BITS;
+        let bit_index = self.len % W::BITS;
         // Clear bit
-        self.bits[word_index] &= !(1 << bit_index);
+        self.bits[word_index] &= !(W::ONE << bit_index);
         // Set bit
-        self.bits[word_index] |= (b as usize) << bit_index;
+        if b {
+            self.bits[word_index] |= W::ONE << bit_index;
+        }
         self.len += 1;
     }
 
@@ -347,16 +586,16 @@ impl BitVec<Vec<usize>> {
             return None;
         }
         self.len -= 1;
-        let word_index = self.len / BITS;
-        let bit_index = self.len % BITS;
-        Some((self.bits[word_index] >> bit_index) & 1 != 0)
+        let word_index = self.len / W::BITS;
+        let bit_index = self.len % W::BITS;
+        Some((self.bits[word_index] >> bit_index) & W::ONE != W::ZERO)
     }
 
     pub fn resize(&mut self, new_len: usize, value: bool) {
         // TODO: rewrite by word
         if new_len > self.len {
-            if new_len > self.bits.len() * BITS {
-                self.bits.resize((new_len + BITS - 1) / BITS, 0);
+            if new_len > self.bits.len() * W::BITS {
+                self.bits.resize(new_len.div_ceil(W::BITS), W::ZERO);
             }
             for i in self.len..new_len {
                 unsafe {
@@ -377,11 +616,11 @@ impl<B> BitLength for BitVec<B> {
 
 /// If the feature "rayon" is enabled, [`count_ones`](BitCount::count_ones) is
 /// computed in parallel.
-impl<B: AsRef<[usize]>> BitCount for BitVec<B> {
+impl<W: Word, B: AsWords<Word = W>> BitCount for BitVec<B> {
     fn count_ones(&self) -> usize {
-        let full_words = self.len() / BITS;
-        let residual = self.len() % BITS;
-        let bits = self.bits.as_ref();
+        let full_words = self.len() / W::BITS;
+        let residual = self.len() % W::BITS;
+        let bits = self.bits.as_words();
         let mut num_ones;
 
         #[cfg(feature = "rayon")]
@@ -401,14 +640,14 @@ impl<B: AsRef<[usize]>> BitCount for BitVec<B> {
         }
 
         if residual != 0 {
-            num_ones += (self.as_ref()[full_words] << (BITS - residual)).count_ones() as usize
+            num_ones += (bits[full_words] << (W::BITS - residual)).count_ones() as usize
         }
 
         num_ones
     }
 }
 
-impl<B: AsRef<[usize]>> Index<usize> for BitVec<B> {
+impl<B: AsWords> Index<usize> for BitVec<B> {
     type Output = bool;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -419,7 +658,7 @@ impl<B: AsRef<[usize]>> Index<usize> for BitVec<B> {
     }
 }
 
-impl Extend<bool> for BitVec<Vec<usize>> {
+impl<W: Word> Extend<bool> for BitVec<Vec<W>> {
     fn extend<T>(&mut self, i: T)
     where
         T: IntoIterator<Item = bool>,
@@ -430,95 +669,107 @@ impl Extend<bool> for BitVec<Vec<usize>> {
     }
 }
 
-impl FromIterator<bool> for BitVec<Vec<usize>> {
+impl<W: Word> FromIterator<bool> for BitVec<Vec<W>> {
     fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
-        let mut res = Self::new(0);
+        let mut res = Self::default();
         res.extend(iter);
         res
     }
 }
 
-impl<B: AsRef<[usize]>> RankHinted<64> for BitVec<B> {
+impl<B: AsWords> RankHinted<64> for BitVec<B> {
     #[inline(always)]
     unsafe fn rank_hinted(&self, pos: usize, hint_pos: usize, hint_rank: usize) -> usize {
-        let bits = self.as_ref();
         let mut rank = hint_rank;
         let mut hint_pos = hint_pos;
 
         debug_assert!(
-            hint_pos < bits.len(),
+            hint_pos * 64 < self.bits.as_words().len() * B::Word::BITS,
             "hint_pos: {}, len: {}",
             hint_pos,
-            bits.len()
+            self.bits.as_words().len() * B::Word::BITS / 64
         );
 
         while (hint_pos + 1) * 64 <= pos {
-            rank += bits.get_unchecked(hint_pos).count_ones() as usize;
+            rank += self.usize_word_unchecked(hint_pos).count_ones() as usize;
             hint_pos += 1;
         }
 
-        rank + (bits.get_unchecked(hint_pos) & ((1 << (pos % 64)) - 1)).count_ones() as usize
+        rank + (self.usize_word_unchecked(hint_pos) & ((1 << (pos % 64)) - 1)).count_ones() as usize
     }
 }
 
-impl<B: AsRef<[usize]>> SelectHinted for BitVec<B> {
+impl<W: Word, B: AsWords<Word = W>> SelectHinted for BitVec<B> {
     unsafe fn select_hinted(&self, rank: usize, hint_pos: usize, hint_rank: usize) -> usize {
-        let mut word_index = hint_pos / BITS;
-        let bit_index = hint_pos % BITS;
+        let bits = self.bits.as_words();
+        let mut word_index = hint_pos / W::BITS;
+        let bit_index = hint_pos % W::BITS;
         let mut residual = rank - hint_rank;
-        let mut word = (self.as_ref().get_unchecked(word_index) >> bit_index) << bit_index;
+        let mut word = (*bits.get_unchecked(word_index) >> bit_index) << bit_index;
         loop {
             let bit_count = word.count_ones() as usize;
             if residual < bit_count {
-                return word_index * BITS + word.select_in_word(residual);
+                return word_index * W::BITS + word.select_in_word(residual);
             }
             word_index += 1;
-            word = *self.as_ref().get_unchecked(word_index);
+            word = *bits.get_unchecked(word_index);
             residual -= bit_count;
         }
     }
 }
 
-impl<B: AsRef<[usize]>> SelectZeroHinted for BitVec<B> {
+impl<W: Word, B: AsWords<Word = W>> SelectZeroHinted for BitVec<B> {
     unsafe fn select_zero_hinted(&self, rank: usize, hint_pos: usize, hint_rank: usize) -> usize {
-        let mut word_index = hint_pos / BITS;
-        let bit_index = hint_pos % BITS;
+        let bits = self.bits.as_words();
+        let mut word_index = hint_pos / W::BITS;
+        let bit_index = hint_pos % W::BITS;
         let mut residual = rank - hint_rank;
-        let mut word = (!*self.as_ref().get_unchecked(word_index) >> bit_index) << bit_index;
+        let mut word = (!*bits.get_unchecked(word_index) >> bit_index) << bit_index;
         loop {
             let bit_count = word.count_ones() as usize;
             if residual < bit_count {
-                return word_index * BITS + word.select_in_word(residual);
+                return word_index * W::BITS + word.select_in_word(residual);
             }
             word_index += 1;
-            word = !self.as_ref().get_unchecked(word_index);
+            word = !*bits.get_unchecked(word_index);
             residual -= bit_count;
         }
     }
 }
 
-impl<B: AsRef<[usize]>, C: AsRef<[usize]>> PartialEq<BitVec<C>> for BitVec<B> {
+impl<W: Word, B: AsWords<Word = W>, C: AsWords<Word = W>> PartialEq<BitVec<C>> for BitVec<B> {
     fn eq(&self, other: &BitVec<C>) -> bool {
         let len = self.len();
         if len != other.len() {
             return false;
         }
 
-        let full_words = len / BITS;
-        if self.as_ref()[..full_words] != other.as_ref()[..full_words] {
+        let bits = self.bits.as_words();
+        let other = other.bits.as_words();
+        let full_words = len / W::BITS;
+        if bits[..full_words] != other[..full_words] {
             return false;
         }
 
-        let residual = len % BITS;
+        let residual = len % W::BITS;
 
-        residual == 0
-            || (self.as_ref()[full_words] ^ other.as_ref()[full_words]) << (BITS - residual) == 0
+        residual == 0 || (bits[full_words] ^ other[full_words]) << (W::BITS - residual) == W::ZERO
     }
 }
 
-impl Eq for BitVec<Vec<usize>> {}
+impl<W: Word> Eq for BitVec<Vec<W>> {}
+
+impl<W: Word> Default for BitVec<Vec<W>> {
+    /// Returns an empty bit vector.
+    fn default() -> Self {
+        Self {
+            bits: Vec::new(),
+            len: 0,
+        }
+    }
+}
 
-impl<B: AsRef<[usize]>> fmt::Display for BitVec<B> {
+impl<B: AsWords> fmt::Display for BitVec<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "[")?;
         for b in self {
@@ -537,7 +788,7 @@ pub struct BitIterator<'a, B> {
     next_bit_pos: usize,
 }
 
-impl<'a, B: AsRef<[usize]>> IntoIterator for &'a BitVec<B> {
+impl<'a, B: AsWords> IntoIterator for &'a BitVec<B> {
     type IntoIter = BitIterator<'a, B>;
     type Item = bool;
 
@@ -550,126 +801,133 @@ impl<'a, B: AsRef<[usize]>> IntoIterator for &'a BitVec<B> {
     }
 }
 
-impl<'a, B: AsRef<[usize]>> Iterator for BitIterator<'a, B> {
+impl<'a, W: Word, B: AsWords<Word = W>> Iterator for BitIterator<'a, B> {
     type Item = bool;
     fn next(&mut self) -> Option<bool> {
         if self.next_bit_pos == self.len {
             return None;
         }
-        let word_idx = self.next_bit_pos / BITS;
-        let bit_idx = self.next_bit_pos % BITS;
-        let word = unsafe { *self.bits.as_ref().get_unchecked(word_idx) };
-        let bit = (word >> bit_idx) & 1;
+        let word_idx = self.next_bit_pos / W::BITS;
+        let bit_idx = self.next_bit_pos % W::BITS;
+        let word = unsafe { *self.bits.as_words().get_unchecked(word_idx) };
+        let bit = (word >> bit_idx) & W::ONE;
         self.next_bit_pos += 1;
-        Some(bit != 0)
+        Some(bit != W::ZERO)
     }
 }
 
-/// An iterator over the positions of the ones in a bit vector.
-#[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct OnesIterator<'a, B> {
-    bits: &'a B,
-    len: usize,
-    word_idx: usize,
-    /// This is a usize because BitVec is currently implemented only for `Vec<usize>` and `&[usize]`.
-    word: usize,
+/// Returns the position of the first one (or zero, if `zeros` is true) in
+/// the range `from..to` of `bits`, if any.
+///
+/// Words are scanned one at a time, so runs of zeros (ones) are skipped in a
+/// single step. `to` must be at most the number of bits in `bits`.
+#[inline(always)]
+fn next_in<W: Word>(bits: &[W], from: usize, to: usize, zeros: bool) -> Option<usize> {
+    if from >= to {
+        return None;
+    }
+    let flip = if zeros { !W::ZERO } else { W::ZERO };
+    let mut word_idx = from / W::BITS;
+    let mut word =
+        (unsafe { *bits.get_unchecked(word_idx) } ^ flip) & (!W::ZERO << (from % W::BITS));
+    loop {
+        if word != W::ZERO {
+            let res = word_idx * W::BITS + word.trailing_zeros() as usize;
+            return (res < to).then_some(res);
+        }
+        word_idx += 1;
+        if word_idx * W::BITS >= to {
+            return None;
+        }
+        word = unsafe { *bits.get_unchecked(word_idx) } ^ flip;
+    }
 }
 
-impl<'a, B: AsRef<[usize]>> OnesIterator<'a, B> {
-    pub fn new(bits: &'a B, len: usize) -> Self {
-        let word = if bits.as_ref().is_empty() {
-            0
-        } else {
-            unsafe { *bits.as_ref().get_unchecked(0) }
-        };
-        Self {
-            bits,
-            len,
-            word_idx: 0,
-            word,
+/// Returns the position of the last one (or zero, if `zeros` is true) in
+/// the range `from..to` of `bits`, if any.
+///
+/// See [`next_in`].
+#[inline(always)]
+fn prev_in<W: Word>(bits: &[W], from: usize, to: usize, zeros: bool) -> Option<usize> {
+    if from >= to {
+        return None;
+    }
+    let flip = if zeros { !W::ZERO } else { W::ZERO };
+    let last = to - 1;
+    let mut word_idx = last / W::BITS;
+    let mut word = (unsafe { *bits.get_unchecked(word_idx) } ^ flip)
+        & (!W::ZERO >> (W::BITS - 1 - last % W::BITS));
+    loop {
+        if word != W::ZERO {
+            let res = word_idx * W::BITS + W::BITS - 1 - word.leading_zeros() as usize;
+            return (res >= from).then_some(res);
+        }
+        if word_idx * W::BITS <= from {
+            return None;
         }
+        word_idx -= 1;
+        word = unsafe { *bits.get_unchecked(word_idx) } ^ flip;
     }
 }
 
-impl<'a, B: AsRef<[usize]>> Iterator for OnesIterator<'a, B> {
-    type Item = usize;
+macro_rules! impl_positions_iterator {
+    ($name: ident, $zeros: expr, $what: literal) => {
+        #[doc = concat!("An iterator over the positions of the ", $what, " in a bit vector.")]
+        ///
+        /// The iterator scans the bit vector a word at a time, and it is
+        /// double-ended, so it can be reversed using [`Iterator::rev`].
+        #[derive(Debug, Clone, MemDbg, MemSize)]
+        pub struct $name<'a, B> {
+            bits: &'a B,
+            /// The first position not returned yet.
+            front: usize,
+            /// The position after the last position not returned yet.
+            back: usize,
+        }
+
+        impl<'a, B: AsWords> $name<'a, B> {
+            pub fn new(bits: &'a B, len: usize) -> Self {
+                Self::from_pos(bits, len, 0)
+            }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // find the next word with ones
-        while self.word == 0 {
-            self.word_idx += 1;
-            if self.word_idx == self.bits.as_ref().len() {
-                return None;
+            #[doc = concat!("Creates an iterator over the positions of the ", $what, " starting")]
+            /// at position `pos` (or at `len`, if `pos` is larger).
+            pub fn from_pos(bits: &'a B, len: usize, pos: usize) -> Self {
+                Self {
+                    bits,
+                    front: pos.min(len),
+                    back: len,
+                }
             }
-            self.word = unsafe { *self.bits.as_ref().get_unchecked(self.word_idx) };
-        }
-        // find the lowest bit set index in the word
-        let bit_idx = self.word.trailing_zeros() as usize;
-        // compute the global bit index
-        let res = (self.word_idx * BITS) + bit_idx;
-        if res >= self.len {
-            None
-        } else {
-            // clear the lowest bit set
-            self.word &= self.word - 1;
-            Some(res)
         }
-    }
-}
 
-/// An iterator over the positions of the zeros in a bit vector.
-#[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct ZerosIterator<'a, B> {
-    bits: &'a B,
-    len: usize,
-    word_idx: usize,
-    /// This is a usize because BitVec is currently implemented only for `Vec<usize>` and `&[usize]`.
-    word: usize,
-}
+        impl<'a, B: AsWords> Iterator for $name<'a, B> {
+            type Item = usize;
 
-impl<'a, B: AsRef<[usize]>> ZerosIterator<'a, B> {
-    pub fn new(bits: &'a B, len: usize) -> Self {
-        let word = if bits.as_ref().is_empty() {
-            0
-        } else {
-            unsafe { !*bits.as_ref().get_unchecked(0) }
-        };
-        Self {
-            bits,
-            len,
-            word_idx: 0,
-            word,
+            fn next(&mut self) -> Option<Self::Item> {
+                let res = next_in(self.bits.as_words(), self.front, self.back, $zeros);
+                // once done, the iterator stays empty
+                self.front = res.map_or(self.back, |pos| pos + 1);
+                res
+            }
         }
-    }
-}
 
-impl<'a, B: AsRef<[usize]>> Iterator for ZerosIterator<'a, B> {
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // find the next flipped word with zeros
-        while self.word == 0 {
-            self.word_idx += 1;
-            if self.word_idx == self.bits.as_ref().len() {
-                return None;
+        impl<'a, B: AsWords> DoubleEndedIterator for $name<'a, B> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let res = prev_in(self.bits.as_words(), self.front, self.back, $zeros);
+                self.back = res.unwrap_or(self.front);
+                res
             }
-            self.word = unsafe { !*self.bits.as_ref().get_unchecked(self.word_idx) };
-        }
-        // find the lowest zero bit index in the word
-        let bit_idx = self.word.trailing_zeros() as usize;
-        // compute the global bit index
-        let res = (self.word_idx * BITS) + bit_idx;
-        if res >= self.len {
-            None
-        } else {
-            // clear the lowest bit set
-            self.word &= self.word - 1;
-            Some(res)
         }
-    }
+
+        impl<'a, B: AsWords> FusedIterator for $name<'a, B> {}
+    };
 }
 
-impl<B: AsRef<[usize]>> BitVec<B> {
+impl_positions_iterator!(OnesIterator, false, "ones");
+impl_positions_iterator!(ZerosIterator, true, "zeros");
+
+impl<B: AsWords> BitVec<B> {
     // Returns an iterator over the bits of this bit vector.
     #[inline(always)]
     pub fn iter(&self) -> BitIterator<B> {
@@ -685,6 +943,175 @@ impl<B: AsRef<[usize]>> BitVec<B> {
     pub fn iter_zeros(&self) -> ZerosIterator<B> {
         ZerosIterator::new(&self.bits, self.len)
     }
+
+    /// Returns an iterator over the positions of the ones in this bit vector
+    /// starting at position `pos`.
+    ///
+    /// If `pos` is larger than the length, the iterator is empty.
+    pub fn iter_ones_from(&self, pos: usize) -> OnesIterator<'_, B> {
+        OnesIterator::from_pos(&self.bits, self.len, pos)
+    }
+
+    /// Returns an iterator over the positions of the zeros in this bit vector
+    /// starting at position `pos`.
+    ///
+    /// If `pos` is larger than the length, the iterator is empty.
+    pub fn iter_zeros_from(&self, pos: usize) -> ZerosIterator<'_, B> {
+        ZerosIterator::from_pos(&self.bits, self.len, pos)
+    }
+
+    /// Returns the position of the first one at position `pos` or greater,
+    /// or `None` if there is no such one.
+    ///
+    /// The bit vector is scanned a word at a time.
+    pub fn next_one(&self, pos: usize) -> Option<usize> {
+        next_in(self.bits.as_words(), pos, self.len, false)
+    }
+
+    /// Returns the position of the last one at position `pos` or smaller,
+    /// or `None` if there is no such one.
+    ///
+    /// The bit vector is scanned a word at a time.
+    pub fn prev_one(&self, pos: usize) -> Option<usize> {
+        prev_in(
+            self.bits.as_words(),
+            0,
+            self.len.min(pos.saturating_add(1)),
+            false,
+        )
+    }
+
+    /// Returns the position of the first zero at position `pos` or greater,
+    /// or `None` if there is no such zero.
+    ///
+    /// The bit vector is scanned a word at a time.
+    pub fn next_zero(&self, pos: usize) -> Option<usize> {
+        next_in(self.bits.as_words(), pos, self.len, true)
+    }
+
+    /// Returns the position of the last zero at position `pos` or smaller,
+    /// or `None` if there is no such zero.
+    ///
+    /// The bit vector is scanned a word at a time.
+    pub fn prev_zero(&self, pos: usize) -> Option<usize> {
+        prev_in(
+            self.bits.as_words(),
+            0,
+            self.len.min(pos.saturating_add(1)),
+            true,
+        )
+    }
+}
+
+impl<W: Word, B: AsWords<Word = W>> BitVec<B> {
+    /// Returns a copy of this bit vector with a [`Vec`] backend, in which
+    /// the bits outside the bit vector are zero.
+    fn to_owned_clean(&self) -> BitVec<Vec<W>> {
+        let mut bits = self.bits.as_words()[..self.len.div_ceil(W::BITS)].to_vec();
+        let residual = self.len % W::BITS;
+        if residual != 0 {
+            *bits.last_mut().unwrap() &= (W::ONE << residual) - W::ONE;
+        }
+        BitVec {
+            bits,
+            len: self.len,
+        }
+    }
+
+    /// Returns the intersection of this bit vector and another bit vector of
+    /// the same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn and<C: AsWords<Word = W>>(&self, other: &BitVec<C>) -> BitVec<Vec<W>> {
+        let mut result = self.to_owned_clean();
+        result.and_assign(other);
+        result
+    }
+
+    /// Returns the union of this bit vector and another bit vector of the
+    /// same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn or<C: AsWords<Word = W>>(&self, other: &BitVec<C>) -> BitVec<Vec<W>> {
+        let mut result = self.to_owned_clean();
+        result.or_assign(other);
+        result
+    }
+
+    /// Returns the symmetric difference of this bit vector and another bit
+    /// vector of the same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn xor<C: AsWords<Word = W>>(&self, other: &BitVec<C>) -> BitVec<Vec<W>> {
+        let mut result = self.to_owned_clean();
+        result.xor_assign(other);
+        result
+    }
+
+    /// Returns the difference between this bit vector and another bit vector
+    /// of the same length, that is, this bit vector with the bits set in
+    /// `other` cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn and_not<C: AsWords<Word = W>>(&self, other: &BitVec<C>) -> BitVec<Vec<W>> {
+        let mut result = self.to_owned_clean();
+        result.and_not_assign(other);
+        result
+    }
+
+    /// Returns the complement of this bit vector.
+    ///
+    /// To complement a bit vector in place, use [`flip`](BitVec::flip).
+    pub fn not(&self) -> BitVec<Vec<W>> {
+        let mut result = self.to_owned_clean();
+        result.flip();
+        result
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($trait:ident, $method:ident, $op:ident, $assign_trait:ident, $assign_method:ident, $assign_op:ident) => {
+        impl<W: Word, B: AsWords<Word = W>, C: AsWords<Word = W>> $trait<&BitVec<C>>
+            for &BitVec<B>
+        {
+            type Output = BitVec<Vec<W>>;
+
+            #[inline(always)]
+            fn $method(self, other: &BitVec<C>) -> Self::Output {
+                BitVec::$op(self, other)
+            }
+        }
+
+        impl<W: Word, B: AsWordsMut<Word = W>, C: AsWords<Word = W>> $assign_trait<&BitVec<C>>
+            for BitVec<B>
+        {
+            #[inline(always)]
+            fn $assign_method(&mut self, other: &BitVec<C>) {
+                BitVec::$assign_op(self, other)
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, and, BitAndAssign, bitand_assign, and_assign);
+impl_bit_op!(BitOr, bitor, or, BitOrAssign, bitor_assign, or_assign);
+impl_bit_op!(BitXor, bitxor, xor, BitXorAssign, bitxor_assign, xor_assign);
+
+impl<W: Word, B: AsWords<Word = W>> Not for &BitVec<B> {
+    type Output = BitVec<Vec<W>>;
+
+    #[inline(always)]
+    fn not(self) -> Self::Output {
+        BitVec::not(self)
+    }
 }
 
 #[derive(Debug, Clone, MemDbg, MemSize)]
@@ -717,7 +1144,7 @@ impl<B> AtomicBitVec<B> {
     }
 }
 
-impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
+impl<W: Word + IntoAtomic, B: AsAtomicWords<Word = W>> AtomicBitVec<B> {
     pub fn get(&self, index: usize, ordering: Ordering) -> bool {
         panic_if_out_of_bounds!(index, self.len);
         unsafe { self.get_unchecked(index, ordering) }
@@ -734,52 +1161,52 @@ impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
     }
 
     unsafe fn get_unchecked(&self, index: usize, ordering: Ordering) -> bool {
-        let word_index = index / BITS;
-        let bits = self.bits.as_ref();
+        let word_index = index / W::BITS;
+        let bits = self.bits.as_atomic_words();
         let word = bits.get_unchecked(word_index).load(ordering);
-        (word >> (index % BITS)) & 1 != 0
+        (word >> (index % W::BITS)) & W::ONE != W::ZERO
     }
     #[inline(always)]
     unsafe fn set_unchecked(&self, index: usize, value: bool, ordering: Ordering) {
-        let word_index = index / BITS;
-        let bit_index = index % BITS;
-        let bits = self.bits.as_ref();
+        let word_index = index / W::BITS;
+        let bit_index = index % W::BITS;
+        let bits = self.bits.as_atomic_words();
 
         // For constant values, this should be inlined with no test.
         if value {
             bits.get_unchecked(word_index)
-                .fetch_or(1 << bit_index, ordering);
+                .fetch_or(W::ONE << bit_index, ordering);
         } else {
             bits.get_unchecked(word_index)
-                .fetch_and(!(1 << bit_index), ordering);
+                .fetch_and(!(W::ONE << bit_index), ordering);
         }
     }
 
     #[inline(always)]
     unsafe fn swap_unchecked(&self, index: usize, value: bool, ordering: Ordering) -> bool {
-        let word_index = index / BITS;
-        let bit_index = index % BITS;
-        let bits = self.bits.as_ref();
+        let word_index = index / W::BITS;
+        let bit_index = index % W::BITS;
+        let bits = self.bits.as_atomic_words();
 
         let old_word = if value {
             bits.get_unchecked(word_index)
-                .fetch_or(1 << bit_index, ordering)
+                .fetch_or(W::ONE << bit_index, ordering)
         } else {
             bits.get_unchecked(word_index)
-                .fetch_and(!(1 << bit_index), ordering)
+                .fetch_and(!(W::ONE << bit_index), ordering)
         };
 
-        (old_word >> (bit_index)) & 1 != 0
+        (old_word >> bit_index) & W::ONE != W::ZERO
     }
 
     /// Set all bits to the given value.
     ///
     /// If the feature "rayon" is enabled, this method is computed in parallel.
     pub fn fill(&mut self, value: bool, ordering: Ordering) {
-        let full_words = self.len() / BITS;
-        let residual = self.len % BITS;
-        let bits = self.bits.as_ref();
-        let word_value = if value { !0 } else { 0 };
+        let full_words = self.len() / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_atomic_words();
+        let word_value = if value { !W::ZERO } else { W::ZERO };
 
         // Just to be sure, add a fence to ensure that we will see all the final
         // values
@@ -799,7 +1226,7 @@ impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
         }
 
         if residual != 0 {
-            let mask = (1 << residual) - 1;
+            let mask = (W::ONE << residual) - W::ONE;
             bits[full_words].store(
                 (bits[full_words].load(ordering) & !mask) | (word_value & mask),
                 ordering,
@@ -811,9 +1238,9 @@ impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
     ///
     /// If the feature "rayon" is enabled, this method is computed in parallel.
     pub fn flip(&mut self, ordering: Ordering) {
-        let full_words = self.len() / BITS;
-        let residual = self.len % BITS;
-        let bits = self.bits.as_ref();
+        let full_words = self.len() / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_atomic_words();
 
         // Just to be sure, add a fence to ensure that we will see all the final
         // values
@@ -822,22 +1249,126 @@ impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
         {
             bits[..full_words]
                 .par_iter()
-                .for_each(|x| _ = x.fetch_xor(!0, ordering));
+                .for_each(|x| _ = x.fetch_xor(!W::ZERO, ordering));
         }
 
         #[cfg(not(feature = "rayon"))]
         {
             bits[..full_words]
                 .iter()
-                .for_each(|x| _ = x.fetch_xor(!0, ordering));
+                .for_each(|x| _ = x.fetch_xor(!W::ZERO, ordering));
         }
 
         if residual != 0 {
-            let mask = (1 << residual) - 1;
+            let mask = (W::ONE << residual) - W::ONE;
             let last_word = bits[full_words].load(ordering);
             bits[full_words].store((last_word & !mask) | (!last_word & mask), ordering);
         }
     }
+
+    /// Applies word by word an atomic operation on this bit vector using the
+    /// words of another bit vector of the same length, masked so that the
+    /// bits of the backend outside the bit vector are not modified.
+    ///
+    /// `mask_out` is the value the bits of the second argument outside the bit
+    /// vector must be set to so that the corresponding bits are not modified.
+    fn apply_atomic<C: AsWords<Word = W>>(
+        &self,
+        other: &BitVec<C>,
+        mask_out: bool,
+        op: impl Fn(&B::AtomicWord, W) + Sync + Send,
+    ) {
+        panic_if_different_lengths!(self.len, other.len);
+        let full_words = self.len / W::BITS;
+        let residual = self.len % W::BITS;
+        let bits = self.bits.as_atomic_words();
+        let other = other.bits.as_words();
+
+        #[cfg(feature = "rayon")]
+        {
+            bits[..full_words]
+                .par_iter()
+                .zip(other[..full_words].par_iter())
+                .for_each(|(x, &y)| op(x, y));
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            bits[..full_words]
+                .iter()
+                .zip(other[..full_words].iter())
+                .for_each(|(x, &y)| op(x, y));
+        }
+
+        if residual != 0 {
+            let mask = (W::ONE << residual) - W::ONE;
+            let out = if mask_out { !mask } else { W::ZERO };
+            op(&bits[full_words], (other[full_words] & mask) | out);
+        }
+    }
+
+    /// Atomically sets the bits of this bit vector that are set in another
+    /// bit vector of the same length.
+    ///
+    /// Each word is modified atomically, but the operation on the whole bit
+    /// vector is not atomic. Differently from [`AtomicUsize::fetch_or`], the
+    /// previous content is not returned.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn fetch_or<C: AsWords<Word = W>>(&self, other: &BitVec<C>, ordering: Ordering) {
+        self.apply_atomic(other, false, |x, y| _ = x.fetch_or(y, ordering));
+    }
+
+    /// Atomically clears the bits of this bit vector that are not set in
+    /// another bit vector of the same length.
+    ///
+    /// Each word is modified atomically, but the operation on the whole bit
+    /// vector is not atomic. Differently from [`AtomicUsize::fetch_and`], the
+    /// previous content is not returned.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn fetch_and<C: AsWords<Word = W>>(&self, other: &BitVec<C>, ordering: Ordering) {
+        self.apply_atomic(other, true, |x, y| _ = x.fetch_and(y, ordering));
+    }
+
+    /// Atomically flips the bits of this bit vector that are set in another
+    /// bit vector of the same length.
+    ///
+    /// Each word is modified atomically, but the operation on the whole bit
+    /// vector is not atomic. Differently from [`AtomicUsize::fetch_xor`], the
+    /// previous content is not returned.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn fetch_xor<C: AsWords<Word = W>>(&self, other: &BitVec<C>, ordering: Ordering) {
+        self.apply_atomic(other, false, |x, y| _ = x.fetch_xor(y, ordering));
+    }
+
+    /// Atomically clears the bits of this bit vector that are set in another
+    /// bit vector of the same length.
+    ///
+    /// Each word is modified atomically, but the operation on the whole bit
+    /// vector is not atomic.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two bit vectors have different lengths.
+    pub fn fetch_and_not<C: AsWords<Word = W>>(&self, other: &BitVec<C>, ordering: Ordering) {
+        self.apply_atomic(other, false, |x, y| _ = x.fetch_and(!y, ordering));
+    }
 }
 
 impl AtomicBitVec<Vec<AtomicUsize>> {
@@ -869,7 +1400,7 @@ impl<B> BitLength for AtomicBitVec<B> {
     }
 }
 
-impl<B: AsRef<[AtomicUsize]>> Index<usize> for AtomicBitVec<B> {
+impl<B: AsAtomicWords> Index<usize> for AtomicBitVec<B> {
     type Output = bool;
 
     /// Shorthand for [`Self::get`] using [`Ordering::Relaxed`].
@@ -883,11 +1414,11 @@ impl<B: AsRef<[AtomicUsize]>> Index<usize> for AtomicBitVec<B> {
 
 /// If the feature "rayon" is enabled, [`count_ones`](BitCount::count_ones) is
 /// computed in parallel.
-impl<B: AsRef<[AtomicUsize]>> BitCount for AtomicBitVec<B> {
+impl<W: Word + IntoAtomic, B: AsAtomicWords<Word = W>> BitCount for AtomicBitVec<B> {
     fn count_ones(&self) -> usize {
-        let full_words = self.len() / BITS;
-        let residual = self.len() % BITS;
-        let bits = self.bits.as_ref();
+        let full_words = self.len() / W::BITS;
+        let residual = self.len() % W::BITS;
+        let bits = self.bits.as_atomic_words();
         let mut num_ones;
 
         // Just to be sure, add a fence to ensure that we will see all the final
@@ -910,8 +1441,8 @@ impl<B: AsRef<[AtomicUsize]>> BitCount for AtomicBitVec<B> {
         }
 
         if residual != 0 {
-            num_ones += (bits[full_words].load(Ordering::Relaxed) << (BITS - residual)).count_ones()
-                as usize
+            num_ones += (bits[full_words].load(Ordering::Relaxed) << (W::BITS - residual))
+                .count_ones() as usize
         }
 
         num_ones
@@ -1028,6 +1559,22 @@ impl<W, B: AsMut<[W]>> AsMut<[W]> for BitVec<B> {
     }
 }
 
+impl<B: AsWords> AsWords for BitVec<B> {
+    type Word = B::Word;
+
+    #[inline(always)]
+    fn as_words(&self) -> &[B::Word] {
+        self.bits.as_words()
+    }
+}
+
+impl<B: AsWordsMut> AsWordsMut for BitVec<B> {
+    #[inline(always)]
+    fn as_words_mut(&mut self) -> &mut [B::Word] {
+        self.bits.as_words_mut()
+    }
+}
+
 impl<W, B: AsRef<[W]>> AsRef<[W]> for AtomicBitVec<B> {
     #[inline(always)]
     fn as_ref(&self) -> &[W] {
@@ -1044,7 +1591,7 @@ pub struct AtomicBitIterator<'a, B> {
 
 // We implement [`IntoIterator`] for a mutable reference so no
 // outstanding references are allowed while iterating.
-impl<'a, B: AsRef<[AtomicUsize]>> IntoIterator for &'a mut AtomicBitVec<B> {
+impl<'a, B: AsAtomicWords> IntoIterator for &'a mut AtomicBitVec<B> {
     type IntoIter = AtomicBitIterator<'a, B>;
     type Item = bool;
 
@@ -1057,27 +1604,27 @@ impl<'a, B: AsRef<[AtomicUsize]>> IntoIterator for &'a mut AtomicBitVec<B> {
     }
 }
 
-impl<'a, B: AsRef<[AtomicUsize]>> Iterator for AtomicBitIterator<'a, B> {
+impl<'a, W: Word + IntoAtomic, B: AsAtomicWords<Word = W>> Iterator for AtomicBitIterator<'a, B> {
     type Item = bool;
     fn next(&mut self) -> Option<bool> {
         if self.next_bit_pos == self.len {
             return None;
         }
-        let word_idx = self.next_bit_pos / BITS;
-        let bit_idx = self.next_bit_pos % BITS;
+        let word_idx = self.next_bit_pos / W::BITS;
+        let bit_idx = self.next_bit_pos % W::BITS;
         let word = unsafe {
             self.bits
-                .as_ref()
+                .as_atomic_words()
                 .get_unchecked(word_idx)
                 .load(Ordering::Relaxed)
         };
-        let bit = (word >> bit_idx) & 1;
+        let bit = (word >> bit_idx) & W::ONE;
         self.next_bit_pos += 1;
-        Some(bit != 0)
+        Some(bit != W::ZERO)
     }
 }
 
-impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
+impl<B: AsAtomicWords> AtomicBitVec<B> {
     // Returns an iterator over the bits of this bit vector.
     //
     // Note that this method takes a mutable reference to the bit vector,
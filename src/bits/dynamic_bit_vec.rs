@@ -0,0 +1,631 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! A dynamic bit vector supporting insertions, deletions, ranking, and
+//! selection.
+//!
+//! All structures in [`rank_sel`](crate::rank_sel) are static: they must be
+//! rebuilt from scratch every time the underlying bit vector changes. A
+//! [`DynamicBitVec`] instead keeps its bits in the leaves of a B-tree whose
+//! internal nodes cache the number of bits and ones of each subtree, so that
+//! access, modification, insertion, deletion, ranking, and selection all take
+//! logarithmic time.
+
+use crate::traits::rank_sel::*;
+use common_traits::SelectInWord;
+use mem_dbg::*;
+use std::ops::Index;
+
+/// The maximum number of bits in a leaf.
+const LEAF_BITS: usize = 2048;
+/// The maximum number of children of an internal node.
+const MAX_CHILDREN: usize = 16;
+
+/// Inserts a bit at the given position of a bit vector of given length,
+/// which must have room for an additional bit.
+fn insert_bit(words: &mut [usize], len: usize, pos: usize, bit: bool) {
+    let word_idx = pos / usize::BITS as usize;
+    let bit_idx = pos % usize::BITS as usize;
+    let low_mask = (1 << bit_idx) - 1;
+    let mut carry = words[word_idx] >> (usize::BITS - 1);
+    let word = words[word_idx];
+    words[word_idx] = (word & low_mask) | ((bit as usize) << bit_idx) | ((word & !low_mask) << 1);
+    for word in &mut words[word_idx + 1..(len + 1).div_ceil(usize::BITS as usize)] {
+        let next_carry = *word >> (usize::BITS - 1);
+        *word = (*word << 1) | carry;
+        carry = next_carry;
+    }
+}
+
+/// Removes the bit at the given position of a bit vector of given length,
+/// returning it.
+fn remove_bit(words: &mut [usize], len: usize, pos: usize) -> bool {
+    let word_idx = pos / usize::BITS as usize;
+    let bit_idx = pos % usize::BITS as usize;
+    let num_words = len.div_ceil(usize::BITS as usize);
+    let word = words[word_idx];
+    let bit = (word >> bit_idx) & 1 != 0;
+    let low_mask = (1 << bit_idx) - 1;
+    words[word_idx] = (word & low_mask) | ((word >> 1) & !low_mask);
+    for i in word_idx + 1..num_words {
+        words[i - 1] |= (words[i] & 1) << (usize::BITS - 1);
+        words[i] >>= 1;
+    }
+    bit
+}
+
+/// A leaf of the tree, containing a plain bit vector.
+///
+/// Bits beyond the length are always zero.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+struct Leaf {
+    words: Vec<usize>,
+    len: usize,
+    ones: usize,
+}
+
+impl Leaf {
+    fn new() -> Self {
+        Self {
+            words: Vec::with_capacity(LEAF_BITS / usize::BITS as usize),
+            len: 0,
+            ones: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn get(&self, pos: usize) -> bool {
+        (self.words[pos / usize::BITS as usize] >> (pos % usize::BITS as usize)) & 1 != 0
+    }
+
+    /// Sets a bit, returning its previous value.
+    fn set(&mut self, pos: usize, bit: bool) -> bool {
+        let old = self.get(pos);
+        let word = &mut self.words[pos / usize::BITS as usize];
+        *word &= !(1 << (pos % usize::BITS as usize));
+        *word |= (bit as usize) << (pos % usize::BITS as usize);
+        self.ones = self.ones + bit as usize - old as usize;
+        old
+    }
+
+    fn insert(&mut self, pos: usize, bit: bool) {
+        if self.len & (usize::BITS as usize - 1) == 0 {
+            self.words.push(0);
+        }
+        insert_bit(&mut self.words, self.len, pos, bit);
+        self.len += 1;
+        self.ones += bit as usize;
+    }
+
+    fn remove(&mut self, pos: usize) -> bool {
+        let bit = remove_bit(&mut self.words, self.len, pos);
+        self.len -= 1;
+        self.ones -= bit as usize;
+        if self.len & (usize::BITS as usize - 1) == 0 {
+            self.words.pop();
+        }
+        bit
+    }
+
+    fn rank(&self, pos: usize) -> usize {
+        let word_idx = pos / usize::BITS as usize;
+        let bit_idx = pos % usize::BITS as usize;
+        let mut rank = self.words[..word_idx]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>();
+        if bit_idx != 0 {
+            rank += (self.words[word_idx] & ((1 << bit_idx) - 1)).count_ones() as usize;
+        }
+        rank
+    }
+
+    /// Selects the one (zero if `ZERO` is true) of given rank.
+    fn select<const ZERO: bool>(&self, mut rank: usize) -> usize {
+        for (i, &word) in self.words.iter().enumerate() {
+            let word = if ZERO { !word } else { word };
+            let count = word.count_ones() as usize;
+            if rank < count {
+                return i * usize::BITS as usize + word.select_in_word(rank);
+            }
+            rank -= count;
+        }
+        unreachable!()
+    }
+
+    /// Appends the bits of another leaf.
+    fn append(&mut self, other: &Leaf) {
+        let shift = self.len % usize::BITS as usize;
+        if shift == 0 {
+            self.words.extend_from_slice(&other.words);
+        } else {
+            for &word in &other.words {
+                *self.words.last_mut().unwrap() |= word << shift;
+                self.words.push(word >> (usize::BITS as usize - shift));
+            }
+        }
+        self.len += other.len;
+        self.ones += other.ones;
+        self.words.truncate(self.len.div_ceil(usize::BITS as usize));
+    }
+
+    /// Splits the leaf at a word boundary close to the middle, returning the
+    /// second half.
+    fn split(&mut self) -> Leaf {
+        let at = self.words.len() / 2;
+        let words = self.words.split_off(at);
+        let len = self.len - at * usize::BITS as usize;
+        let ones = words.iter().map(|w| w.count_ones() as usize).sum();
+        self.len -= len;
+        self.ones -= ones;
+        Leaf { words, len, ones }
+    }
+}
+
+/// A node of the tree.
+///
+/// [`MemSize`] and [`MemDbg`] are implemented manually, as deriving them on a
+/// recursive type leads to an infinite recursion in trait resolution.
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf(Leaf),
+    Internal(Internal),
+}
+
+/// An internal node of the tree, caching the number of bits and ones of each
+/// child.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+struct Internal {
+    children: Vec<Node>,
+    lens: Vec<usize>,
+    ones: Vec<usize>,
+}
+
+impl Internal {
+    fn new(children: Vec<Node>) -> Self {
+        let lens = children.iter().map(Node::len).collect();
+        let ones = children.iter().map(Node::ones).collect();
+        Self {
+            children,
+            lens,
+            ones,
+        }
+    }
+
+    /// Returns the index of the child containing the bit of given position,
+    /// and the position relative to the child.
+    ///
+    /// If `INSERT` is true, the position might be equal to the length of the
+    /// child.
+    #[inline(always)]
+    fn find<const INSERT: bool>(&self, mut pos: usize) -> (usize, usize) {
+        let last = self.lens.len() - 1;
+        for (i, &len) in self.lens[..last].iter().enumerate() {
+            if pos < len || (INSERT && pos == len) {
+                return (i, pos);
+            }
+            pos -= len;
+        }
+        (last, pos)
+    }
+
+    /// Splits the node in half, returning the second half.
+    fn split(&mut self) -> Internal {
+        let at = self.children.len() / 2;
+        Internal {
+            children: self.children.split_off(at),
+            lens: self.lens.split_off(at),
+            ones: self.ones.split_off(at),
+        }
+    }
+
+    /// Inserts a child after the child of given index.
+    fn insert_child(&mut self, index: usize, child: Node) {
+        self.lens.insert(index + 1, child.len());
+        self.ones.insert(index + 1, child.ones());
+        self.children.insert(index + 1, child);
+    }
+
+    /// Merges the child of given index with a neighbor, or rebalances them, if
+    /// it is underfull.
+    fn fix_child(&mut self, index: usize) {
+        if self.children.len() == 1 {
+            return;
+        }
+        let underfull = match &self.children[index] {
+            Node::Leaf(leaf) => leaf.len < LEAF_BITS / 4,
+            Node::Internal(internal) => internal.children.len() < MAX_CHILDREN / 4,
+        };
+        if !underfull {
+            return;
+        }
+        let left = if index == 0 { 0 } else { index - 1 };
+        let right = self.children.remove(left + 1);
+        self.lens.remove(left + 1);
+        self.ones.remove(left + 1);
+        let overflow = match (&mut self.children[left], right) {
+            (Node::Leaf(left), Node::Leaf(right)) => {
+                left.append(&right);
+                (left.len > LEAF_BITS).then(|| Node::Leaf(left.split()))
+            }
+            (Node::Internal(left), Node::Internal(mut right)) => {
+                left.children.append(&mut right.children);
+                left.lens.append(&mut right.lens);
+                left.ones.append(&mut right.ones);
+                (left.children.len() > MAX_CHILDREN).then(|| Node::Internal(left.split()))
+            }
+            _ => unreachable!("All leaves are at the same depth"),
+        };
+        self.lens[left] = self.children[left].len();
+        self.ones[left] = self.children[left].ones();
+        if let Some(node) = overflow {
+            self.insert_child(left, node);
+        }
+    }
+}
+
+impl CopyType for Node {
+    type Copy = False;
+}
+
+impl MemSize for Node {
+    fn mem_size(&self, flags: SizeFlags) -> usize {
+        core::mem::size_of::<Self>()
+            + match self {
+                Node::Leaf(leaf) => leaf.mem_size(flags) - core::mem::size_of::<Leaf>(),
+                Node::Internal(internal) => {
+                    internal.mem_size(flags) - core::mem::size_of::<Internal>()
+                }
+            }
+    }
+}
+
+impl MemDbgImpl for Node {}
+
+impl Node {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(leaf) => leaf.len,
+            Node::Internal(internal) => internal.lens.iter().sum(),
+        }
+    }
+
+    fn ones(&self) -> usize {
+        match self {
+            Node::Leaf(leaf) => leaf.ones,
+            Node::Internal(internal) => internal.ones.iter().sum(),
+        }
+    }
+
+    fn get(&self, pos: usize) -> bool {
+        match self {
+            Node::Leaf(leaf) => leaf.get(pos),
+            Node::Internal(internal) => {
+                let (i, pos) = internal.find::<false>(pos);
+                internal.children[i].get(pos)
+            }
+        }
+    }
+
+    /// Sets a bit, returning its previous value.
+    fn set(&mut self, pos: usize, bit: bool) -> bool {
+        match self {
+            Node::Leaf(leaf) => leaf.set(pos, bit),
+            Node::Internal(internal) => {
+                let (i, pos) = internal.find::<false>(pos);
+                let old = internal.children[i].set(pos, bit);
+                internal.ones[i] = internal.ones[i] + bit as usize - old as usize;
+                old
+            }
+        }
+    }
+
+    /// Inserts a bit, returning a new sibling if the node had to be split.
+    fn insert(&mut self, pos: usize, bit: bool) -> Option<Node> {
+        match self {
+            Node::Leaf(leaf) => {
+                leaf.insert(pos, bit);
+                (leaf.len > LEAF_BITS).then(|| Node::Leaf(leaf.split()))
+            }
+            Node::Internal(internal) => {
+                let (i, pos) = internal.find::<true>(pos);
+                let sibling = internal.children[i].insert(pos, bit);
+                internal.lens[i] += 1;
+                internal.ones[i] += bit as usize;
+                if let Some(sibling) = sibling {
+                    internal.lens[i] -= sibling.len();
+                    internal.ones[i] -= sibling.ones();
+                    internal.insert_child(i, sibling);
+                }
+                (internal.children.len() > MAX_CHILDREN).then(|| Node::Internal(internal.split()))
+            }
+        }
+    }
+
+    /// Removes a bit, returning it.
+    fn remove(&mut self, pos: usize) -> bool {
+        match self {
+            Node::Leaf(leaf) => leaf.remove(pos),
+            Node::Internal(internal) => {
+                let (i, pos) = internal.find::<false>(pos);
+                let bit = internal.children[i].remove(pos);
+                internal.lens[i] -= 1;
+                internal.ones[i] -= bit as usize;
+                internal.fix_child(i);
+                bit
+            }
+        }
+    }
+
+    fn rank(&self, pos: usize) -> usize {
+        match self {
+            Node::Leaf(leaf) => leaf.rank(pos),
+            Node::Internal(internal) => {
+                let (i, rel_pos) = internal.find::<true>(pos);
+                internal.ones[..i].iter().sum::<usize>() + internal.children[i].rank(rel_pos)
+            }
+        }
+    }
+
+    /// Selects the one (zero if `ZERO` is true) of given rank.
+    fn select<const ZERO: bool>(&self, mut rank: usize) -> usize {
+        match self {
+            Node::Leaf(leaf) => leaf.select::<ZERO>(rank),
+            Node::Internal(internal) => {
+                let mut pos = 0;
+                for (i, (&len, &ones)) in internal.lens.iter().zip(&internal.ones).enumerate() {
+                    let count = if ZERO { len - ones } else { ones };
+                    if rank < count {
+                        return pos + internal.children[i].select::<ZERO>(rank);
+                    }
+                    rank -= count;
+                    pos += len;
+                }
+                unreachable!()
+            }
+        }
+    }
+}
+
+/// A dynamic bit vector supporting insertions, deletions, ranking, and
+/// selection in logarithmic time.
+///
+/// Bits are stored in the leaves of a B-tree, each containing at most 2048
+/// bits; internal nodes have at most 16 children, and store the number of bits
+/// and ones of each child. The structure implements [`BitLength`],
+/// [`BitCount`], [`NumBits`], the ranking and selection traits, and [`Index`],
+/// so it can be used by generic code in place of a static structure such as
+/// [`Rank9`](crate::rank_sel::Rank9) or
+/// [`SelectAdapt`](crate::rank_sel::SelectAdapt).
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::bits::DynamicBitVec;
+/// use sux::traits::{BitLength, Rank, Select};
+///
+/// let mut b = DynamicBitVec::new();
+/// b.push(true);
+/// b.push(false);
+/// b.push(true);
+/// b.insert(1, true);
+/// assert_eq!(b.len(), 4);
+/// assert_eq!(b.rank(3), 2);
+/// assert_eq!(b.select(2), Some(3));
+///
+/// assert!(b.remove(0));
+/// b.set(1, true);
+/// assert_eq!(b.select(1), Some(1));
+/// assert_eq!(b[2], true);
+/// ```
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct DynamicBitVec {
+    root: Node,
+    len: usize,
+    ones: usize,
+}
+
+impl Default for DynamicBitVec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynamicBitVec {
+    /// Creates a new empty dynamic bit vector.
+    pub fn new() -> Self {
+        Self {
+            root: Node::Leaf(Leaf::new()),
+            len: 0,
+            ones: 0,
+        }
+    }
+
+    /// Returns the number of bits in the bit vector.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the bit of given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    pub fn get(&self, pos: usize) -> bool {
+        assert!(
+            pos < self.len,
+            "Index out of bounds: {} >= {}",
+            pos,
+            self.len
+        );
+        self.root.get(pos)
+    }
+
+    /// Sets the bit of given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    pub fn set(&mut self, pos: usize, bit: bool) {
+        assert!(
+            pos < self.len,
+            "Index out of bounds: {} >= {}",
+            pos,
+            self.len
+        );
+        let old = self.root.set(pos, bit);
+        self.ones = self.ones + bit as usize - old as usize;
+    }
+
+    /// Inserts a bit at the given position, shifting the following bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is greater than the length.
+    pub fn insert(&mut self, pos: usize, bit: bool) {
+        assert!(
+            pos <= self.len,
+            "Index out of bounds: {} > {}",
+            pos,
+            self.len
+        );
+        if let Some(sibling) = self.root.insert(pos, bit) {
+            let root = std::mem::replace(&mut self.root, Node::Leaf(Leaf::new()));
+            self.root = Node::Internal(Internal::new(vec![root, sibling]));
+        }
+        self.len += 1;
+        self.ones += bit as usize;
+    }
+
+    /// Removes the bit at the given position, shifting the following bits,
+    /// and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    pub fn remove(&mut self, pos: usize) -> bool {
+        assert!(
+            pos < self.len,
+            "Index out of bounds: {} >= {}",
+            pos,
+            self.len
+        );
+        let bit = self.root.remove(pos);
+        if let Node::Internal(internal) = &mut self.root {
+            if internal.children.len() == 1 {
+                self.root = internal.children.pop().unwrap();
+            }
+        }
+        self.len -= 1;
+        self.ones -= bit as usize;
+        bit
+    }
+
+    /// Appends a bit.
+    pub fn push(&mut self, bit: bool) {
+        self.insert(self.len, bit);
+    }
+
+    /// Removes the last bit and returns it, or returns `None` if the bit
+    /// vector is empty.
+    pub fn pop(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.remove(self.len - 1))
+        }
+    }
+}
+
+impl BitLength for DynamicBitVec {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl BitCount for DynamicBitVec {
+    #[inline(always)]
+    fn count_ones(&self) -> usize {
+        self.ones
+    }
+}
+
+impl NumBits for DynamicBitVec {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.ones
+    }
+}
+
+impl Index<usize> for DynamicBitVec {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            false => &false,
+            true => &true,
+        }
+    }
+}
+
+impl RankUnchecked for DynamicBitVec {
+    /// # Safety
+    ///
+    /// The implementation of [`RankUnchecked`] for [`DynamicBitVec`] accepts
+    /// also a position equal to the length of the bit vector.
+    #[inline]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        self.root.rank(pos)
+    }
+}
+
+impl Rank for DynamicBitVec {}
+impl RankZero for DynamicBitVec {}
+
+impl SelectUnchecked for DynamicBitVec {
+    #[inline]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.root.select::<false>(rank)
+    }
+}
+
+impl Select for DynamicBitVec {}
+
+impl SelectZeroUnchecked for DynamicBitVec {
+    #[inline]
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        self.root.select::<true>(rank)
+    }
+}
+
+impl SelectZero for DynamicBitVec {}
+
+impl Extend<bool> for DynamicBitVec {
+    fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
+        for bit in iter {
+            self.push(bit);
+        }
+    }
+}
+
+impl FromIterator<bool> for DynamicBitVec {
+    fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<B: AsWords> From<&crate::bits::BitVec<B>> for DynamicBitVec {
+    fn from(bits: &crate::bits::BitVec<B>) -> Self {
+        bits.into_iter().collect()
+    }
+}
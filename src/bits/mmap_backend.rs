@@ -0,0 +1,289 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Memory-mapped backends for bit vectors and bit-field vectors.
+//!
+//! A [`MmapBackend`] is a fixed-size slice of words stored in a writable,
+//! shared memory mapping of a file. It makes it possible to build bit vectors
+//! and bit-field vectors larger than the available memory: pages are loaded
+//! and written back by the operating system as needed, and the file is sparse
+//! until the words are written.
+//!
+//! The file contains the [ε-serde](epserde) serialization of a structure
+//! whose first field is a `Vec` of words (e.g., a `BitVec<Vec<W>>`): the
+//! header of the structure, the number of words, zero padding up to the
+//! alignment of the words, the words, and finally the remaining fields of the
+//! structure. The position of the words depends only on the header, so it
+//! can be computed in advance using ε-serde's alignment rules
+//! (see [`WriteWithNames::align`]). After a call to [`BitVec::flush`] or
+//! [`BitFieldVec::flush`], which write the rest of the serialized structure
+//! around the words, the file can be loaded directly using ε-serde (e.g., by
+//! [`Deserialize::mmap`](epserde::deser::Deserialize::mmap)), with no
+//! copying.
+//!
+//! Backends are created by [`BitVec::new_mmap`] and
+//! [`BitFieldVec::new_mmap`]. Bit vectors and bit-field vectors with a
+//! memory-mapped backend can be turned into their atomic counterparts (and
+//! back) using [`From`]/[`Into`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sux::prelude::*;
+//! use epserde::prelude::*;
+//!
+//! let path = std::env::temp_dir().join("mmap_backend_doctest.bin");
+//! let mut b = BitVec::new_mmap(&path, 100)?;
+//! b.set(10, true);
+//! b.set(90, true);
+//! b.flush()?;
+//! drop(b);
+//!
+//! let b = <BitVec<Vec<usize>>>::mmap(&path, Flags::empty())?;
+//! assert_eq!(b.len(), 100);
+//! assert_eq!(b.iter_ones().collect::<Vec<_>>(), vec![10, 90]);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::prelude::*;
+use anyhow::{ensure, Result};
+use common_traits::{AtomicUnsignedInt, IntoAtomic};
+use epserde::pad_align_to;
+use epserde::ser::{self, write_header, WriteNoStd, WriteWithNames, WriteWithPos, WriterWithPos};
+use epserde::traits::{MaxSizeOf, ReprHash, TypeHash};
+use mmap_rs::{MmapFlags, MmapMut, MmapOptions};
+use std::fs::{File, OpenOptions};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// A fixed-size slice of words stored in a writable memory-mapped file.
+///
+/// See the [module documentation](self).
+#[derive(Debug)]
+pub struct MmapBackend<W = usize> {
+    file: File,
+    mmap: MmapMut,
+    /// The position in bytes of the first word in the file.
+    offset: usize,
+    /// The number of words.
+    len: usize,
+    _marker: PhantomData<W>,
+}
+
+/// An ε-serde writer on the bytes of a memory mapping.
+pub(crate) struct MmapWriter<'a> {
+    data: &'a mut [u8],
+    pos: usize,
+}
+
+impl WriteNoStd for MmapWriter<'_> {
+    fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
+        let end = self.pos + buf.len();
+        if end > self.data.len() {
+            return Err(ser::Error::WriteError);
+        }
+        self.data[self.pos..end].copy_from_slice(buf);
+        self.pos = end;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> ser::Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteWithPos for MmapWriter<'_> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl WriteWithNames for MmapWriter<'_> {}
+
+impl<W: Word + MaxSizeOf> MmapBackend<W> {
+    /// Returns the position of the words in the ε-serde serialization of a
+    /// structure of type `T` whose first field is a `Vec<W>`.
+    ///
+    /// The header is followed by the length of the vector and by zero
+    /// padding up to the alignment of `W`.
+    fn words_offset<T: TypeHash + ReprHash>() -> Result<usize> {
+        let mut header = Vec::new();
+        write_header::<T>(&mut WriterWithPos::new(&mut header))?;
+        let pos = header.len() + core::mem::size_of::<usize>();
+        Ok(pos + pad_align_to(pos, W::max_size_of()))
+    }
+
+    /// Creates a backend of `len` zeroed words in the file at `path`, which
+    /// is created or truncated.
+    ///
+    /// The words are placed where ε-serde places the first field, a
+    /// `Vec<W>`, of a structure of type `T`, and they are followed by
+    /// `trailer_size` bytes for the remaining fields of the structure.
+    pub(crate) fn new<T: TypeHash + ReprHash>(
+        path: impl AsRef<Path>,
+        len: usize,
+        trailer_size: usize,
+    ) -> Result<Self> {
+        let offset = Self::words_offset::<T>()?;
+        let size = offset + len * W::BYTES + trailer_size;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(size as u64)?;
+        let mmap = unsafe {
+            MmapOptions::new(size)?
+                .with_flags(MmapFlags::SHARED)
+                .with_file(&file, 0)
+                .map_mut()?
+        };
+
+        Ok(Self {
+            file,
+            mmap,
+            offset,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Writes around the words the ε-serde header of a structure of type `T`,
+    /// the number of words, and the remaining fields of the structure, which
+    /// are written by `trailer`, and flushes the mapping.
+    ///
+    /// `T` and the size of the data written by `trailer` must be the same
+    /// passed to [`MmapBackend::new`].
+    pub(crate) fn flush<T: TypeHash + ReprHash>(
+        &mut self,
+        trailer: impl FnOnce(&mut MmapWriter) -> ser::Result<()>,
+    ) -> Result<()> {
+        let mut writer = MmapWriter {
+            data: self.mmap.as_mut_slice(),
+            pos: 0,
+        };
+        write_header::<T>(&mut writer)?;
+        writer.write("len", &self.len)?;
+        writer.align::<W>()?;
+        ensure!(
+            writer.pos == self.offset,
+            "The words are at position {}, but ε-serde expects them at position {}",
+            self.offset,
+            writer.pos
+        );
+        // The words are already in place
+        writer.pos += self.len * W::BYTES;
+        trailer(&mut writer)?;
+        let (pos, size) = (writer.pos, writer.data.len());
+        ensure!(
+            pos == size,
+            "The serialized structure has length {}, but the file has length {}",
+            pos,
+            size
+        );
+        self.mmap.flush(0..size)?;
+        Ok(())
+    }
+}
+
+impl<W> MmapBackend<W> {
+    /// Reinterprets the words as words of another type of the same size
+    /// (e.g., their atomic counterparts).
+    fn cast<V>(self) -> MmapBackend<V> {
+        debug_assert_eq!(core::mem::size_of::<W>(), core::mem::size_of::<V>());
+        MmapBackend {
+            file: self.file,
+            mmap: self.mmap,
+            offset: self.offset,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W> AsRef<[W]> for MmapBackend<W> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[W] {
+        unsafe {
+            core::slice::from_raw_parts(self.mmap.as_ptr().add(self.offset) as *const W, self.len)
+        }
+    }
+}
+
+impl<W> AsMut<[W]> for MmapBackend<W> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [W] {
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self.mmap.as_mut_ptr().add(self.offset) as *mut W,
+                self.len,
+            )
+        }
+    }
+}
+
+impl<W: Word> AsWords for MmapBackend<W> {
+    type Word = W;
+
+    #[inline(always)]
+    fn as_words(&self) -> &[W] {
+        self.as_ref()
+    }
+}
+
+impl<W: Word> AsWordsMut for MmapBackend<W> {
+    #[inline(always)]
+    fn as_words_mut(&mut self) -> &mut [W] {
+        self.as_mut()
+    }
+}
+
+impl<A: AtomicUnsignedInt> AsAtomicWords for MmapBackend<A>
+where
+    A::NonAtomicType: Word,
+{
+    type Word = A::NonAtomicType;
+    type AtomicWord = A;
+
+    #[inline(always)]
+    fn as_atomic_words(&self) -> &[A] {
+        self.as_ref()
+    }
+}
+
+impl<W: IntoAtomic> From<BitVec<MmapBackend<W>>> for AtomicBitVec<MmapBackend<W::AtomicType>> {
+    fn from(value: BitVec<MmapBackend<W>>) -> Self {
+        let (bits, len) = value.into_raw_parts();
+        unsafe { AtomicBitVec::from_raw_parts(bits.cast(), len) }
+    }
+}
+
+impl<W: IntoAtomic> From<AtomicBitVec<MmapBackend<W::AtomicType>>> for BitVec<MmapBackend<W>> {
+    fn from(value: AtomicBitVec<MmapBackend<W::AtomicType>>) -> Self {
+        let (bits, len) = value.into_raw_parts();
+        unsafe { BitVec::from_raw_parts(bits.cast(), len) }
+    }
+}
+
+impl<W: Word + IntoAtomic> From<BitFieldVec<W, MmapBackend<W>>>
+    for AtomicBitFieldVec<W, MmapBackend<W::AtomicType>>
+{
+    fn from(value: BitFieldVec<W, MmapBackend<W>>) -> Self {
+        let (bits, bit_width, len) = value.into_raw_parts();
+        unsafe { AtomicBitFieldVec::from_raw_parts(bits.cast(), bit_width, len) }
+    }
+}
+
+impl<W: Word + IntoAtomic> From<AtomicBitFieldVec<W, MmapBackend<W::AtomicType>>>
+    for BitFieldVec<W, MmapBackend<W>>
+{
+    fn from(value: AtomicBitFieldVec<W, MmapBackend<W::AtomicType>>) -> Self {
+        let (bits, bit_width, len) = value.into_raw_parts();
+        unsafe { BitFieldVec::from_raw_parts(bits.cast(), bit_width, len) }
+    }
+}
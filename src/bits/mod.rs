@@ -5,11 +5,23 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
-//! Structures for [bit vectors](`bit_vec`) and
-//! [vectors of values of bounded bit width](`bit_field_vec`).
+//! Structures for [bit vectors](`bit_vec`),
+//! [views on ranges of bits](`bit_slice`),
+//! [vectors of values of bounded bit width](`bit_field_vec`),
+//! [dynamic bit vectors](`dynamic_bit_vec`), and
+//! [memory-mapped backends](`mmap_backend`).
 
 pub mod bit_field_vec;
 pub use bit_field_vec::*;
 
+pub mod bit_slice;
+pub use bit_slice::*;
+
 pub mod bit_vec;
 pub use bit_vec::*;
+
+pub mod dynamic_bit_vec;
+pub use dynamic_bit_vec::*;
+
+pub mod mmap_backend;
+pub use mmap_backend::*;
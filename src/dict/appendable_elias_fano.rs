@@ -0,0 +1,377 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! An appendable version of the Elias–Fano representation of monotone
+//! sequences.
+//!
+//! An [`EliasFano`] structure is immutable once its selection structures have
+//! been attached by [`EliasFano::map_high_bits`]. An [`AppendableEliasFano`]
+//! instead makes it possible to [push](AppendableEliasFano::push) new values
+//! at any time, keeping all queries available.
+//!
+//! The high bits are split into an _indexed prefix_, which has selection
+//! structures attached, and an _unindexed tail_ containing the high bits of
+//! the values pushed after the last rebuild of the selection structures.
+//! Queries involving the tail fall back to a linear scan of the tail. When the
+//! tail becomes too long with respect to the prefix, the prefix and the tail
+//! are merged and the selection structures are rebuilt; as the length of the
+//! tail triggering a rebuild is proportional to the length of the prefix, the
+//! cost of rebuilding is constant per value in an amortized sense.
+//!
+//! Since the number of values and their upper bound are unknown, the number of
+//! lower bits must be fixed at construction time.
+
+use crate::prelude::*;
+use crate::traits::bit_field_slice::*;
+use common_traits::SelectInWord;
+use epserde::*;
+use mem_dbg::*;
+use std::borrow::Borrow;
+
+/// The type of the indexed prefix of the high bits.
+type HighBits = SelectZeroAdaptConst<
+    SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>,
+    Box<[usize]>,
+    12,
+    3,
+>;
+
+/// The minimum number of values in the tail triggering a rebuild.
+const MIN_TAIL_LEN: usize = 1 << 10;
+/// The base-2 logarithm of the ratio between the length of the prefix and the
+/// length of the tail triggering a rebuild.
+const LOG2_TAIL_RATIO: usize = 2;
+
+/// Builds the selection structures on a bit vector.
+fn index_high_bits(bits: BitVec<Box<[usize]>>) -> HighBits {
+    SelectZeroAdaptConst::new(SelectAdaptConst::new(bits))
+}
+
+/// Returns the position of the bit of given rank, that is, of the bit with
+/// `rank` bits equal to `bit` preceding it, in a bit array.
+///
+/// The bit must exist.
+#[inline]
+fn select_in_words(words: &[usize], mut rank: usize, bit: bool) -> usize {
+    for (word_idx, &word) in words.iter().enumerate() {
+        let word = if bit { word } else { !word };
+        let count = word.count_ones() as usize;
+        if rank < count {
+            return word_idx * usize::BITS as usize + word.select_in_word(rank);
+        }
+        rank -= count;
+    }
+    unreachable!()
+}
+
+/// A monotone sequence of integers in Elias–Fano representation supporting
+/// appends.
+///
+/// The structure implements [`IndexedSeq`], [`IndexedDict`], [`Succ`], and
+/// [`Pred`] with the same semantics as an [`EliasFano`] structure. Operations
+/// on values in the indexed prefix take constant time, whereas operations on
+/// values in the unindexed tail require a linear scan of the tail (see the
+/// [module documentation](crate::dict::appendable_elias_fano)).
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::AppendableEliasFano;
+/// # use sux::traits::{IndexedSeq, Succ, Pred};
+/// let mut aef = AppendableEliasFano::new(3);
+/// aef.push(0);
+/// aef.push(2);
+/// aef.push(8);
+/// assert_eq!(aef.succ(1), Some((1, 2)));
+///
+/// aef.push(10);
+/// assert_eq!(aef.get(3), 10);
+/// assert_eq!(aef.pred(9), Some((2, 8)));
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct AppendableEliasFano {
+    /// The number of values.
+    n: usize,
+    /// The number of lower bits.
+    l: usize,
+    /// The last value, or zero.
+    last_value: usize,
+    /// The lower-bits array.
+    low_bits: BitFieldVec<usize, Vec<usize>>,
+    /// The high bits of the first `num_indexed` values, ending with the one of
+    /// the last such value, with selection structures.
+    prefix: HighBits,
+    /// The number of values whose high bits are in the prefix.
+    num_indexed: usize,
+    /// The high bits following the prefix.
+    tail: BitVec<Vec<usize>>,
+}
+
+impl AppendableEliasFano {
+    /// Creates an empty structure using `l` lower bits.
+    ///
+    /// The best choice for `l` is ⌊lg(*u*/*n*)⌋, where *n* is the number of
+    /// values and *u* their upper bound (see
+    /// [`with_estimate`](Self::with_estimate)).
+    pub fn new(l: usize) -> Self {
+        assert!(l < usize::BITS as usize, "Too many lower bits: {}", l);
+        Self {
+            n: 0,
+            l,
+            last_value: 0,
+            low_bits: BitFieldVec::new(l, 0),
+            prefix: index_high_bits(BitVec::new(0).into()),
+            num_indexed: 0,
+            tail: BitVec::new(0),
+        }
+    }
+
+    /// Creates an empty structure whose number of lower bits is optimal for
+    /// `n` values smaller than or equal to `u`.
+    ///
+    /// The estimates do not need to be exact, and more values, or larger
+    /// values, can be pushed.
+    pub fn with_estimate(n: usize, u: usize) -> Self {
+        Self::new(if u >= n && n > 0 {
+            (u / n).ilog2() as usize
+        } else {
+            0
+        })
+    }
+
+    /// Returns the number of values in the sequence.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of values in the indexed prefix.
+    #[inline(always)]
+    pub fn num_indexed(&self) -> usize {
+        self.num_indexed
+    }
+
+    /// Adds a new value at the end of the sequence.
+    ///
+    /// The selection structures are rebuilt if the unindexed tail becomes too
+    /// long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is smaller than the last value.
+    pub fn push(&mut self, value: usize) {
+        if value < self.last_value {
+            panic!(
+                "The values provided are not monotone: {} < {}",
+                value, self.last_value
+            );
+        }
+        self.low_bits.push(value & ((1 << self.l) - 1));
+        let high = (value >> self.l) + self.n - self.prefix.len();
+        self.tail.resize(high, false);
+        self.tail.push(true);
+        self.n += 1;
+        self.last_value = value;
+
+        let num_unindexed = self.n - self.num_indexed;
+        if num_unindexed >= MIN_TAIL_LEN.max(self.num_indexed >> LOG2_TAIL_RATIO) {
+            self.rebuild();
+        }
+    }
+
+    /// Merges the unindexed tail into the indexed prefix and rebuilds the
+    /// selection structures.
+    ///
+    /// This method is called automatically by [`push`](Self::push), but it
+    /// can be called explicitly, for example after a batch of appends, to
+    /// make all queries constant-time.
+    pub fn rebuild(&mut self) {
+        if self.tail.is_empty() {
+            return;
+        }
+        let prefix = std::mem::replace(&mut self.prefix, index_high_bits(BitVec::new(0).into()));
+        let (bits, len) = prefix.into_inner().into_inner().into_raw_parts();
+        // SAFETY: the length is the one of the original bit vector
+        let mut high_bits = unsafe { BitVec::from_raw_parts(bits.into_vec(), len) };
+        for bit in &self.tail {
+            high_bits.push(bit);
+        }
+        self.prefix = index_high_bits(high_bits.into());
+        self.num_indexed = self.n;
+        self.tail = BitVec::new(0);
+    }
+
+    /// Returns the number of zeros in the prefix.
+    #[inline(always)]
+    fn prefix_zeros(&self) -> usize {
+        self.prefix.len() - self.num_indexed
+    }
+
+    /// Returns the number of zeros in the high bits.
+    #[inline(always)]
+    fn num_zeros(&self) -> usize {
+        self.prefix.len() + self.tail.len() - self.n
+    }
+
+    /// Returns the value of the bit of given position in the high bits.
+    #[inline(always)]
+    fn high_bit(&self, pos: usize) -> bool {
+        let prefix_len = self.prefix.len();
+        if pos < prefix_len {
+            self.prefix.as_ref()[pos / usize::BITS as usize] >> (pos % usize::BITS as usize) & 1
+                != 0
+        } else {
+            self.tail.get(pos - prefix_len)
+        }
+    }
+
+    /// Returns the position of the one of given rank in the high bits.
+    #[inline]
+    fn select(&self, rank: usize) -> usize {
+        if rank < self.num_indexed {
+            unsafe { self.prefix.select_unchecked(rank) }
+        } else {
+            self.prefix.len() + select_in_words(self.tail.as_ref(), rank - self.num_indexed, true)
+        }
+    }
+
+    /// Returns the position of the zero of given rank in the high bits.
+    #[inline]
+    fn select_zero(&self, rank: usize) -> usize {
+        let prefix_zeros = self.prefix_zeros();
+        if rank < prefix_zeros {
+            unsafe { self.prefix.select_zero_unchecked(rank) }
+        } else {
+            self.prefix.len() + select_in_words(self.tail.as_ref(), rank - prefix_zeros, false)
+        }
+    }
+
+    /// Returns the value of given index, given the position of its one in the
+    /// high bits.
+    #[inline(always)]
+    fn value(&self, index: usize, pos: usize) -> usize {
+        (pos - index) << self.l | unsafe { self.low_bits.get_unchecked(index) }
+    }
+}
+
+impl Types for AppendableEliasFano {
+    type Input = usize;
+    type Output = usize;
+}
+
+impl IndexedSeq for AppendableEliasFano {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(&self, index: usize) -> usize {
+        self.value(index, self.select(index))
+    }
+}
+
+impl SuccUnchecked for AppendableEliasFano {
+    unsafe fn succ_unchecked<const STRICT: bool>(
+        &self,
+        value: impl Borrow<Self::Input>,
+    ) -> (usize, Self::Output) {
+        let value = *value.borrow();
+        let high = value >> self.l;
+        // The position of the first one of the values with the same high bits
+        let mut pos = if high == 0 {
+            0
+        } else {
+            self.select_zero(high - 1) + 1
+        };
+        let mut index = pos - high;
+
+        // SAFETY: there is a successor, so the scan stops before the end of
+        // the high bits.
+        while self.high_bit(pos) {
+            let candidate = self.value(index, pos);
+            if candidate > value || (!STRICT && candidate == value) {
+                return (index, candidate);
+            }
+            pos += 1;
+            index += 1;
+        }
+        // All values with the same high bits are smaller than the given value
+        (index, self.get_unchecked(index))
+    }
+}
+
+impl Succ for AppendableEliasFano {
+    fn succ(&self, value: impl Borrow<Self::Input>) -> Option<(usize, Self::Output)> {
+        if self.n == 0 || *value.borrow() > self.last_value {
+            None
+        } else {
+            Some(unsafe { self.succ_unchecked::<false>(value) })
+        }
+    }
+
+    fn succ_strict(&self, value: impl Borrow<Self::Input>) -> Option<(usize, Self::Output)> {
+        if self.n == 0 || *value.borrow() >= self.last_value {
+            None
+        } else {
+            Some(unsafe { self.succ_unchecked::<true>(value) })
+        }
+    }
+}
+
+impl PredUnchecked for AppendableEliasFano {
+    unsafe fn pred_unchecked<const STRICT: bool>(
+        &self,
+        value: impl Borrow<Self::Input>,
+    ) -> (usize, Self::Output) {
+        let value = *value.borrow();
+        if value > self.last_value || (!STRICT && value == self.last_value) {
+            return (self.n - 1, self.last_value);
+        }
+        let high = value >> self.l;
+        // The position following the last one of the values with the same
+        // high bits; since the last value is greater than or equal to the
+        // given value, the zero exists unless the high bits are the same
+        let mut pos = if high < self.num_zeros() {
+            self.select_zero(high)
+        } else {
+            self.prefix.len() + self.tail.len()
+        };
+        let mut index = pos - high;
+
+        // SAFETY: there is a predecessor, so the scan stops before the start
+        // of the high bits.
+        loop {
+            pos -= 1;
+            if !self.high_bit(pos) {
+                break;
+            }
+            index -= 1;
+            let candidate = self.value(index, pos);
+            if candidate < value || (!STRICT && candidate == value) {
+                return (index, candidate);
+            }
+        }
+        // All values with the same high bits are greater than the given value
+        (index - 1, self.get_unchecked(index - 1))
+    }
+}
+
+impl Pred for AppendableEliasFano {}
+
+impl IndexedDict for AppendableEliasFano {
+    fn index_of(&self, value: impl Borrow<Self::Input>) -> Option<usize> {
+        let value = *value.borrow();
+        match self.succ(value) {
+            Some((index, succ)) if succ == value => Some(index),
+            _ => None,
+        }
+    }
+}
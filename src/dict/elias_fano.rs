@@ -56,18 +56,18 @@
 ///
 /// You can start from this type to customize your Elias–Fano structure using
 /// different const parameters or a different selection structure altogether.
-type EfSeq = EliasFano<SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>>;
+pub type EfSeq = EliasFano<SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>>;
 /// The default type for an Elias–Fano structure implementing an [`Succ`] and [`Pred`].
 ///
 /// You can start from this type to customize your Elias–Fano structure using
 /// different const parameters or a different selection structure altogether.
-type EfDict = EliasFano<SelectZeroAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>>;
+pub type EfDict = EliasFano<SelectZeroAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>>;
 /// The default type for an Elias–Fano structure implementing an
 /// [`IndexedDict`], [`Succ`], and [`Pred`].
 ///
 /// You can start from this type to customize your Elias–Fano structure using
 /// different const parameters or different selection structures altogether.
-type EfSeqDict = EliasFano<
+pub type EfSeqDict = EliasFano<
     SelectZeroAdaptConst<
         SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>,
         Box<[usize]>,
@@ -78,10 +78,16 @@ type EfSeqDict = EliasFano<
 
 use crate::prelude::*;
 use crate::traits::bit_field_slice::*;
+use anyhow::Result;
 use core::sync::atomic::Ordering;
 use epserde::*;
 use mem_dbg::*;
 use std::borrow::Borrow;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Index;
+use std::path::Path;
+use std::sync::atomic::AtomicUsize;
 
 /// An [`IndexedDict`] that stores a monotone sequence of integers using the
 /// Elias–Fano representation.
@@ -191,12 +197,12 @@ impl<H, L> EliasFano<H, L> {
     }
 }
 
-impl<H: AsRef<[usize]>, L: BitFieldSlice<usize>> Types for EliasFano<H, L> {
+impl<H, L: BitFieldSlice<usize>> Types for EliasFano<H, L> {
     type Output = usize;
     type Input = usize;
 }
 
-impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> IndexedSeq for EliasFano<H, L> {
+impl<H: SelectUnchecked, L: BitFieldSlice<usize>> IndexedSeq for EliasFano<H, L> {
     #[inline]
     fn len(&self) -> usize {
         self.n
@@ -210,7 +216,7 @@ impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> IndexedSeq fo
     }
 }
 
-impl<H: AsRef<[usize]> + SelectZeroUnchecked, L: BitFieldSlice<usize>> IndexedDict
+impl<H: Index<usize, Output = bool> + SelectZeroUnchecked, L: BitFieldSlice<usize>> IndexedDict
     for EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
@@ -221,7 +227,7 @@ where
             return None;
         }
         let zeros_to_skip = value >> self.l;
-        let bit_pos = if zeros_to_skip == 0 {
+        let mut bit_pos = if zeros_to_skip == 0 {
             0
         } else {
             unsafe { self.high_bits.select_zero_unchecked(zeros_to_skip - 1) + 1 }
@@ -229,45 +235,31 @@ where
 
         let mut rank = bit_pos - zeros_to_skip;
         let mut iter = self.low_bits.into_unchecked_iter_from(rank);
-        let mut word_idx = bit_pos / (usize::BITS as usize);
-        let bits_to_clean = bit_pos % (usize::BITS as usize);
 
         // SAFETY: we are certainly iterating within the length of the arrays
-        // and within the range of the iterator because there is a successor for sure.
-
-        let mut window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) }
-            & (usize::MAX << bits_to_clean);
+        // and within the range of the iterator because the bucket of the value
+        // is followed by a zero.
 
-        loop {
-            while window == 0 {
-                word_idx += 1;
-                if word_idx >= self.high_bits.as_ref().len() {
-                    return None;
-                }
-                window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
-            }
-            // find the lowest bit set index in the word
-            let bit_idx = window.trailing_zeros() as usize;
-            // compute the global bit index
-            let high_bits = (word_idx * usize::BITS as usize) + bit_idx - rank;
+        // The values with the same high bits as value are associated with the
+        // ones preceding the next zero
+        while self.high_bits[bit_pos] {
             // compose the value
-            let res = (high_bits << self.l) | unsafe { iter.next_unchecked() };
+            let res = (zeros_to_skip << self.l) | unsafe { iter.next_unchecked() };
             if res == value {
                 return Some(rank);
             }
             if res > value {
                 return None;
             }
-
-            // clear the lowest bit set
-            window &= window - 1;
+            bit_pos += 1;
             rank += 1;
         }
+        None
     }
 }
 
 #[allow(clippy::collapsible_else_if)]
-impl<H: AsRef<[usize]> + SelectZeroUnchecked, L: BitFieldSlice<usize>> SuccUnchecked
+impl<H: Index<usize, Output = bool> + SelectZeroUnchecked, L: BitFieldSlice<usize>> SuccUnchecked
     for EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
@@ -278,7 +270,7 @@ where
     ) -> (usize, Self::Output) {
         let value = *value.borrow();
         let zeros_to_skip = value >> self.l;
-        let bit_pos = if zeros_to_skip == 0 {
+        let mut bit_pos = if zeros_to_skip == 0 {
             0
         } else {
             self.high_bits.select_zero_unchecked(zeros_to_skip - 1) + 1
@@ -286,27 +278,17 @@ where
 
         let mut rank = bit_pos - zeros_to_skip;
         let mut iter = self.low_bits.into_unchecked_iter_from(rank);
-        let mut word_idx = bit_pos / (usize::BITS as usize);
-        let bits_to_clean = bit_pos % (usize::BITS as usize);
 
         // SAFETY: we are certainly iterating within the length of the arrays
         // and within the range of the iterator because there is a successor for sure.
 
-        let mut window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) }
-            & (usize::MAX << bits_to_clean);
-
         loop {
-            while window == 0 {
-                word_idx += 1;
-                debug_assert!(word_idx < self.high_bits.as_ref().len());
-                window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
+            // find the next one
+            while !self.high_bits[bit_pos] {
+                bit_pos += 1;
             }
-            // find the lowest bit set index in the word
-            let bit_idx = window.trailing_zeros() as usize;
-            // compute the global bit index
-            let high_bits = (word_idx * usize::BITS as usize) + bit_idx - rank;
             // compose the value
-            let res = (high_bits << self.l) | unsafe { iter.next_unchecked() };
+            let res = ((bit_pos - rank) << self.l) | unsafe { iter.next_unchecked() };
 
             if STRICT {
                 if res > value {
@@ -318,22 +300,63 @@ where
                 }
             }
 
-            // clear the lowest bit set
-            window &= window - 1;
+            bit_pos += 1;
             rank += 1;
         }
     }
 }
 
-impl<H: AsRef<[usize]> + SelectUnchecked + SelectZeroUnchecked, L: BitFieldSlice<usize>> Succ
-    for EliasFano<H, L>
+impl<
+        H: Index<usize, Output = bool> + SelectUnchecked + SelectZeroUnchecked,
+        L: BitFieldSlice<usize>,
+    > Succ for EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
+    /// Return the results of [`succ`](Succ::succ) on a batch of values.
+    ///
+    /// If the batch is sorted, this method delegates to
+    /// [`succ_sorted`](Succ::succ_sorted).
+    fn succ_batch<V: Borrow<usize>>(&self, values: &[V]) -> Vec<Option<(usize, usize)>> {
+        let values = values.iter().map(|value| *value.borrow());
+        if values.clone().is_sorted() {
+            self.succ_sorted(values).collect()
+        } else {
+            values.map(|value| self.succ(value)).collect()
+        }
+    }
+
+    /// Return an iterator over the results of [`succ`](Succ::succ) on a
+    /// nondecreasing sequence of values.
+    ///
+    /// This implementation uses an [`EliasFanoCursor`], so each query starts
+    /// from the position in the high bits of the result of the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not nondecreasing.
+    fn succ_sorted<V: Borrow<usize>>(
+        &self,
+        values: impl IntoIterator<Item = V>,
+    ) -> impl Iterator<Item = Option<(usize, usize)>> {
+        let mut cursor = self.cursor();
+        let mut last_value = 0;
+        values.into_iter().map(move |value| {
+            let value = *value.borrow();
+            if value < last_value {
+                panic!(
+                    "The values provided are not monotone: {} < {}",
+                    value, last_value
+                );
+            }
+            last_value = value;
+            cursor.advance_to(value).map(|succ| (cursor.index(), succ))
+        })
+    }
 }
 
 #[allow(clippy::collapsible_else_if)]
-impl<H: AsRef<[usize]> + SelectZeroUnchecked, L: BitFieldSlice<usize>> PredUnchecked
+impl<H: Index<usize, Output = bool> + SelectZeroUnchecked, L: BitFieldSlice<usize>> PredUnchecked
     for EliasFano<H, L>
 where
     for<'b> &'b L: IntoReverseUncheckedIterator<Item = usize>,
@@ -343,6 +366,11 @@ where
         value: impl Borrow<Self::Input>,
     ) -> (usize, Self::Output) {
         let value = *value.borrow();
+        if value > self.u {
+            // All values are smaller than or equal to the upper bound, so the
+            // (strict) predecessor is the predecessor of the upper bound.
+            return self.pred_unchecked::<false>(self.u);
+        }
         let zeros_to_skip = value >> self.l;
         let mut bit_pos = self.high_bits.select_zero_unchecked(zeros_to_skip) - 1;
 
@@ -354,26 +382,14 @@ where
 
         loop {
             let lower_bits = unsafe { iter.next_unchecked() };
-            let mut word_idx = bit_pos / (usize::BITS as usize);
-            let bit_idx = bit_pos % (usize::BITS as usize);
-            if self.high_bits.get(word_idx) & (1_usize << bit_idx) == 0 {
-                let mut zeros = bit_idx;
-                let mut window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) }
-                    & !(usize::MAX << bit_idx);
-                while window == 0 {
-                    word_idx -= 1;
-                    window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
-                    zeros += usize::BITS as usize;
+            if !self.high_bits[bit_pos] {
+                // The value of given rank is in a preceding bucket, so it is
+                // the predecessor: we find the associated one
+                let mut one_pos = bit_pos - 1;
+                while !self.high_bits[one_pos] {
+                    one_pos -= 1;
                 }
-                return (
-                    rank,
-                    ((usize::BITS as usize) - 1 + bit_pos
-                        - zeros
-                        - window.leading_zeros() as usize
-                        - rank)
-                        << self.l
-                        | lower_bits,
-                );
+                return (rank, (one_pos - rank) << self.l | lower_bits);
             }
 
             if STRICT {
@@ -392,14 +408,69 @@ where
     }
 }
 
-impl<H: AsRef<[usize]> + SelectUnchecked + SelectZeroUnchecked, L: BitFieldSlice<usize>> Pred
-    for EliasFano<H, L>
+impl<
+        H: Index<usize, Output = bool> + SelectUnchecked + SelectZeroUnchecked,
+        L: BitFieldSlice<usize>,
+    > Pred for EliasFano<H, L>
 where
     for<'b> &'b L: IntoReverseUncheckedIterator<Item = usize>,
 {
+    /// Return the results of [`pred`](Pred::pred) on a batch of values.
+    ///
+    /// If the batch is sorted, this method delegates to
+    /// [`pred_sorted`](Pred::pred_sorted).
+    fn pred_batch<V: Borrow<usize>>(&self, values: &[V]) -> Vec<Option<(usize, usize)>> {
+        let values = values.iter().map(|value| *value.borrow());
+        if values.clone().is_sorted() {
+            self.pred_sorted(values).collect()
+        } else {
+            values.map(|value| self.pred(value)).collect()
+        }
+    }
+
+    /// Return an iterator over the results of [`pred`](Pred::pred) on a
+    /// nondecreasing sequence of values.
+    ///
+    /// This implementation uses an [`EliasFanoCursor`] positioned on the
+    /// strict successor of the last value, so each query starts from the
+    /// position in the high bits of the result of the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not nondecreasing.
+    fn pred_sorted<V: Borrow<usize>>(
+        &self,
+        values: impl IntoIterator<Item = V>,
+    ) -> impl Iterator<Item = Option<(usize, usize)>> {
+        let mut cursor = self.cursor();
+        let mut last_value = 0;
+        values.into_iter().map(move |value| {
+            let value = *value.borrow();
+            if value < last_value {
+                panic!(
+                    "The values provided are not monotone: {} < {}",
+                    value, last_value
+                );
+            }
+            last_value = value;
+            // Move to the strict successor; the predecessor precedes it
+            if value < usize::MAX {
+                cursor.advance_to(value + 1);
+            } else {
+                cursor.advance_to(value);
+                if cursor.value() == Some(usize::MAX) {
+                    return Some((self.n - 1, usize::MAX));
+                }
+            }
+            let mut pred = cursor.clone();
+            pred.prev_value()
+                .map(|pred_value| (pred.index(), pred_value))
+        })
+    }
 }
 
-impl<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>> IntoIterator for &'a EliasFano<H, L>
+impl<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> IntoIterator
+    for &'a EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
@@ -412,7 +483,7 @@ where
     }
 }
 
-impl<H: AsRef<[usize]>, L: BitFieldSlice<usize>> EliasFano<H, L>
+impl<H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
@@ -422,7 +493,7 @@ where
     }
 }
 
-impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFano<H, L>
+impl<H: Index<usize, Output = bool> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
@@ -434,42 +505,35 @@ where
 
 /// An iterator for [`EliasFano`].
 #[derive(MemDbg, MemSize)]
-pub struct EliasFanoIterator<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>>
+pub struct EliasFanoIterator<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
     ef: &'a EliasFano<H, L>,
     /// The index of the next value it will be returned when `next` is called.
     index: usize,
-    /// Index of the word loaded in the `word` field.
-    word_idx: usize,
-    /// Current window on the high bits.
-    /// This is an usize because BitVec is implemented only for `Vec<usize>` and `&[usize]`.
-    window: usize,
+    /// The position in the high bits from which the one associated with the
+    /// next value will be searched.
+    bit_pos: usize,
     low_bits: <&'a L as IntoUncheckedIterator>::IntoUncheckedIter,
 }
 
-impl<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>> EliasFanoIterator<'a, H, L>
+impl<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> EliasFanoIterator<'a, H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
     pub fn new(ef: &'a EliasFano<H, L>) -> Self {
-        let word = if ef.high_bits.as_ref().is_empty() {
-            0
-        } else {
-            unsafe { *ef.high_bits.as_ref().get_unchecked(0) }
-        };
         Self {
             ef,
             index: 0,
-            word_idx: 0,
-            window: word,
+            bit_pos: 0,
             low_bits: ef.low_bits.into_unchecked_iter(),
         }
     }
 }
 
-impl<'a, H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFanoIterator<'a, H, L>
+impl<'a, H: Index<usize, Output = bool> + SelectUnchecked, L: BitFieldSlice<usize>>
+    EliasFanoIterator<'a, H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
@@ -477,30 +541,24 @@ where
         if start_index > ef.len() {
             panic!("Index out of bounds: {} > {}", start_index, ef.len());
         }
-        let bit_pos = unsafe { ef.high_bits.select_unchecked(start_index) };
-        let word_idx = bit_pos / (usize::BITS as usize);
-        let bits_to_clean = bit_pos % (usize::BITS as usize);
-
-        let window = if ef.high_bits.as_ref().is_empty() {
+        let bit_pos = if start_index == ef.len() {
+            // the iterator is exhausted, so the position is irrelevant
             0
         } else {
-            // get the word from the high bits
-            let word = unsafe { *ef.high_bits.as_ref().get_unchecked(word_idx) };
-            // clean off the bits that we don't care about
-            word & (usize::MAX << bits_to_clean)
+            unsafe { ef.high_bits.select_unchecked(start_index) }
         };
 
         Self {
             ef,
             index: start_index,
-            word_idx,
-            window,
+            bit_pos,
             low_bits: ef.low_bits.into_unchecked_iter_from(start_index),
         }
     }
 }
 
-impl<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>> Iterator for EliasFanoIterator<'a, H, L>
+impl<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> Iterator
+    for EliasFanoIterator<'a, H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
 {
@@ -511,18 +569,13 @@ where
         if self.index >= self.ef.len() {
             return None;
         }
-        // find the next word with zeros
-        while self.window == 0 {
-            self.word_idx += 1;
-            debug_assert!(self.word_idx < self.ef.high_bits.as_ref().len());
-            self.window = unsafe { *self.ef.high_bits.as_ref().get_unchecked(self.word_idx) };
-        }
-        // find the lowest bit set index in the word
-        let bit_idx = self.window.trailing_zeros() as usize;
-        // compute the global bit index
-        let high_bits = (self.word_idx * usize::BITS as usize) + bit_idx - self.index;
-        // clear the lowest bit set
-        self.window &= self.window - 1;
+        // find the next one
+        while !self.ef.high_bits[self.bit_pos] {
+            self.bit_pos += 1;
+        }
+        // compute the high bits
+        let high_bits = self.bit_pos - self.index;
+        self.bit_pos += 1;
         // compose the value
         let res = (high_bits << self.ef.l) | unsafe { self.low_bits.next_unchecked() };
         self.index += 1;
@@ -535,7 +588,7 @@ where
     }
 }
 
-impl<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>> ExactSizeIterator
+impl<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> ExactSizeIterator
     for EliasFanoIterator<'a, H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
@@ -546,12 +599,285 @@ where
     }
 }
 
+impl<H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> EliasFano<H, L> {
+    /// Returns a [cursor](EliasFanoCursor) positioned on the first value.
+    #[inline(always)]
+    pub fn cursor(&self) -> EliasFanoCursor<'_, H, L> {
+        EliasFanoCursor::new(self)
+    }
+}
+
+impl<H: Index<usize, Output = bool> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFano<H, L> {
+    /// Returns a [cursor](EliasFanoCursor) positioned on the value of given
+    /// index.
+    #[inline(always)]
+    pub fn cursor_from(&self, from: usize) -> EliasFanoCursor<'_, H, L> {
+        EliasFanoCursor::new_from(self, from)
+    }
+}
+
+/// The minimum number of buckets of the high bits that
+/// [`EliasFanoCursor::advance_to`] and [`EliasFanoCursor::retreat_to`] skip
+/// using a selection on zeros rather than a linear scan.
+const CURSOR_SKIP_BUCKETS: usize = usize::BITS as usize;
+
+/// A bidirectional cursor over an [`EliasFano`].
+///
+/// Differently from an [`EliasFanoIterator`], a cursor is always positioned
+/// on a value, or past the end of the sequence, and it can move in both
+/// directions. Moreover, [`advance_to`](EliasFanoCursor::advance_to) and
+/// [`retreat_to`](EliasFanoCursor::retreat_to) move the cursor to the first
+/// following (last preceding) value greater (smaller) than or equal to a
+/// given value: for example, they are the basic building block of the
+/// intersection of sorted lists.
+///
+/// Short moves are performed by scanning the high bits; long forward and
+/// backward skips use the selection structure on zeros of the high bits, if
+/// available (e.g., in the case of a [`SelectZeroAdaptConst`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::EliasFanoBuilder;
+/// let mut efb = EliasFanoBuilder::new(5, 100);
+/// for value in [0, 2, 8, 10, 90] {
+///     efb.push(value);
+/// }
+/// let ef = efb.build_with_dict();
+///
+/// let mut cursor = ef.cursor();
+/// assert_eq!(cursor.value(), Some(0));
+/// assert_eq!(cursor.advance_to(5), Some(8));
+/// assert_eq!(cursor.index(), 2);
+/// assert_eq!(cursor.next_value(), Some(10));
+/// assert_eq!(cursor.retreat_to(9), Some(8));
+/// assert_eq!(cursor.prev_value(), Some(2));
+/// assert_eq!(cursor.advance_to(91), None);
+/// assert_eq!(cursor.index(), 5);
+/// ```
+#[derive(Debug, MemDbg, MemSize)]
+pub struct EliasFanoCursor<'a, H, L> {
+    ef: &'a EliasFano<H, L>,
+    /// The index of the current value, or the length of the sequence.
+    index: usize,
+    /// The position in the high bits of the one associated with the current
+    /// value, or the length of the high bits.
+    high_pos: usize,
+}
+
+// Manual implementation to avoid the bounds on H and L of the derive
+impl<'a, H, L> Clone for EliasFanoCursor<'a, H, L> {
+    fn clone(&self) -> Self {
+        Self {
+            ef: self.ef,
+            index: self.index,
+            high_pos: self.high_pos,
+        }
+    }
+}
+
+impl<'a, H: Index<usize, Output = bool>, L: BitFieldSlice<usize>> EliasFanoCursor<'a, H, L> {
+    pub fn new(ef: &'a EliasFano<H, L>) -> Self {
+        let mut cursor = Self {
+            ef,
+            index: 0,
+            high_pos: 0,
+        };
+        cursor.high_pos = if ef.n == 0 {
+            cursor.high_len()
+        } else {
+            cursor.next_one(0)
+        };
+        cursor
+    }
+
+    /// Returns the length in bits of the high bits.
+    #[inline(always)]
+    fn high_len(&self) -> usize {
+        self.ef.n + (self.ef.u >> self.ef.l) + 1
+    }
+
+    /// Returns the position of the first one at or after `pos`, which must
+    /// exist.
+    #[inline(always)]
+    fn next_one(&self, mut pos: usize) -> usize {
+        while !self.ef.high_bits[pos] {
+            pos += 1;
+        }
+        pos
+    }
+
+    /// Returns the position of the last one before `pos`, which must exist.
+    #[inline(always)]
+    fn prev_one(&self, mut pos: usize) -> usize {
+        loop {
+            debug_assert!(pos > 0);
+            pos -= 1;
+            if self.ef.high_bits[pos] {
+                return pos;
+            }
+        }
+    }
+
+    /// Moves the cursor to the given index, given the position of the
+    /// associated one in the high bits.
+    #[inline(always)]
+    fn set(&mut self, index: usize, high_pos: usize) -> Option<usize> {
+        self.index = index;
+        self.high_pos = high_pos;
+        self.value()
+    }
+
+    /// Returns the index of the current value, or the length of the sequence
+    /// if the cursor is past the end.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the current value, or `None` if the cursor is past the end.
+    #[inline(always)]
+    pub fn value(&self) -> Option<usize> {
+        if self.index >= self.ef.n {
+            return None;
+        }
+        let high_bits = self.high_pos - self.index;
+        Some((high_bits << self.ef.l) | unsafe { self.ef.low_bits.get_unchecked(self.index) })
+    }
+
+    /// Moves the cursor to the next value and returns it, or returns `None`
+    /// and moves the cursor past the end if there is no next value.
+    ///
+    /// If the cursor is already past the end, it is not moved.
+    #[inline]
+    pub fn next_value(&mut self) -> Option<usize> {
+        if self.index + 1 >= self.ef.n {
+            self.index = self.ef.n;
+            self.high_pos = self.high_len();
+            return None;
+        }
+        self.set(self.index + 1, self.next_one(self.high_pos + 1))
+    }
+
+    /// Moves the cursor to the previous value and returns it, or returns
+    /// `None` without moving the cursor if the cursor is on the first value.
+    ///
+    /// If the cursor is past the end, it is moved to the last value.
+    #[inline]
+    pub fn prev_value(&mut self) -> Option<usize> {
+        if self.index == 0 {
+            return None;
+        }
+        self.set(self.index - 1, self.prev_one(self.high_pos))
+    }
+}
+
+impl<'a, H: Index<usize, Output = bool> + SelectUnchecked, L: BitFieldSlice<usize>>
+    EliasFanoCursor<'a, H, L>
+{
+    /// Creates a cursor positioned on the value of given index, or past the
+    /// end if `start_index` is the length of the sequence.
+    pub fn new_from(ef: &'a EliasFano<H, L>, start_index: usize) -> Self {
+        if start_index > ef.len() {
+            panic!("Index out of bounds: {} > {}", start_index, ef.len());
+        }
+        let mut cursor = Self::new(ef);
+        if start_index == ef.n {
+            cursor.index = ef.n;
+            cursor.high_pos = cursor.high_len();
+        } else {
+            cursor.index = start_index;
+            cursor.high_pos = unsafe { ef.high_bits.select_unchecked(start_index) };
+        }
+        cursor
+    }
+}
+
+impl<'a, H: Index<usize, Output = bool> + SelectZeroUnchecked, L: BitFieldSlice<usize>>
+    EliasFanoCursor<'a, H, L>
+{
+    /// Moves the cursor to the first value greater than or equal to `value`
+    /// among the current value and the following ones, and returns it.
+    ///
+    /// If there is no such value, the cursor is moved past the end and `None`
+    /// is returned.
+    pub fn advance_to(&mut self, value: usize) -> Option<usize> {
+        let mut current = self.value()?;
+        if current >= value {
+            return Some(current);
+        }
+        let bucket = value >> self.ef.l;
+        if bucket > self.ef.u >> self.ef.l {
+            // All values are smaller than value
+            self.index = self.ef.n;
+            self.high_pos = self.high_len();
+            return None;
+        }
+
+        if bucket - (current >> self.ef.l) >= CURSOR_SKIP_BUCKETS {
+            // Skip to the first one after the bucket-th zero
+            let bit_pos = unsafe { self.ef.high_bits.select_zero_unchecked(bucket - 1) } + 1;
+            let index = bit_pos - bucket;
+            if index >= self.ef.n {
+                self.index = self.ef.n;
+                self.high_pos = self.high_len();
+                return None;
+            }
+            current = self.set(index, self.next_one(bit_pos))?;
+        }
+
+        while current < value {
+            current = self.next_value()?;
+        }
+        Some(current)
+    }
+
+    /// Moves the cursor to the last value smaller than or equal to `value`
+    /// among the current value and the preceding ones, and returns it.
+    ///
+    /// If the cursor is past the end, the search starts from the last value.
+    /// If there is no such value, the cursor is moved to the first value and
+    /// `None` is returned.
+    pub fn retreat_to(&mut self, value: usize) -> Option<usize> {
+        if self.ef.n == 0 {
+            return None;
+        }
+        let mut current = match self.value() {
+            Some(current) => current,
+            None => self.prev_value()?,
+        };
+        if current <= value {
+            return Some(current);
+        }
+        let bucket = value >> self.ef.l;
+        if (current >> self.ef.l) - bucket >= CURSOR_SKIP_BUCKETS {
+            // Skip to the last one before the (bucket + 1)-th zero
+            let bit_pos = unsafe { self.ef.high_bits.select_zero_unchecked(bucket) };
+            let index = bit_pos - bucket;
+            if index == 0 {
+                self.set(0, self.next_one(0));
+                return None;
+            }
+            current = self.set(index - 1, self.prev_one(bit_pos))?;
+        }
+
+        while current > value {
+            current = self.prev_value()?;
+        }
+        Some(current)
+    }
+}
+
 /// A sequential builder for [`EliasFano`].
 ///
 /// After creating an instance, you can use [`EliasFanoBuilder::push`] to add
 /// new values, and then call [`EliasFanoBuilder::build`] to create the
 /// [`EliasFano`] instance.
 ///
+/// A builder created with [`EliasFanoBuilder::new_mmap`] stores the high and
+/// low bits in [memory-mapped files](crate::bits::mmap_backend), so it can
+/// build structures larger than the available memory.
+///
 /// # Examples
 ///
 /// ```rust
@@ -572,12 +898,12 @@ where
 /// assert_eq!(iter.next(), None);
 /// ```
 #[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct EliasFanoBuilder {
+pub struct EliasFanoBuilder<H = BitVec, L = BitFieldVec> {
     n: usize,
     u: usize,
     l: usize,
-    low_bits: BitFieldVec,
-    high_bits: BitVec,
+    low_bits: L,
+    high_bits: H,
     last_value: usize,
     count: usize,
 }
@@ -602,7 +928,63 @@ impl EliasFanoBuilder {
             count: 0,
         }
     }
+}
+
+impl EliasFanoBuilder<BitVec<MmapBackend>, BitFieldVec<usize, MmapBackend>> {
+    /// Creates a builder for an [`EliasFano`] containing `n` numbers smaller
+    /// than or equal to `u` whose high and low bits are stored in writable
+    /// memory mappings of the files at the given paths, which are created or
+    /// truncated.
+    ///
+    /// See [`BitVec::new_mmap`] and [`BitFieldVec::new_mmap`].
+    pub fn new_mmap(
+        n: usize,
+        u: usize,
+        high_bits: impl AsRef<Path>,
+        low_bits: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let l = if u >= n {
+            (u as f64 / n as f64).log2().floor() as usize
+        } else {
+            0
+        };
+
+        Ok(Self {
+            n,
+            u,
+            l,
+            low_bits: BitFieldVec::new_mmap(low_bits, l, n)?,
+            high_bits: BitVec::new_mmap(high_bits, n + (u >> l) + 1)?,
+            last_value: 0,
+            count: 0,
+        })
+    }
 
+    /// Builds an Elias–Fano structure whose high and low bits are stored in
+    /// memory-mapped files.
+    ///
+    /// The high and low bits are [flushed](BitVec::flush), so their files can
+    /// be loaded by ε-serde as a `BitVec<Vec<usize>>` and a
+    /// `BitFieldVec<usize, Vec<usize>>`, respectively.
+    ///
+    /// The resulting structure has no selection structure attached: see
+    /// [`EliasFanoBuilder::build`].
+    pub fn build(
+        mut self,
+    ) -> Result<EliasFano<BitVec<MmapBackend>, BitFieldVec<usize, MmapBackend>>> {
+        self.high_bits.flush()?;
+        self.low_bits.flush()?;
+        Ok(EliasFano {
+            n: self.n,
+            u: self.u,
+            l: self.l,
+            low_bits: self.low_bits,
+            high_bits: self.high_bits,
+        })
+    }
+}
+
+impl<B: AsWordsMut<Word = usize>, L: BitFieldSliceMut<usize>> EliasFanoBuilder<BitVec<B>, L> {
     /// Adds a new value to the builder.
     ///
     /// # Panic
@@ -640,7 +1022,9 @@ impl EliasFanoBuilder {
         self.count += 1;
         self.last_value = value;
     }
+}
 
+impl EliasFanoBuilder {
     /// Builds an Elias-Fano structure.
     ///
     /// The resulting structure has no selection structure attached. To use it
@@ -695,6 +1079,136 @@ impl EliasFanoBuilder {
     }
 }
 
+/// A builder for [`EliasFano`] that does not need to know in advance the
+/// number of values and their upper bound.
+///
+/// Values are written to a temporary file as they are pushed; when one of the
+/// build methods is called, the values are read back and encoded using an
+/// [`EliasFanoBuilder`] whose number of values and upper bound are those of
+/// the pushed values. Thus, the builder uses a constant amount of memory, and
+/// the resulting structure is identical to the one that would be obtained
+/// knowing `n` and `u` in advance, with `u` equal to the last value.
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::EliasFanoStreamingBuilder;
+/// # use sux::traits::IndexedSeq;
+/// let mut efb = EliasFanoStreamingBuilder::new()?;
+/// for value in [0, 2, 8, 10] {
+///     efb.push(value)?;
+/// }
+///
+/// let ef = efb.build_with_seq()?;
+/// assert_eq!(ef.len(), 4);
+/// assert_eq!(ef.get(2), 8);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct EliasFanoStreamingBuilder {
+    /// The temporary file containing the values as little-endian 64-bit
+    /// integers.
+    writer: BufWriter<File>,
+    last_value: usize,
+    count: usize,
+}
+
+impl EliasFanoStreamingBuilder {
+    /// Creates a new builder backed by a temporary file.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(tempfile::tempfile()?),
+            last_value: 0,
+            count: 0,
+        })
+    }
+
+    /// Adds a new value to the builder.
+    ///
+    /// # Panic
+    /// Panics if the value is smaller than the last provided value.
+    pub fn push(&mut self, value: usize) -> std::io::Result<()> {
+        if value < self.last_value {
+            panic!(
+                "The values provided are not monotone: {} < {}",
+                value, self.last_value
+            );
+        }
+        self.writer.write_all(&(value as u64).to_le_bytes())?;
+        self.count += 1;
+        self.last_value = value;
+        Ok(())
+    }
+
+    /// Adds values to the builder.
+    ///
+    /// # Panic
+    /// Panics if the values are not monotone.
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = usize>) -> std::io::Result<()> {
+        for value in iter {
+            self.push(value)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of values added to the builder so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns whether no value has been added to the builder so far.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Reads back the values into an [`EliasFanoBuilder`] with the
+    /// number of values and the upper bound of the pushed values.
+    fn into_builder(self) -> Result<EliasFanoBuilder> {
+        let mut file = self.writer.into_inner()?;
+        file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(file);
+        let mut efb = EliasFanoBuilder::new(self.count, self.last_value);
+        let mut buffer = [0; 8];
+        for _ in 0..self.count {
+            reader.read_exact(&mut buffer)?;
+            // SAFETY: the values have been checked by push
+            unsafe { efb.push_unchecked(u64::from_le_bytes(buffer) as usize) };
+        }
+        Ok(efb)
+    }
+
+    /// Builds an Elias-Fano structure.
+    ///
+    /// See [`EliasFanoBuilder::build`].
+    pub fn build(self) -> Result<EliasFano> {
+        Ok(self.into_builder()?.build())
+    }
+
+    /// Builds an Elias-Fano structure with constant-time access, using
+    /// default values.
+    ///
+    /// See [`EliasFanoBuilder::build_with_seq`].
+    pub fn build_with_seq(self) -> Result<EfSeq> {
+        Ok(self.into_builder()?.build_with_seq())
+    }
+
+    /// Builds an Elias-Fano structure with constant-time indexing, using
+    /// default values.
+    ///
+    /// See [`EliasFanoBuilder::build_with_dict`].
+    pub fn build_with_dict(self) -> Result<EfDict> {
+        Ok(self.into_builder()?.build_with_dict())
+    }
+
+    /// Builds an Elias-Fano structure with constant-time access and indexing,
+    /// using default values.
+    ///
+    /// See [`EliasFanoBuilder::build_with_seq_and_dict`].
+    pub fn build_with_seq_and_dict(self) -> Result<EfSeqDict> {
+        Ok(self.into_builder()?.build_with_seq_and_dict())
+    }
+}
+
 /// A concurrent builder for [`EliasFano`].
 ///
 /// After creating an instance, you can use [`EliasFanoConcurrentBuilder::set`]
@@ -724,12 +1238,12 @@ impl EliasFanoBuilder {
 /// ```
 
 #[derive(MemDbg, MemSize)]
-pub struct EliasFanoConcurrentBuilder {
+pub struct EliasFanoConcurrentBuilder<H = AtomicBitVec, L = AtomicBitFieldVec> {
     n: usize,
     u: usize,
     l: usize,
-    low_bits: AtomicBitFieldVec,
-    high_bits: AtomicBitVec,
+    low_bits: L,
+    high_bits: H,
 }
 
 impl EliasFanoConcurrentBuilder {
@@ -751,6 +1265,78 @@ impl EliasFanoConcurrentBuilder {
         }
     }
 
+    pub fn build(self) -> EliasFano {
+        let high_bits: BitVec<Vec<usize>> = self.high_bits.into();
+        let high_bits: BitVec<Box<[usize]>> = high_bits.into();
+        let low_bits: BitFieldVec<usize, Vec<usize>> = self.low_bits.into();
+        let low_bits: BitFieldVec<usize, Box<[usize]>> = low_bits.into();
+        EliasFano {
+            n: self.n,
+            u: self.u,
+            l: self.l,
+            low_bits,
+            high_bits,
+        }
+    }
+}
+
+impl
+    EliasFanoConcurrentBuilder<
+        AtomicBitVec<MmapBackend<AtomicUsize>>,
+        AtomicBitFieldVec<usize, MmapBackend<AtomicUsize>>,
+    >
+{
+    /// Creates a concurrent builder for a sequence containing `n` nonnegative
+    /// numbers smaller than or equal to `u` whose high and low bits are stored
+    /// in writable memory mappings of the files at the given paths, which are
+    /// created or truncated.
+    ///
+    /// See [`EliasFanoBuilder::new_mmap`].
+    pub fn new_mmap(
+        n: usize,
+        u: usize,
+        high_bits: impl AsRef<Path>,
+        low_bits: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let l = if u >= n {
+            (u as f64 / n as f64).log2().floor() as usize
+        } else {
+            0
+        };
+
+        Ok(Self {
+            u,
+            n,
+            l,
+            low_bits: BitFieldVec::<usize, MmapBackend>::new_mmap(low_bits, l, n)?.into(),
+            high_bits: BitVec::new_mmap(high_bits, n + (u >> l) + 1)?.into(),
+        })
+    }
+
+    /// Builds an Elias–Fano structure whose high and low bits are stored in
+    /// memory-mapped files.
+    ///
+    /// The high and low bits are [flushed](BitVec::flush), so their files can
+    /// be loaded by ε-serde as a `BitVec<Vec<usize>>` and a
+    /// `BitFieldVec<usize, Vec<usize>>`, respectively.
+    pub fn build(self) -> Result<EliasFano<BitVec<MmapBackend>, BitFieldVec<usize, MmapBackend>>> {
+        let mut high_bits: BitVec<MmapBackend> = self.high_bits.into();
+        let mut low_bits: BitFieldVec<usize, MmapBackend> = self.low_bits.into();
+        high_bits.flush()?;
+        low_bits.flush()?;
+        Ok(EliasFano {
+            n: self.n,
+            u: self.u,
+            l: self.l,
+            low_bits,
+            high_bits,
+        })
+    }
+}
+
+impl<B: AsAtomicWords<Word = usize>, L: AtomicBitFieldSlice<usize>>
+    EliasFanoConcurrentBuilder<AtomicBitVec<B>, L>
+{
     /// Sets a value concurrently.
     ///
     /// # Safety
@@ -768,18 +1354,4 @@ impl EliasFanoConcurrentBuilder {
         let high = (value >> self.l) + index;
         self.high_bits.set(high, true, Ordering::Relaxed);
     }
-
-    pub fn build(self) -> EliasFano {
-        let high_bits: BitVec<Vec<usize>> = self.high_bits.into();
-        let high_bits: BitVec<Box<[usize]>> = high_bits.into();
-        let low_bits: BitFieldVec<usize, Vec<usize>> = self.low_bits.into();
-        let low_bits: BitFieldVec<usize, Box<[usize]>> = low_bits.into();
-        EliasFano {
-            n: self.n,
-            u: self.u,
-            l: self.l,
-            low_bits,
-            high_bits,
-        }
-    }
 }
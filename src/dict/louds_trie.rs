@@ -0,0 +1,517 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Succinct tries of strings represented by LOUDS.
+//!
+//! The *level-order unary degree sequence* (LOUDS) of a tree is obtained by
+//! visiting the tree in breadth-first order and writing, for each node, its
+//! degree in unary. Nodes are numbered in breadth-first order, and the
+//! children of a node are found by selecting zeros in the sequence.
+//!
+//! LOUDS has been introduced by Guy Jacobson in “[Space-efficient static trees
+//! and graphs](https://doi.org/10.1109/SFCS.1989.63533)”, _30th Annual
+//! Symposium on Foundations of Computer Science_, pages 549–554, IEEE, 1989.
+
+use crate::dict::rear_coded_list::{decode_int, encode_int, longest_common_prefix};
+use crate::prelude::*;
+use epserde::*;
+use mem_dbg::*;
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// The number of tails in a front-coded block.
+const TAILS_PER_BLOCK: usize = 8;
+
+/// An immutable trie over a sorted set of strings, represented by LOUDS with
+/// front-coded tails.
+///
+/// The trie contains a node for each prefix shared by at least two strings;
+/// the remaining suffix of each string (its *tail*) is stored separately using
+/// front coding in blocks of eight strings. The identifier of a string is its
+/// position in lexicographical order, so a [`LoudsTrie`] can be used in place
+/// of a sorted [`RearCodedList`](crate::dict::RearCodedList).
+///
+/// Besides implementing [`IndexedSeq`] and [`IndexedDict`], a trie can
+/// [compute the range](LoudsTrie::prefix_range) of the strings starting with a
+/// given prefix, and [iterate](LoudsTrie::iter_prefix) over them.
+///
+/// To build a [`LoudsTrie`] you use a [`LoudsTrieBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::traits::{IndexedDict, IndexedSeq};
+/// use sux::dict::LoudsTrieBuilder;
+/// let mut builder = LoudsTrieBuilder::new();
+///
+/// builder.push("car");
+/// builder.push("card");
+/// builder.push("care");
+/// builder.push("cat");
+/// builder.push("dog");
+///
+/// let trie = builder.build();
+/// assert_eq!(trie.len(), 5);
+/// assert_eq!(trie.get(2), "care");
+/// assert_eq!(trie.index_of("cat"), Some(3));
+/// assert_eq!(trie.index_of("ca"), None);
+/// assert_eq!(trie.prefix_range("car"), 0..3);
+/// assert_eq!(
+///     trie.iter_prefix("ca").collect::<Vec<_>>(),
+///     vec!["car", "card", "care", "cat"]
+/// );
+/// ```
+#[derive(Debug, Clone, Epserde, MemDbg, MemSize)]
+pub struct LoudsTrie<
+    L = SelectZeroAdaptConst<BitVec<Box<[usize]>>>,
+    T = BitVec<Box<[usize]>>,
+    S = BitFieldVec<usize, Box<[usize]>>,
+    D = Box<[u8]>,
+    P = Box<[usize]>,
+> {
+    /// The number of strings.
+    len: usize,
+    /// The LOUDS of the trie, preceded by `10` (the super-root).
+    louds: L,
+    /// The label of the edge entering each node (zero for the root).
+    labels: D,
+    /// Whether each internal node corresponds to a string.
+    terminal: T,
+    /// For each node, the number of strings smaller than the strings in its
+    /// subtree.
+    lex_start: S,
+    /// The front-coded tails, one for each string.
+    tails: D,
+    /// The pointer to the starting tail of each block.
+    pointers: P,
+}
+
+impl<L, T, S, D, P> LoudsTrie<L, T, S, D, P> {
+    /// Returns the number of strings.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<
+        L: SelectZeroUnchecked,
+        T: AsRef<[usize]>,
+        S: BitFieldSlice<usize>,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > LoudsTrie<L, T, S, D, P>
+{
+    /// Returns the range of identifiers of the children of a node.
+    #[inline(always)]
+    fn children(&self, node: usize) -> Range<usize> {
+        unsafe {
+            self.louds.select_zero_unchecked(node) - node
+                ..self.louds.select_zero_unchecked(node + 1) - node - 1
+        }
+    }
+
+    #[inline(always)]
+    fn label(&self, node: usize) -> u8 {
+        self.labels.as_ref()[node]
+    }
+
+    #[inline(always)]
+    fn lex_start(&self, node: usize) -> usize {
+        unsafe { self.lex_start.get_unchecked(node) }
+    }
+
+    #[inline(always)]
+    fn is_terminal(&self, node: usize) -> bool {
+        self.terminal.as_ref()[node / usize::BITS as usize] >> (node % usize::BITS as usize) & 1
+            != 0
+    }
+
+    /// Finds among the given children the one with the given label, or
+    /// returns the first child with a larger label.
+    #[inline]
+    fn find_child(&self, children: Range<usize>, label: u8) -> Result<usize, usize> {
+        self.labels.as_ref()[children.clone()]
+            .binary_search(&label)
+            .map(|i| children.start + i)
+            .map_err(|i| children.start + i)
+    }
+
+    /// Appends the tail of the string of given index to `result`.
+    fn tail_in_place(&self, index: usize, result: &mut Vec<u8>) {
+        let start = self.pointers.as_ref()[index / TAILS_PER_BLOCK];
+        let mut data = &self.tails.as_ref()[start..];
+        let base = result.len();
+        let (len, rest) = decode_int(data);
+        result.extend_from_slice(&rest[..len]);
+        data = &rest[len..];
+        for _ in 0..index % TAILS_PER_BLOCK {
+            let (lcp, rest) = decode_int(data);
+            let (len, rest) = decode_int(rest);
+            result.truncate(base + lcp);
+            result.extend_from_slice(&rest[..len]);
+            data = &rest[len..];
+        }
+    }
+
+    /// Writes the index-th string to `result` as bytes. This is useful to avoid
+    /// allocating a new string for every query and skipping the UTF-8 validity
+    /// check.
+    pub fn get_in_place(&self, index: usize, result: &mut Vec<u8>) {
+        result.clear();
+        let mut node = 0;
+        loop {
+            let children = self.children(node);
+            if children.is_empty() {
+                self.tail_in_place(index, result);
+                return;
+            }
+            if self.is_terminal(node) && self.lex_start(node) == index {
+                return;
+            }
+            // Find the last child whose subtree starts at or before index
+            let (mut lo, mut hi) = (children.start, children.end);
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                if self.lex_start(mid) <= index {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            result.push(self.label(lo));
+            node = lo;
+        }
+    }
+
+    /// Descends the trie following `prefix`.
+    ///
+    /// Returns the node at which the descent stopped, the length of the path
+    /// leading to it, and the end of the range of identifiers of its subtree;
+    /// or, if no string starts with `prefix`, the position at which `prefix`
+    /// would be inserted.
+    fn descend(&self, prefix: &[u8]) -> Result<(usize, usize, usize), usize> {
+        if self.len == 0 {
+            return Err(0);
+        }
+        let mut node = 0;
+        let mut end = self.len;
+        for (depth, &byte) in prefix.iter().enumerate() {
+            let children = self.children(node);
+            if children.is_empty() {
+                let index = self.lex_start(node);
+                let mut tail = Vec::with_capacity(128);
+                self.tail_in_place(index, &mut tail);
+                let rest = &prefix[depth..];
+                return if tail.starts_with(rest) {
+                    Ok((node, depth, end))
+                } else if tail.as_slice() < rest {
+                    Err(index + 1)
+                } else {
+                    Err(index)
+                };
+            }
+            match self.find_child(children.clone(), byte) {
+                Ok(child) => {
+                    if child + 1 < children.end {
+                        end = self.lex_start(child + 1);
+                    }
+                    node = child;
+                }
+                Err(child) => {
+                    return Err(if child < children.end {
+                        self.lex_start(child)
+                    } else {
+                        end
+                    })
+                }
+            }
+        }
+        Ok((node, prefix.len(), end))
+    }
+
+    /// Returns the range of identifiers of the strings starting with
+    /// `prefix`.
+    ///
+    /// If no string starts with `prefix`, the returned range is empty and
+    /// starts at the position at which `prefix` would be inserted.
+    pub fn prefix_range(&self, prefix: impl AsRef<str>) -> Range<usize> {
+        match self.descend(prefix.as_ref().as_bytes()) {
+            Ok((node, _, end)) => self.lex_start(node)..end,
+            Err(pos) => pos..pos,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the strings starting with `prefix`, in
+    /// lexicographical order.
+    pub fn iter_prefix(&self, prefix: impl AsRef<str>) -> Iter<'_, L, T, S, D, P> {
+        let prefix = prefix.as_ref().as_bytes();
+        let mut iter = Iter {
+            trie: self,
+            stack: vec![],
+            buffer: Vec::with_capacity(128),
+        };
+        if let Ok((node, depth, _)) = self.descend(prefix) {
+            iter.buffer.extend_from_slice(&prefix[..depth]);
+            iter.stack.push((node, depth, false));
+        }
+        iter
+    }
+
+    /// Returns an [`Iterator`] over the strings, in lexicographical order.
+    pub fn iter(&self) -> Iter<'_, L, T, S, D, P> {
+        self.iter_prefix("")
+    }
+}
+
+impl<L, T, S, D, P> Types for LoudsTrie<L, T, S, D, P> {
+    type Output = String;
+    type Input = str;
+}
+
+impl<
+        L: SelectZeroUnchecked,
+        T: AsRef<[usize]>,
+        S: BitFieldSlice<usize>,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > IndexedSeq for LoudsTrie<L, T, S, D, P>
+{
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> Self::Output {
+        let mut result = Vec::with_capacity(128);
+        self.get_in_place(index, &mut result);
+        String::from_utf8_unchecked(result)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<
+        L: SelectZeroUnchecked,
+        T: AsRef<[usize]>,
+        S: BitFieldSlice<usize>,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > IndexedDict for LoudsTrie<L, T, S, D, P>
+{
+    fn index_of(&self, value: impl Borrow<Self::Input>) -> Option<usize> {
+        let string = value.borrow().as_bytes();
+        if self.len == 0 {
+            return None;
+        }
+        let mut node = 0;
+        let mut tail = Vec::with_capacity(128);
+        for (depth, &byte) in string.iter().enumerate() {
+            let children = self.children(node);
+            if children.is_empty() {
+                let index = self.lex_start(node);
+                self.tail_in_place(index, &mut tail);
+                return (tail == string[depth..]).then_some(index);
+            }
+            node = self.find_child(children, byte).ok()?;
+        }
+        if self.children(node).is_empty() {
+            let index = self.lex_start(node);
+            self.tail_in_place(index, &mut tail);
+            tail.is_empty().then_some(index)
+        } else {
+            self.is_terminal(node).then(|| self.lex_start(node))
+        }
+    }
+}
+
+impl<
+        'a,
+        L: SelectZeroUnchecked,
+        T: AsRef<[usize]>,
+        S: BitFieldSlice<usize>,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > IntoIterator for &'a LoudsTrie<L, T, S, D, P>
+{
+    type Item = String;
+    type IntoIter = Iter<'a, L, T, S, D, P>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An [`Iterator`] over the strings of a [`LoudsTrie`] in lexicographical
+/// order, performing a depth-first visit of the trie.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct Iter<'a, L, T, S, D, P> {
+    trie: &'a LoudsTrie<L, T, S, D, P>,
+    /// The nodes to visit, with the length of the path leading to their parent
+    /// and whether their label must be appended to the path.
+    stack: Vec<(usize, usize, bool)>,
+    /// The path leading to the current node.
+    buffer: Vec<u8>,
+}
+
+impl<
+        'a,
+        L: SelectZeroUnchecked,
+        T: AsRef<[usize]>,
+        S: BitFieldSlice<usize>,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > Iterator for Iter<'a, L, T, S, D, P>
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let (node, depth, push_label) = self.stack.pop()?;
+            self.buffer.truncate(depth);
+            if push_label {
+                self.buffer.push(self.trie.label(node));
+            }
+            let children = self.trie.children(node);
+            if children.is_empty() {
+                self.trie
+                    .tail_in_place(self.trie.lex_start(node), &mut self.buffer);
+                return Some(unsafe { String::from_utf8_unchecked(self.buffer.clone()) });
+            }
+            let depth = self.buffer.len();
+            self.stack
+                .extend(children.rev().map(|child| (child, depth, true)));
+            if self.trie.is_terminal(node) {
+                return Some(unsafe { String::from_utf8_unchecked(self.buffer.clone()) });
+            }
+        }
+    }
+}
+
+/// Builder for a [`LoudsTrie`].
+///
+/// Strings must be pushed in strictly increasing lexicographical order.
+#[derive(Debug, Clone, Default, MemDbg, MemSize)]
+pub struct LoudsTrieBuilder {
+    /// The concatenated strings.
+    data: Vec<u8>,
+    /// The end of each string in `data`.
+    ends: Vec<usize>,
+}
+
+impl LoudsTrieBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn string(&self, index: usize) -> &[u8] {
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        &self.data[start..self.ends[index]]
+    }
+
+    /// Adds a string to the trie.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not greater than the previous one.
+    pub fn push(&mut self, string: impl AsRef<str>) {
+        let string = string.as_ref();
+        if let Some(last) = self.ends.len().checked_sub(1) {
+            let last = self.string(last);
+            assert!(
+                string.as_bytes() > last,
+                "The strings provided are not strictly increasing: {:?} <= {:?}",
+                string,
+                String::from_utf8_lossy(last)
+            );
+        }
+        self.data.extend_from_slice(string.as_bytes());
+        self.ends.push(self.data.len());
+    }
+
+    /// Builds the trie.
+    pub fn build(self) -> LoudsTrie {
+        let len = self.ends.len();
+        let mut louds = BitVec::new(0);
+        louds.push(true);
+        louds.push(false);
+        let mut labels = vec![0];
+        let mut terminal = BitVec::new(0);
+        let mut lex_start = vec![];
+        let mut tails = vec![vec![]; len];
+
+        // Each node is represented by the range of its strings and the length
+        // of its path
+        let mut queue = VecDeque::from([(0, len, 0)]);
+        while let Some((start, end, depth)) = queue.pop_front() {
+            lex_start.push(start);
+            if end - start <= 1 {
+                // A leaf (or the root of an empty trie)
+                if start < end {
+                    tails[start] = self.string(start)[depth..].to_vec();
+                }
+                terminal.push(false);
+                louds.push(false);
+                continue;
+            }
+            let mut first = start;
+            let is_terminal = self.string(start).len() == depth;
+            terminal.push(is_terminal);
+            if is_terminal {
+                first += 1;
+            }
+            // Group the remaining strings by their byte at depth
+            while first < end {
+                let label = self.string(first)[depth];
+                let mut last = first + 1;
+                while last < end && self.string(last)[depth] == label {
+                    last += 1;
+                }
+                louds.push(true);
+                labels.push(label);
+                queue.push_back((first, last, depth + 1));
+                first = last;
+            }
+            louds.push(false);
+        }
+
+        let mut lex_start_vec =
+            BitFieldVec::<usize>::new(len.max(1).ilog2() as usize + 1, lex_start.len());
+        for (node, &start) in lex_start.iter().enumerate() {
+            lex_start_vec.set(node, start);
+        }
+
+        // Front-code the tails
+        let mut data = vec![];
+        let mut pointers = vec![];
+        for (index, tail) in tails.iter().enumerate() {
+            if index % TAILS_PER_BLOCK == 0 {
+                pointers.push(data.len());
+                encode_int(tail.len(), &mut data);
+                data.extend_from_slice(tail);
+            } else {
+                let lcp = longest_common_prefix(&tails[index - 1], tail).0;
+                encode_int(lcp, &mut data);
+                encode_int(tail.len() - lcp, &mut data);
+                data.extend_from_slice(&tail[lcp..]);
+            }
+        }
+
+        LoudsTrie {
+            len,
+            louds: SelectZeroAdaptConst::new(louds.into()),
+            labels: labels.into(),
+            terminal: terminal.into(),
+            lex_start: lex_start_vec.into(),
+            tails: data.into(),
+            pointers: pointers.into(),
+        }
+    }
+}
@@ -7,8 +7,34 @@
 
 //! Indexed dictionaries.
 
+pub mod appendable_elias_fano;
+pub use appendable_elias_fano::AppendableEliasFano;
+
 pub mod elias_fano;
-pub use elias_fano::{EliasFano, EliasFanoBuilder, EliasFanoConcurrentBuilder};
+pub use elias_fano::{
+    EfDict, EfSeq, EfSeqDict, EliasFano, EliasFanoBuilder, EliasFanoConcurrentBuilder,
+    EliasFanoStreamingBuilder,
+};
+
+pub mod louds_trie;
+pub use louds_trie::{LoudsTrie, LoudsTrieBuilder};
+
+pub mod partitioned_elias_fano;
+pub use partitioned_elias_fano::{
+    PartitionedEliasFano, PartitionedEliasFanoBuilder, PartitionedEliasFanoIterator,
+};
+
+pub mod prefix_sum_elias_fano;
+pub use prefix_sum_elias_fano::{
+    PrefixSumEliasFano, PrefixSumEliasFanoBuilder, SignedPrefixSumEliasFano,
+    SignedPrefixSumEliasFanoBuilder,
+};
 
 pub mod rear_coded_list;
-pub use rear_coded_list::{RearCodedList, RearCodedListBuilder};
+pub use rear_coded_list::{
+    ByteEscapeCoder, NibbleCoder, RearCodedElement, RearCodedList, RearCodedListBuilder,
+    RearCodedListCursor, RearLengthCoder, VByteCoder,
+};
+
+pub mod wavelet_matrix;
+pub use wavelet_matrix::{WaveletMatrix, WaveletMatrixLevel};
@@ -0,0 +1,765 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! A partitioned version of the Elias–Fano representation of monotone
+//! sequences.
+//!
+//! The [Elias–Fano representation](crate::dict::elias_fano) uses a single
+//! number of lower bits for the whole sequence, which is optimal when the
+//! values are uniformly distributed, but wastes space on sequences that are
+//! locally dense and globally sparse, such as posting lists or offsets of
+//! clustered records. The partitioned representation splits the sequence in
+//! chunks of fixed size and encodes each chunk relatively to the last value of
+//! the previous chunk, choosing among Elias–Fano, a plain bitmap, or a run of
+//! consecutive values, depending on which uses less space.
+//!
+//! The representation was introduced by Giuseppe Ottaviano and Rossano
+//! Venturini in “[Partitioned Elias–Fano
+//! Indexes](https://doi.org/10.1145/2600428.2609615)”, _Proceedings of the 37th
+//! International ACM SIGIR Conference on Research & Development in Information
+//! Retrieval_, pages 273–282, ACM, 2014. Differently from the original
+//! proposal, chunks have fixed size.
+
+use super::elias_fano::{EfSeq, EfSeqDict};
+use crate::prelude::*;
+use common_traits::SelectInWord;
+use epserde::*;
+use mem_dbg::*;
+use std::borrow::Borrow;
+
+/// The base-2 logarithm of the number of values in a chunk.
+const LOG2_CHUNK_SIZE: usize = 7;
+/// The number of values in a chunk.
+const CHUNK_SIZE: usize = 1 << LOG2_CHUNK_SIZE;
+/// The number of bits of the header of a chunk.
+const KIND_BITS: usize = 2;
+
+/// The possible encodings of a chunk.
+///
+/// The encoding is stored in the first [`KIND_BITS`] bits of each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(usize)]
+enum ChunkKind {
+    /// The values are stored using the Elias–Fano representation: first the
+    /// lower bits, then the upper bits.
+    EliasFano = 0,
+    /// The values are strictly increasing and they are stored as the ones of
+    /// a bitmap.
+    Bitmap = 1,
+    /// The values are consecutive and end at the upper bound of the chunk:
+    /// nothing is stored.
+    Run = 2,
+}
+
+impl ChunkKind {
+    fn from_bits(bits: usize) -> Self {
+        match bits {
+            0 => ChunkKind::EliasFano,
+            1 => ChunkKind::Bitmap,
+            2 => ChunkKind::Run,
+            _ => unreachable!("Invalid chunk kind {}", bits),
+        }
+    }
+}
+
+/// Returns the number of lower bits used by the Elias–Fano representation of
+/// `len` values smaller than or equal to `u`.
+#[inline(always)]
+fn ef_low_bits(len: usize, u: usize) -> usize {
+    if u >= len {
+        (u / len).ilog2() as usize
+    } else {
+        0
+    }
+}
+
+/// Reads `width` < 64 bits starting at bit position `pos`.
+#[inline(always)]
+fn read_bits(data: &[usize], pos: usize, width: usize) -> usize {
+    if width == 0 {
+        return 0;
+    }
+    let word = pos / usize::BITS as usize;
+    let bit = pos % usize::BITS as usize;
+    let mut value = unsafe { *data.get_unchecked(word) } >> bit;
+    if bit + width > usize::BITS as usize {
+        value |= unsafe { *data.get_unchecked(word + 1) } << (usize::BITS as usize - bit);
+    }
+    value & ((1 << width) - 1)
+}
+
+/// Returns the position, relative to `start`, of the `rank`-th one of the bit
+/// array starting at bit position `start`.
+#[inline(always)]
+fn select_from(data: &[usize], start: usize, mut rank: usize) -> usize {
+    let mut word_idx = start / usize::BITS as usize;
+    let mut word =
+        unsafe { *data.get_unchecked(word_idx) } & (usize::MAX << (start % usize::BITS as usize));
+    loop {
+        let ones = word.count_ones() as usize;
+        if rank < ones {
+            return word_idx * usize::BITS as usize + word.select_in_word(rank) - start;
+        }
+        rank -= ones;
+        word_idx += 1;
+        word = unsafe { *data.get_unchecked(word_idx) };
+    }
+}
+
+/// A view on a chunk of a [`PartitionedEliasFano`].
+///
+/// Values are relative to the upper bound of the previous chunk.
+#[derive(Debug, Clone, Copy)]
+struct Chunk<'a> {
+    data: &'a [usize],
+    kind: ChunkKind,
+    /// The bit position of the data following the header.
+    pos: usize,
+    /// The number of values in the chunk.
+    len: usize,
+    /// The relative upper bound of the chunk, which is also its last value.
+    u: usize,
+    /// The number of lower bits, if the chunk uses Elias–Fano.
+    l: usize,
+}
+
+impl<'a> Chunk<'a> {
+    #[inline(always)]
+    fn new(data: &'a [usize], pos: usize, len: usize, u: usize) -> Self {
+        let kind = ChunkKind::from_bits(read_bits(data, pos, KIND_BITS));
+        Self {
+            data,
+            kind,
+            pos: pos + KIND_BITS,
+            len,
+            u,
+            l: ef_low_bits(len, u),
+        }
+    }
+
+    /// Returns the relative value of index `index`.
+    #[inline(always)]
+    fn get(&self, index: usize) -> usize {
+        debug_assert!(index < self.len);
+        match self.kind {
+            ChunkKind::EliasFano => {
+                let high_start = self.pos + self.len * self.l;
+                let high = select_from(self.data, high_start, index) - index;
+                (high << self.l) | read_bits(self.data, self.pos + index * self.l, self.l)
+            }
+            ChunkKind::Bitmap => select_from(self.data, self.pos, index),
+            ChunkKind::Run => self.u + 1 - self.len + index,
+        }
+    }
+
+    /// Returns the index of the first relative value greater than or equal
+    /// to `value` (greater than `value` if `STRICT` is true), or the length
+    /// of the chunk if there is no such value.
+    #[inline]
+    fn succ<const STRICT: bool>(&self, value: usize) -> usize {
+        let (mut lo, mut hi) = (0, self.len);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let x = self.get(mid);
+            if x < value || (STRICT && x == value) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+}
+
+/// An [`IndexedSeq`] that stores a monotone sequence of integers using the
+/// partitioned Elias–Fano representation.
+///
+/// The sequence is split in chunks of 128 values; the last value of each
+/// chunk is stored, followed by the upper bound of the sequence, in an
+/// [`EliasFano`] structure supporting [`Succ`], and the bit offset of each
+/// chunk in another [`EliasFano`] structure. Each chunk is then encoded
+/// relatively to the last value of the previous chunk (or zero, for the first
+/// chunk) using Elias–Fano, a bitmap, or a run of consecutive values.
+///
+/// Instances are built using a [`PartitionedEliasFanoBuilder`]. The semantics of
+/// [`IndexedSeq`], [`IndexedDict`], [`Succ`], and [`Pred`] are the same as in
+/// the case of [`EliasFano`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::PartitionedEliasFanoBuilder;
+/// # use sux::traits::{IndexedSeq, Succ, Pred};
+/// let mut pefb = PartitionedEliasFanoBuilder::new(1000, 100_000);
+/// for i in 0..500 {
+///     pefb.push(i);
+/// }
+/// for i in 0..500 {
+///     pefb.push(50_000 + i * 100);
+/// }
+/// let pef = pefb.build();
+///
+/// assert_eq!(pef.get(499), 499);
+/// assert_eq!(pef.get(500), 50_000);
+/// assert_eq!(pef.succ(1000), Some((500, 50_000)));
+/// assert_eq!(pef.pred(50_099), Some((500, 50_000)));
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct PartitionedEliasFano<E = EfSeqDict, O = EfSeq, D = Box<[usize]>> {
+    /// The number of values.
+    n: usize,
+    /// An upper bound to the values.
+    u: usize,
+    /// The last value of each chunk, followed by `u`.
+    upper_bounds: E,
+    /// The bit offset of each chunk in `data`, followed by the length in bits
+    /// of `data`.
+    offsets: O,
+    /// The encoded chunks.
+    data: D,
+}
+
+impl<E, O, D> PartitionedEliasFano<E, O, D> {
+    /// Returns the number of values in the sequence.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided
+    /// to reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of chunks.
+    #[inline(always)]
+    pub fn num_chunks(&self) -> usize {
+        self.n.div_ceil(CHUNK_SIZE)
+    }
+}
+
+impl<
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > PartitionedEliasFano<E, O, D>
+{
+    /// Returns the last value of the previous chunk, or zero.
+    #[inline(always)]
+    fn base(&self, chunk: usize) -> usize {
+        if chunk == 0 {
+            0
+        } else {
+            unsafe { self.upper_bounds.get_unchecked(chunk - 1) }
+        }
+    }
+
+    /// Returns a view on the given chunk and its base.
+    #[inline(always)]
+    fn chunk(&self, chunk: usize) -> (Chunk<'_>, usize) {
+        let base = self.base(chunk);
+        let upper_bound = unsafe { self.upper_bounds.get_unchecked(chunk) };
+        let len = CHUNK_SIZE.min(self.n - chunk * CHUNK_SIZE);
+        let pos = unsafe { self.offsets.get_unchecked(chunk) };
+        (
+            Chunk::new(self.data.as_ref(), pos, len, upper_bound - base),
+            base,
+        )
+    }
+
+    /// Returns an iterator over the values of the sequence.
+    #[inline(always)]
+    pub fn iter(&self) -> PartitionedEliasFanoIterator<'_, E, O, D> {
+        PartitionedEliasFanoIterator::new(self)
+    }
+
+    /// Returns an iterator over the values of the sequence starting at the
+    /// given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is greater than the length of the sequence.
+    #[inline(always)]
+    pub fn iter_from(&self, from: usize) -> PartitionedEliasFanoIterator<'_, E, O, D> {
+        PartitionedEliasFanoIterator::new_from(self, from)
+    }
+}
+
+impl<E, O, D: AsRef<[usize]>> Types for PartitionedEliasFano<E, O, D> {
+    type Output = usize;
+    type Input = usize;
+}
+
+impl<
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > IndexedSeq for PartitionedEliasFano<E, O, D>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> usize {
+        let (chunk, base) = self.chunk(index >> LOG2_CHUNK_SIZE);
+        base + chunk.get(index & (CHUNK_SIZE - 1))
+    }
+}
+
+impl<
+        E: Succ + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > SuccUnchecked for PartitionedEliasFano<E, O, D>
+{
+    unsafe fn succ_unchecked<const STRICT: bool>(
+        &self,
+        value: impl Borrow<Self::Input>,
+    ) -> (usize, Self::Output) {
+        let value = *value.borrow();
+        // The first chunk whose last value is a successor
+        let (chunk_index, _) = self.upper_bounds.succ_unchecked::<STRICT>(value);
+        let (chunk, base) = self.chunk(chunk_index);
+        // The last value of the previous chunk is not a successor
+        debug_assert!(value >= base);
+        let index = chunk.succ::<STRICT>(value - base);
+        debug_assert!(index < chunk.len);
+        (
+            (chunk_index << LOG2_CHUNK_SIZE) + index,
+            base + chunk.get(index),
+        )
+    }
+}
+
+impl<
+        E: Succ + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > Succ for PartitionedEliasFano<E, O, D>
+{
+}
+
+impl<
+        E: Succ + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > PredUnchecked for PartitionedEliasFano<E, O, D>
+{
+    unsafe fn pred_unchecked<const STRICT: bool>(
+        &self,
+        value: impl Borrow<Self::Input>,
+    ) -> (usize, Self::Output) {
+        let value = *value.borrow();
+        // The first chunk whose last value is not a predecessor; because of
+        // the final upper bound, it might be past the last chunk
+        let chunk_index = if STRICT {
+            self.upper_bounds.succ(value)
+        } else {
+            self.upper_bounds.succ_strict(value)
+        }
+        .map_or(self.num_chunks(), |(index, _)| index);
+
+        if chunk_index < self.num_chunks() {
+            let (chunk, base) = self.chunk(chunk_index);
+            // The last value of the previous chunk is a predecessor
+            debug_assert!(value >= base);
+            // Index of the first value that is not a predecessor
+            let index = if STRICT {
+                chunk.succ::<false>(value - base)
+            } else {
+                chunk.succ::<true>(value - base)
+            };
+            if index > 0 {
+                return (
+                    (chunk_index << LOG2_CHUNK_SIZE) + index - 1,
+                    base + chunk.get(index - 1),
+                );
+            }
+        }
+
+        // The predecessor is the last value of the previous chunk, which
+        // might be the last, partial chunk
+        debug_assert!(chunk_index > 0);
+        (
+            (chunk_index << LOG2_CHUNK_SIZE).min(self.n) - 1,
+            self.upper_bounds.get_unchecked(chunk_index - 1),
+        )
+    }
+}
+
+impl<
+        E: Succ + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > Pred for PartitionedEliasFano<E, O, D>
+{
+}
+
+impl<
+        E: Succ + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > IndexedDict for PartitionedEliasFano<E, O, D>
+{
+    fn index_of(&self, value: impl Borrow<Self::Input>) -> Option<usize> {
+        let value = *value.borrow();
+        match self.succ(value) {
+            Some((index, succ)) if succ == value => Some(index),
+            _ => None,
+        }
+    }
+}
+
+impl<
+        'a,
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > IntoIterator for &'a PartitionedEliasFano<E, O, D>
+{
+    type Item = usize;
+    type IntoIter = PartitionedEliasFanoIterator<'a, E, O, D>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        PartitionedEliasFanoIterator::new(self)
+    }
+}
+
+/// An iterator for [`PartitionedEliasFano`].
+///
+/// The iterator decodes a chunk at a time in an internal buffer.
+#[derive(Debug, MemDbg, MemSize)]
+pub struct PartitionedEliasFanoIterator<'a, E, O, D> {
+    pef: &'a PartitionedEliasFano<E, O, D>,
+    /// The index of the next value it will be returned when `next` is called.
+    index: usize,
+    /// The decoded values of the current chunk.
+    buffer: Vec<usize>,
+}
+
+impl<
+        'a,
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > PartitionedEliasFanoIterator<'a, E, O, D>
+{
+    pub fn new(pef: &'a PartitionedEliasFano<E, O, D>) -> Self {
+        Self::new_from(pef, 0)
+    }
+
+    pub fn new_from(pef: &'a PartitionedEliasFano<E, O, D>, start_index: usize) -> Self {
+        if start_index > pef.len() {
+            panic!("Index out of bounds: {} > {}", start_index, pef.len());
+        }
+        let mut iter = Self {
+            pef,
+            index: start_index,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        };
+        if start_index & (CHUNK_SIZE - 1) != 0 {
+            iter.fill_buffer();
+        }
+        iter
+    }
+
+    /// Decodes the chunk containing the current index.
+    fn fill_buffer(&mut self) {
+        let (chunk, base) = self.pef.chunk(self.index >> LOG2_CHUNK_SIZE);
+        self.buffer.clear();
+        match chunk.kind {
+            ChunkKind::EliasFano => {
+                let data = chunk.data;
+                let high_start = chunk.pos + chunk.len * chunk.l;
+                let mut word_idx = high_start / usize::BITS as usize;
+                let mut window =
+                    data[word_idx] & (usize::MAX << (high_start % usize::BITS as usize));
+                for i in 0..chunk.len {
+                    while window == 0 {
+                        word_idx += 1;
+                        window = data[word_idx];
+                    }
+                    let high = word_idx * usize::BITS as usize + window.trailing_zeros() as usize
+                        - high_start
+                        - i;
+                    window &= window - 1;
+                    self.buffer.push(
+                        base + ((high << chunk.l)
+                            | read_bits(data, chunk.pos + i * chunk.l, chunk.l)),
+                    );
+                }
+            }
+            ChunkKind::Bitmap => {
+                let data = chunk.data;
+                let mut word_idx = chunk.pos / usize::BITS as usize;
+                let mut window =
+                    data[word_idx] & (usize::MAX << (chunk.pos % usize::BITS as usize));
+                for _ in 0..chunk.len {
+                    while window == 0 {
+                        word_idx += 1;
+                        window = data[word_idx];
+                    }
+                    let bit = word_idx * usize::BITS as usize + window.trailing_zeros() as usize;
+                    window &= window - 1;
+                    self.buffer.push(base + bit - chunk.pos);
+                }
+            }
+            ChunkKind::Run => {
+                let first = base + chunk.u + 1 - chunk.len;
+                self.buffer.extend(first..first + chunk.len);
+            }
+        }
+    }
+}
+
+impl<
+        'a,
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > Iterator for PartitionedEliasFanoIterator<'a, E, O, D>
+{
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.pef.len() {
+            return None;
+        }
+        if self.index & (CHUNK_SIZE - 1) == 0 {
+            self.fill_buffer();
+        }
+        let res = self.buffer[self.index & (CHUNK_SIZE - 1)];
+        self.index += 1;
+        Some(res)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<
+        'a,
+        E: IndexedSeq + Types<Input = usize, Output = usize>,
+        O: IndexedSeq + Types<Input = usize, Output = usize>,
+        D: AsRef<[usize]>,
+    > ExactSizeIterator for PartitionedEliasFanoIterator<'a, E, O, D>
+{
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.pef.len() - self.index
+    }
+}
+
+/// A sequential builder for [`PartitionedEliasFano`].
+///
+/// After creating an instance, you can use
+/// [`PartitionedEliasFanoBuilder::push`] to add new values, and then call
+/// [`PartitionedEliasFanoBuilder::build`] to create the
+/// [`PartitionedEliasFano`] instance.
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::PartitionedEliasFanoBuilder;
+/// let mut pefb = PartitionedEliasFanoBuilder::new(4, 10);
+///
+/// pefb.push(0);
+/// pefb.push(2);
+/// pefb.push(8);
+/// pefb.push(10);
+///
+/// let pef = pefb.build();
+/// let mut iter = pef.iter();
+/// assert_eq!(iter.next(), Some(0));
+/// assert_eq!(iter.next(), Some(2));
+/// assert_eq!(iter.next(), Some(8));
+/// assert_eq!(iter.next(), Some(10));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct PartitionedEliasFanoBuilder {
+    n: usize,
+    u: usize,
+    /// The values of the current chunk.
+    chunk: Vec<usize>,
+    /// The last value of each chunk.
+    upper_bounds: Vec<usize>,
+    /// The bit offset of each chunk.
+    offsets: Vec<usize>,
+    /// The encoded chunks.
+    data: Vec<usize>,
+    /// The length in bits of `data`.
+    bit_len: usize,
+    last_value: usize,
+    count: usize,
+}
+
+impl PartitionedEliasFanoBuilder {
+    /// Creates a builder for a [`PartitionedEliasFano`] containing `n` numbers
+    /// smaller than or equal to `u`.
+    pub fn new(n: usize, u: usize) -> Self {
+        Self {
+            n,
+            u,
+            chunk: Vec::with_capacity(CHUNK_SIZE),
+            upper_bounds: Vec::with_capacity(n.div_ceil(CHUNK_SIZE)),
+            offsets: Vec::with_capacity(n.div_ceil(CHUNK_SIZE) + 1),
+            data: Vec::new(),
+            bit_len: 0,
+            last_value: 0,
+            count: 0,
+        }
+    }
+
+    /// Adds a new value to the builder.
+    ///
+    /// # Panic
+    /// May panic if the value is smaller than the last provided
+    /// value, or if too many values are provided.
+    pub fn push(&mut self, value: usize) {
+        if self.count == self.n {
+            panic!("Too many values");
+        }
+        if value > self.u {
+            panic!("Value too large: {} > {}", value, self.u);
+        }
+        if value < self.last_value {
+            panic!(
+                "The values provided are not monotone: {} < {}",
+                value, self.last_value
+            );
+        }
+        unsafe {
+            self.push_unchecked(value);
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Values passed to this function must be smaller than or equal `u` and must be monotone.
+    /// Moreover, the function should not be called more than `n` times.
+    pub unsafe fn push_unchecked(&mut self, value: usize) {
+        self.chunk.push(value);
+        self.count += 1;
+        self.last_value = value;
+        if self.chunk.len() == CHUNK_SIZE {
+            self.flush_chunk();
+        }
+    }
+
+    /// Appends the lowest `width` < 64 bits of `value` to the data.
+    fn write_bits(&mut self, value: usize, width: usize) {
+        if width == 0 {
+            return;
+        }
+        let bit = self.bit_len % usize::BITS as usize;
+        if bit == 0 {
+            self.data.push(0);
+        }
+        *self.data.last_mut().unwrap() |= value << bit;
+        if bit + width > usize::BITS as usize {
+            self.data.push(value >> (usize::BITS as usize - bit));
+        }
+        self.bit_len += width;
+    }
+
+    /// Appends a zeroed bit array of length `len` to the data, returning its
+    /// starting position.
+    fn write_zeros(&mut self, len: usize) -> usize {
+        let pos = self.bit_len;
+        self.bit_len += len;
+        self.data
+            .resize(self.bit_len.div_ceil(usize::BITS as usize), 0);
+        pos
+    }
+
+    /// Sets the bit of given position in the data.
+    fn set_bit(&mut self, pos: usize) {
+        self.data[pos / usize::BITS as usize] |= 1 << (pos % usize::BITS as usize);
+    }
+
+    /// Encodes the current chunk.
+    fn flush_chunk(&mut self) {
+        if self.chunk.is_empty() {
+            return;
+        }
+        let base = self.upper_bounds.last().copied().unwrap_or(0);
+        let upper_bound = *self.chunk.last().unwrap();
+        let len = self.chunk.len();
+        let u = upper_bound - base;
+
+        let l = ef_low_bits(len, u);
+        let ef_cost = len * l + len + (u >> l) + 1;
+        // Bitmaps and runs can represent only distinct values
+        let strict = self.chunk.windows(2).all(|w| w[0] < w[1]);
+        let is_run = strict && self.chunk[0] - base == u + 1 - len;
+        let bitmap_cost = if strict { u + 1 } else { usize::MAX };
+
+        self.offsets.push(self.bit_len);
+        self.upper_bounds.push(upper_bound);
+        let chunk = std::mem::take(&mut self.chunk);
+        if is_run {
+            self.write_bits(ChunkKind::Run as usize, KIND_BITS);
+        } else if bitmap_cost <= ef_cost {
+            self.write_bits(ChunkKind::Bitmap as usize, KIND_BITS);
+            let start = self.write_zeros(u + 1);
+            for &value in &chunk {
+                self.set_bit(start + value - base);
+            }
+        } else {
+            self.write_bits(ChunkKind::EliasFano as usize, KIND_BITS);
+            for &value in &chunk {
+                self.write_bits((value - base) & ((1 << l) - 1), l);
+            }
+            let start = self.write_zeros(len + (u >> l) + 1);
+            for (i, &value) in chunk.iter().enumerate() {
+                self.set_bit(start + ((value - base) >> l) + i);
+            }
+        }
+        self.chunk = chunk;
+        self.chunk.clear();
+    }
+
+    /// Builds a partitioned Elias–Fano structure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` values have been provided.
+    pub fn build(mut self) -> PartitionedEliasFano {
+        if self.count != self.n {
+            panic!("Too few values: {} < {}", self.count, self.n);
+        }
+        self.flush_chunk();
+
+        let mut upper_bounds = EliasFanoBuilder::new(self.upper_bounds.len() + 1, self.u);
+        for &upper_bound in &self.upper_bounds {
+            upper_bounds.push(upper_bound);
+        }
+        upper_bounds.push(self.u);
+
+        let mut offsets = EliasFanoBuilder::new(self.offsets.len() + 1, self.bit_len);
+        for &offset in &self.offsets {
+            offsets.push(offset);
+        }
+        offsets.push(self.bit_len);
+
+        // Padding makes it possible to always read the word after the last one
+        self.data.push(0);
+
+        PartitionedEliasFano {
+            n: self.n,
+            u: self.u,
+            upper_bounds: upper_bounds.build_with_seq_and_dict(),
+            offsets: offsets.build_with_seq(),
+            data: self.data.into(),
+        }
+    }
+}
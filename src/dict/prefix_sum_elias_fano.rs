@@ -0,0 +1,372 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Sequences of arbitrary integers stored as the prefix sums of an
+//! [Elias–Fano representation](crate::dict::elias_fano).
+//!
+//! An [`EliasFano`] structure can store only monotone sequences, but
+//! storing the prefix sums of a sequence of nonnegative integers, such as the
+//! lengths of a sequence of records, makes it possible to recover each value
+//! as the difference of two consecutive prefix sums. Moreover, predecessor
+//! queries on the prefix sums make it possible to find the record containing
+//! a given offset.
+//!
+//! [`PrefixSumEliasFano`] stores nonnegative values, whereas
+//! [`SignedPrefixSumEliasFano`] stores signed values by mapping them to
+//! nonnegative values using the [zig-zag
+//! encoding](https://en.wikipedia.org/wiki/Variable-length_quantity#Zigzag_encoding).
+
+use super::elias_fano::{EfSeq, EfSeqDict};
+use crate::prelude::*;
+use epserde::*;
+use mem_dbg::*;
+
+/// Maps a signed integer to a nonnegative integer, interleaving positive and
+/// negative values.
+#[inline(always)]
+fn zigzag_encode(value: isize) -> usize {
+    ((value << 1) ^ (value >> (isize::BITS - 1))) as usize
+}
+
+/// Inverts [`zigzag_encode`].
+#[inline(always)]
+fn zigzag_decode(value: usize) -> isize {
+    (value >> 1) as isize ^ -((value & 1) as isize)
+}
+
+/// An [`IndexedSeq`] of nonnegative integers (_deltas_) stored as their prefix
+/// sums in an [`EliasFano`] structure.
+///
+/// The structure stores the `n` + 1 prefix sums of the deltas, starting with
+/// zero. [`get_delta`](PrefixSumEliasFano::get_delta) (or, equivalently,
+/// [`IndexedSeq::get`]) returns a delta, and
+/// [`prefix_sum`](PrefixSumEliasFano::prefix_sum) returns the sum of the
+/// deltas preceding a given index. If the underlying structure implements
+/// [`Pred`], [`find`](PrefixSumEliasFano::find) returns the index of the delta
+/// “containing” a given value, that is, if the deltas are the lengths of a
+/// sequence of records, the index of the record containing a given offset.
+///
+/// Instances are built using a [`PrefixSumEliasFanoBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::PrefixSumEliasFanoBuilder;
+/// # use sux::traits::IndexedSeq;
+/// let mut psefb = PrefixSumEliasFanoBuilder::new(4, 100);
+/// for len in [10, 0, 5, 20] {
+///     psefb.push(len);
+/// }
+/// let psef = psefb.build();
+///
+/// assert_eq!(psef.get_delta(2), 5);
+/// assert_eq!(psef.prefix_sum(3), 15);
+/// assert_eq!(psef.prefix_sum(4), 35);
+/// // Offset 12 is in the third record (the second one is empty)
+/// assert_eq!(psef.find(12), Some(2));
+/// assert_eq!(psef.find(35), None);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct PrefixSumEliasFano<E = EfSeqDict> {
+    /// The prefix sums, starting with zero.
+    prefix_sums: E,
+}
+
+impl<E> PrefixSumEliasFano<E> {
+    /// Returns the underlying structure containing the prefix sums, starting
+    /// with zero.
+    pub fn into_inner(self) -> E {
+        self.prefix_sums
+    }
+}
+
+impl<E: IndexedSeq + Types<Input = usize, Output = usize>> PrefixSumEliasFano<E> {
+    /// Returns the number of deltas.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided
+    /// to reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.prefix_sums.len() - 1
+    }
+
+    /// Returns the delta of given index.
+    ///
+    /// This method is equivalent to [`IndexedSeq::get`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than the number of deltas.
+    #[inline]
+    pub fn get_delta(&self, index: usize) -> usize {
+        if index >= self.len() {
+            panic!("Index out of bounds: {} >= {}", index, self.len());
+        }
+        unsafe { self.get_unchecked(index) }
+    }
+
+    /// Returns the sum of the deltas of index smaller than `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of deltas.
+    #[inline]
+    pub fn prefix_sum(&self, index: usize) -> usize {
+        self.prefix_sums.get(index)
+    }
+
+    /// Returns the sum of all deltas.
+    #[inline(always)]
+    pub fn total(&self) -> usize {
+        unsafe { self.prefix_sums.get_unchecked(self.len()) }
+    }
+}
+
+impl<E: IndexedSeq + Pred + Types<Input = usize, Output = usize>> PrefixSumEliasFano<E> {
+    /// Returns the index `i` such that the [prefix sum](Self::prefix_sum) of
+    /// index `i` is smaller than or equal to `value`, and the prefix sum of
+    /// index `i` + 1 is greater than `value`, or `None` if `value` is greater
+    /// than or equal to the [sum of all deltas](Self::total).
+    ///
+    /// In other words, if the deltas are the lengths of a sequence of
+    /// records, this method returns the index of the record containing the
+    /// offset `value`. Indices of zero deltas are never returned.
+    #[inline]
+    pub fn find(&self, value: usize) -> Option<usize> {
+        // The index of the last prefix sum smaller than or equal to value;
+        // there is always one, as the first prefix sum is zero
+        let (index, _) = unsafe { self.prefix_sums.pred_unchecked::<false>(value) };
+        (index < self.len()).then_some(index)
+    }
+}
+
+impl<E> Types for PrefixSumEliasFano<E> {
+    type Input = usize;
+    type Output = usize;
+}
+
+impl<E: IndexedSeq + Types<Input = usize, Output = usize>> IndexedSeq for PrefixSumEliasFano<E> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.prefix_sums.len() - 1
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> usize {
+        self.prefix_sums.get_unchecked(index + 1) - self.prefix_sums.get_unchecked(index)
+    }
+}
+
+/// A builder for [`PrefixSumEliasFano`].
+///
+/// After creating an instance, you can use [`PrefixSumEliasFanoBuilder::push`]
+/// to add new deltas; when you are done, you can call one of the build
+/// methods to obtain a [`PrefixSumEliasFano`] structure.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct PrefixSumEliasFanoBuilder {
+    n: usize,
+    u: usize,
+    /// The builder of the prefix sums.
+    efb: EliasFanoBuilder,
+    sum: usize,
+    count: usize,
+}
+
+impl PrefixSumEliasFanoBuilder {
+    /// Creates a builder for a [`PrefixSumEliasFano`] containing `n` deltas
+    /// whose sum is smaller than or equal to `u`.
+    pub fn new(n: usize, u: usize) -> Self {
+        let mut efb = EliasFanoBuilder::new(n + 1, u);
+        efb.push(0);
+        Self {
+            n,
+            u,
+            efb,
+            sum: 0,
+            count: 0,
+        }
+    }
+
+    /// Adds a new delta to the builder.
+    ///
+    /// # Panic
+    /// May panic if the sum of the deltas becomes larger than the upper
+    /// bound, or if too many deltas are provided.
+    pub fn push(&mut self, delta: usize) {
+        if self.count == self.n {
+            panic!("Too many values");
+        }
+        let sum = self
+            .sum
+            .checked_add(delta)
+            .filter(|&sum| sum <= self.u)
+            .unwrap_or_else(|| panic!("Sum too large: {} + {} > {}", self.sum, delta, self.u));
+        unsafe {
+            self.efb.push_unchecked(sum);
+        }
+        self.sum = sum;
+        self.count += 1;
+    }
+
+    fn check_len(&self) {
+        if self.count != self.n {
+            panic!("Too few values: {} < {}", self.count, self.n);
+        }
+    }
+
+    /// Builds a [`PrefixSumEliasFano`] structure supporting access,
+    /// prefix sums, and [`find`](PrefixSumEliasFano::find).
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` deltas have been provided.
+    pub fn build(self) -> PrefixSumEliasFano {
+        self.check_len();
+        PrefixSumEliasFano {
+            prefix_sums: self.efb.build_with_seq_and_dict(),
+        }
+    }
+
+    /// Builds a [`PrefixSumEliasFano`] structure supporting only access and
+    /// prefix sums.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` deltas have been provided.
+    pub fn build_with_seq(self) -> PrefixSumEliasFano<EfSeq> {
+        self.check_len();
+        PrefixSumEliasFano {
+            prefix_sums: self.efb.build_with_seq(),
+        }
+    }
+}
+
+/// An [`IndexedSeq`] of signed integers stored as a [`PrefixSumEliasFano`]
+/// of their zig-zag encodings.
+///
+/// The zig-zag encoding maps 0, −1, 1, −2, 2, … to 0, 1, 2, 3, 4, …, so the
+/// space used is similar to that of a [`PrefixSumEliasFano`] containing the
+/// absolute values.
+///
+/// Instances are built using a [`SignedPrefixSumEliasFanoBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::dict::SignedPrefixSumEliasFanoBuilder;
+/// # use sux::traits::IndexedSeq;
+/// let values = [3, -1, 0, -100, 42];
+/// let mut spsefb = SignedPrefixSumEliasFanoBuilder::new(values.len(), 300);
+/// for value in values {
+///     spsefb.push(value);
+/// }
+/// let spsef = spsefb.build();
+///
+/// for (i, value) in values.into_iter().enumerate() {
+///     assert_eq!(spsef.get(i), value);
+/// }
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct SignedPrefixSumEliasFano<E = EfSeq> {
+    /// The zig-zag encoded values.
+    psef: PrefixSumEliasFano<E>,
+}
+
+impl<E> SignedPrefixSumEliasFano<E> {
+    /// Returns the underlying [`PrefixSumEliasFano`], containing the
+    /// zig-zag encoded values.
+    pub fn into_inner(self) -> PrefixSumEliasFano<E> {
+        self.psef
+    }
+}
+
+impl<E: IndexedSeq + Types<Input = usize, Output = usize>> SignedPrefixSumEliasFano<E> {
+    /// Returns the number of values.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided
+    /// to reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.psef.len()
+    }
+}
+
+impl<E> Types for SignedPrefixSumEliasFano<E> {
+    type Input = isize;
+    type Output = isize;
+}
+
+impl<E: IndexedSeq + Types<Input = usize, Output = usize>> IndexedSeq
+    for SignedPrefixSumEliasFano<E>
+{
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.psef.len()
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> isize {
+        zigzag_decode(self.psef.get_unchecked(index))
+    }
+}
+
+/// A builder for [`SignedPrefixSumEliasFano`].
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct SignedPrefixSumEliasFanoBuilder {
+    psefb: PrefixSumEliasFanoBuilder,
+}
+
+impl SignedPrefixSumEliasFanoBuilder {
+    /// Creates a builder for a [`SignedPrefixSumEliasFano`] containing `n`
+    /// values whose zig-zag encodings have sum smaller than or equal to `u`.
+    ///
+    /// Since the zig-zag encoding of a value is at most twice its absolute
+    /// value, twice the sum of the absolute values is always a valid upper
+    /// bound.
+    pub fn new(n: usize, u: usize) -> Self {
+        Self {
+            psefb: PrefixSumEliasFanoBuilder::new(n, u),
+        }
+    }
+
+    /// Adds a new value to the builder.
+    ///
+    /// # Panic
+    /// May panic if the sum of the zig-zag encodings of the values becomes
+    /// larger than the upper bound, or if too many values are provided.
+    pub fn push(&mut self, value: isize) {
+        self.psefb.push(zigzag_encode(value));
+    }
+
+    /// Builds a [`SignedPrefixSumEliasFano`] structure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` values have been provided.
+    pub fn build(self) -> SignedPrefixSumEliasFano {
+        SignedPrefixSumEliasFano {
+            psef: self.psefb.build_with_seq(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zigzag() {
+        for (value, encoded) in [(0, 0), (-1, 1), (1, 2), (-2, 3), (2, 4)] {
+            assert_eq!(zigzag_encode(value), encoded);
+            assert_eq!(zigzag_decode(encoded), value);
+        }
+        for value in [isize::MIN, isize::MIN + 1, isize::MAX - 1, isize::MAX] {
+            assert_eq!(zigzag_decode(zigzag_encode(value)), value);
+        }
+        assert_eq!(zigzag_encode(isize::MIN), usize::MAX);
+    }
+}
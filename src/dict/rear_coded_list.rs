@@ -8,12 +8,233 @@
 //! Compressed string storage by rear-coded prefix omission.
 
 use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::Range;
 
 use crate::traits::{IndexedDict, IndexedSeq, Types};
 use epserde::*;
 use lender::for_;
 use lender::{ExactSizeLender, IntoLender, Lender, Lending};
 use mem_dbg::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The types of strings that can be stored in a [`RearCodedList`].
+///
+/// This trait is implemented for [`str`], in which case the list contains
+/// UTF-8 strings, and for `[u8]`, in which case the list contains arbitrary
+/// byte strings (e.g., hashes or serialized tuples), possibly containing NUL
+/// bytes.
+pub trait RearCodedElement: AsRef<Self> + PartialEq + PartialEq<Self::Owned> {
+    /// The owned version of the string, returned by
+    /// [`IndexedSeq::get`](crate::traits::IndexedSeq::get).
+    type Owned: PartialEq + PartialEq<Self>;
+
+    /// Returns the bytes representing the string.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns a reference to the string represented by the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be a valid representation of the string (e.g., valid
+    /// UTF-8 for [`str`]).
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self;
+
+    /// Returns the owned string represented by the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be a valid representation of the string (e.g., valid
+    /// UTF-8 for [`str`]).
+    unsafe fn from_vec_unchecked(bytes: Vec<u8>) -> Self::Owned;
+}
+
+impl RearCodedElement for str {
+    type Owned = String;
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    #[inline(always)]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        std::str::from_utf8_unchecked(bytes)
+    }
+
+    #[inline(always)]
+    unsafe fn from_vec_unchecked(bytes: Vec<u8>) -> Self::Owned {
+        String::from_utf8_unchecked(bytes)
+    }
+}
+
+impl RearCodedElement for [u8] {
+    type Owned = Vec<u8>;
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline(always)]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        bytes
+    }
+
+    #[inline(always)]
+    unsafe fn from_vec_unchecked(bytes: Vec<u8>) -> Self::Owned {
+        bytes
+    }
+}
+
+/// Coders for the lengths preceding the suffixes in a [`RearCodedList`].
+///
+/// Each string that is not the first of its block is stored as its rear
+/// length (the number of bytes to remove from the end of the previous
+/// string), followed by the length of its suffix and by the suffix itself.
+/// A coder decides how the two lengths are represented: which coder is best
+/// depends on the distribution of the lengths, and
+/// [`RearCodedListBuilder::print_stats`] displays the space used by each of
+/// the available coders.
+///
+/// The coder is a type parameter of [`RearCodedList`], and thus it is
+/// recorded in its [ε-serde](epserde) type.
+pub trait RearLengthCoder {
+    /// Writes a rear length and a suffix length to `data`.
+    fn encode(rear_length: usize, suffix_length: usize, data: &mut Vec<u8>);
+
+    /// Reads a rear length and a suffix length from `data`, returning them
+    /// together with the remaining data.
+    fn decode(data: &[u8]) -> (usize, usize, &[u8]);
+
+    /// Returns the number of bytes used to encode a rear length and a
+    /// suffix length.
+    fn encoded_len(rear_length: usize, suffix_length: usize) -> usize;
+}
+
+/// A [`RearLengthCoder`] writing both lengths using a variable-length byte
+/// code.
+///
+/// This is the default coder: it is a good choice when lengths are large.
+#[derive(Epserde, Debug, Clone, Copy, Default, MemDbg, MemSize)]
+pub struct VByteCoder;
+
+impl RearLengthCoder for VByteCoder {
+    #[inline(always)]
+    fn encode(rear_length: usize, suffix_length: usize, data: &mut Vec<u8>) {
+        encode_int(rear_length, data);
+        encode_int(suffix_length, data);
+    }
+
+    #[inline(always)]
+    fn decode(data: &[u8]) -> (usize, usize, &[u8]) {
+        let (rear_length, data) = decode_int(data);
+        let (suffix_length, data) = decode_int(data);
+        (rear_length, suffix_length, data)
+    }
+
+    #[inline(always)]
+    fn encoded_len(rear_length: usize, suffix_length: usize) -> usize {
+        encode_int_len(rear_length) + encode_int_len(suffix_length)
+    }
+}
+
+/// A [`RearLengthCoder`] packing both lengths in the two nibbles of a
+/// single byte.
+///
+/// A nibble equal to 15 is an escape: the length minus 15 follows the byte
+/// using a variable-length byte code. This coder is a good choice for short
+/// strings.
+#[derive(Epserde, Debug, Clone, Copy, Default, MemDbg, MemSize)]
+pub struct NibbleCoder;
+
+impl RearLengthCoder for NibbleCoder {
+    #[inline(always)]
+    fn encode(rear_length: usize, suffix_length: usize, data: &mut Vec<u8>) {
+        data.push((rear_length.min(15) << 4 | suffix_length.min(15)) as u8);
+        if rear_length >= 15 {
+            encode_int(rear_length - 15, data);
+        }
+        if suffix_length >= 15 {
+            encode_int(suffix_length - 15, data);
+        }
+    }
+
+    #[inline(always)]
+    fn decode(data: &[u8]) -> (usize, usize, &[u8]) {
+        let (mut rear_length, mut suffix_length) =
+            ((data[0] >> 4) as usize, (data[0] & 15) as usize);
+        let mut data = &data[1..];
+        if rear_length == 15 {
+            let (len, tmp) = decode_int(data);
+            rear_length += len;
+            data = tmp;
+        }
+        if suffix_length == 15 {
+            let (len, tmp) = decode_int(data);
+            suffix_length += len;
+            data = tmp;
+        }
+        (rear_length, suffix_length, data)
+    }
+
+    #[inline(always)]
+    fn encoded_len(rear_length: usize, suffix_length: usize) -> usize {
+        let mut len = 1;
+        if rear_length >= 15 {
+            len += encode_int_len(rear_length - 15);
+        }
+        if suffix_length >= 15 {
+            len += encode_int_len(suffix_length - 15);
+        }
+        len
+    }
+}
+
+/// A [`RearLengthCoder`] writing the rear length in a single byte, and the
+/// suffix length using a variable-length byte code.
+///
+/// A byte equal to 255 is an escape: the rear length minus 255 follows
+/// using a variable-length byte code. Differently from [`VByteCoder`], rear
+/// lengths up to 254 take a single byte.
+#[derive(Epserde, Debug, Clone, Copy, Default, MemDbg, MemSize)]
+pub struct ByteEscapeCoder;
+
+impl RearLengthCoder for ByteEscapeCoder {
+    #[inline(always)]
+    fn encode(rear_length: usize, suffix_length: usize, data: &mut Vec<u8>) {
+        if rear_length < 255 {
+            data.push(rear_length as u8);
+        } else {
+            data.push(255);
+            encode_int(rear_length - 255, data);
+        }
+        encode_int(suffix_length, data);
+    }
+
+    #[inline(always)]
+    fn decode(data: &[u8]) -> (usize, usize, &[u8]) {
+        let (rear_length, data) = if data[0] < 255 {
+            (data[0] as usize, &data[1..])
+        } else {
+            let (len, data) = decode_int(&data[1..]);
+            (len + 255, data)
+        };
+        let (suffix_length, data) = decode_int(data);
+        (rear_length, suffix_length, data)
+    }
+
+    #[inline(always)]
+    fn encoded_len(rear_length: usize, suffix_length: usize) -> usize {
+        let rear_bytes = if rear_length < 255 {
+            1
+        } else {
+            1 + encode_int_len(rear_length - 255)
+        };
+        rear_bytes + encode_int_len(suffix_length)
+    }
+}
 
 #[derive(Debug, Clone, MemDbg, MemSize, Default)]
 /// Statistics of the encoded data.
@@ -33,15 +254,95 @@ struct Stats {
     /// The total sum of the string lengths in bytes.
     pub sum_str_len: usize,
 
-    /// The number of bytes used to store the rear lengths in data.
+    /// The number of bytes used to store the lengths in data.
     pub code_bytes: usize,
     /// The number of bytes used to store the suffixes in data.
     pub suffixes_bytes: usize,
 
+    /// The number of bytes [`VByteCoder`] would use to store the rear and
+    /// suffix lengths.
+    pub vbyte_code_bytes: usize,
+    /// The number of bytes [`NibbleCoder`] would use to store the rear and
+    /// suffix lengths.
+    pub nibble_code_bytes: usize,
+    /// The number of bytes [`ByteEscapeCoder`] would use to store the rear
+    /// and suffix lengths.
+    pub byte_escape_code_bytes: usize,
+
     /// The bytes wasted writing without compression the first string in block.
     pub redundancy: isize,
 }
 
+impl Stats {
+    /// Prints the statistics in a human-readable format.
+    fn print(&self, len: usize, data_bytes: usize, num_pointers: usize, struct_bytes: usize) {
+        println!("{:>20}: {:>10}", "max_block_bytes", self.max_block_bytes);
+        println!(
+            "{:>20}: {:>10.3}",
+            "avg_block_bytes",
+            self.sum_block_bytes as f64 / len as f64
+        );
+
+        println!("{:>20}: {:>10}", "max_lcp", self.max_lcp);
+        println!(
+            "{:>20}: {:>10.3}",
+            "avg_lcp",
+            self.sum_lcp as f64 / len as f64
+        );
+
+        println!("{:>20}: {:>10}", "max_str_len", self.max_str_len);
+        println!(
+            "{:>20}: {:>10.3}",
+            "avg_str_len",
+            self.sum_str_len as f64 / len as f64
+        );
+
+        let ptr_size: usize = num_pointers * core::mem::size_of::<usize>();
+
+        fn human(key: &str, x: usize) {
+            const UOM: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+            let mut y = x as f64;
+            let mut uom_idx = 0;
+            while y > 1000.0 {
+                uom_idx += 1;
+                y /= 1000.0;
+            }
+            println!("{:>20}:{:>10.3}{}{:>20} ", key, y, UOM[uom_idx], x);
+        }
+
+        let total_size = ptr_size + data_bytes + struct_bytes;
+        human("data_bytes", data_bytes);
+        human("codes_bytes", self.code_bytes);
+        human("suffixes_bytes", self.suffixes_bytes);
+        human("vbyte_codes_bytes", self.vbyte_code_bytes);
+        human("nibble_codes_bytes", self.nibble_code_bytes);
+        human("escape_codes_bytes", self.byte_escape_code_bytes);
+        human("ptrs_bytes", ptr_size);
+        human("uncompressed_size", self.sum_str_len);
+        human("total_size", total_size);
+
+        human(
+            "optimal_size",
+            (data_bytes as isize - self.redundancy) as usize,
+        );
+        human("redundancy", self.redundancy as usize);
+        let overhead = self.redundancy + ptr_size as isize;
+        println!(
+            "overhead_ratio: {:>10}",
+            overhead as f64 / (overhead + data_bytes as isize) as f64
+        );
+        println!(
+            "no_overhead_compression_ratio: {:.3}",
+            (data_bytes as isize - self.redundancy) as f64 / self.sum_str_len as f64
+        );
+
+        println!(
+            "compression_ratio: {:.3}",
+            (ptr_size + data_bytes) as f64 / self.sum_str_len as f64
+        );
+    }
+}
+
 /// Immutable lists of strings compressed by rear-coded prefix omission.
 ///
 /// Prefix omission compresses a list of strings omitting the common prefixes of
@@ -51,7 +352,17 @@ struct Stats {
 ///
 /// The encoding is done in blocks of `k` strings: in each block the first
 /// string is encoded without compression, wheres the other strings are encoded
-/// with the common prefix removed.
+/// with the common prefix removed. All strings are length-prefixed, so
+/// they can contain any byte.
+///
+/// The type parameter `I` is the type of the strings in the list: it is
+/// [`str`] by default, but it can be `[u8]` to store arbitrary byte strings
+/// (see [`RearCodedElement`]). Note that lists of strings and lists of byte
+/// strings have different [ε-serde](epserde) types.
+///
+/// The type parameter `C` is the [coder](RearLengthCoder) of the lengths
+/// preceding each suffix; it is [`VByteCoder`] by default, and it is part of
+/// the [ε-serde](epserde) type, too.
 ///
 /// Rear-coded lists can be iterated upon using either an
 /// [`Iterator`](RearCodedList::iter) or a [`Lender`](RearCodedList::lend).
@@ -80,22 +391,65 @@ struct Stats {
 /// assert_eq!(rcl.get(1), "aab");
 /// assert_eq!(rcl.get(2), "abc");
 /// ```
+///
+/// Byte strings work in the same way, but the builder must be created with
+/// [`new_bytes`](RearCodedListBuilder::new_bytes):
+///
+/// ```rust
+/// use sux::traits::{IndexedDict, IndexedSeq};
+/// use sux::dict::RearCodedListBuilder;
+/// let mut rclb = RearCodedListBuilder::new_bytes(4);
+///
+/// rclb.push(b"a\0\xff");
+/// rclb.push(b"a\0\xff\0");
+/// rclb.push(b"b");
+///
+/// let rcl = rclb.build();
+/// assert_eq!(rcl.get(1), b"a\0\xff\0");
+/// assert_eq!(rcl.index_of(b"b".as_slice()), Some(2));
+/// ```
 
-#[derive(Debug, Clone, Epserde, MemDbg, MemSize)]
-pub struct RearCodedList<D: AsRef<[u8]> = Box<[u8]>, P: AsRef<[usize]> = Box<[usize]>> {
+#[derive(Debug, Epserde, MemDbg, MemSize)]
+pub struct RearCodedList<
+    I: ?Sized = str,
+    C = VByteCoder,
+    D: AsRef<[u8]> = Box<[u8]>,
+    P: AsRef<[usize]> = Box<[usize]>,
+> {
     /// The number of strings in a block; this value trades off compression for speed.
     k: usize,
     /// Number of encoded strings.
     len: usize,
     /// Whether the strings are sorted.
     is_sorted: bool,
-    /// The encoded strings, `\0`-terminated.
+    /// The encoded strings, length-prefixed.
     data: D,
     /// The pointer to the starting string of each block.
     pointers: P,
+    _marker: PhantomData<I>,
+    _coder: PhantomData<C>,
+}
+
+// Derived implementations would require `I: Clone`
+impl<I: ?Sized, C, D: AsRef<[u8]> + Clone, P: AsRef<[usize]> + Clone> Clone
+    for RearCodedList<I, C, D, P>
+{
+    fn clone(&self) -> Self {
+        Self {
+            k: self.k,
+            len: self.len,
+            is_sorted: self.is_sorted,
+            data: self.data.clone(),
+            pointers: self.pointers.clone(),
+            _marker: PhantomData,
+            _coder: PhantomData,
+        }
+    }
 }
 
-impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
+impl<I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    RearCodedList<I, C, D, P>
+{
     /// Returns the number of strings.
     ///
     /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
@@ -107,7 +461,7 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
 
     /// Returns an [`Iterator`] over the strings starting from the given position.
     #[inline(always)]
-    pub fn iter_from(&self, from: usize) -> Iter<'_, D, P> {
+    pub fn iter_from(&self, from: usize) -> Iter<'_, I, C, D, P> {
         Iter {
             iter: Lend::new_from(self, from),
         }
@@ -115,22 +469,28 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
 
     /// Returns an [`Iterator`] over the strings.
     #[inline(always)]
-    pub fn iter(&self) -> Iter<'_, D, P> {
+    pub fn iter(&self) -> Iter<'_, I, C, D, P> {
         self.iter_from(0)
     }
 
     /// Returns a [`Lender`] over the strings starting from the given position.
     #[inline(always)]
-    pub fn lend_from(&self, from: usize) -> Lend<'_, D, P> {
+    pub fn lend_from(&self, from: usize) -> Lend<'_, I, C, D, P> {
         Lend::new_from(self, from)
     }
 
     /// Returns a [`Lender`] over the strings.
     #[inline(always)]
-    pub fn lend(&self) -> Lend<'_, D, P> {
+    pub fn lend(&self) -> Lend<'_, I, C, D, P> {
         self.lend_from(0)
     }
 
+    /// Returns a [cursor](RearCodedListCursor) on the strings.
+    #[inline(always)]
+    pub fn cursor(&self) -> RearCodedListCursor<'_, I, C, D, P> {
+        RearCodedListCursor::new(self)
+    }
+
     /// Writes the index-th string to `result` as bytes. This is useful to avoid
     /// allocating a new string for every query and skipping the UTF-8 validity
     /// check.
@@ -144,90 +504,294 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
         let data = &self.data.as_ref()[start..];
 
         // decode the first string in the block
-        let mut data = strcpy(data, result);
+        let mut data = copy_bytes(data, result);
 
         for _ in 0..offset {
-            // get how much data to throw away
-            let (len, tmp) = decode_int(data);
-            // throw away the data
-            result.resize(result.len() - len, 0);
-            // copy the new suffix
-            let tmp = strcpy(tmp, result);
-            data = tmp;
+            data = copy_suffix::<C>(data, result);
         }
     }
 
-    fn index_of_unsorted(&self, value: impl Borrow<<Self as Types>::Input>) -> Option<usize> {
+    fn index_of_unsorted(&self, value: impl Borrow<I>) -> Option<usize> {
         let key = value.borrow().as_bytes();
         let mut iter = self.into_lender().enumerate();
         while let Some((idx, string)) = iter.next() {
-            if matches!(
-                strcmp_rust(key, string.as_bytes()),
-                core::cmp::Ordering::Equal
-            ) {
+            if string.as_bytes() == key {
                 return Some(idx);
             }
         }
         None
     }
 
-    fn index_of_sorted(&self, value: impl Borrow<<Self as Types>::Input>) -> Option<usize> {
-        let string = value.borrow().as_bytes();
-        // first to a binary search on the blocks to find the block
-        let block_idx = self.pointers.as_ref().binary_search_by(|block_ptr| {
-            strcmp(string, &self.data.as_ref()[*block_ptr..]).reverse()
+    /// Returns the index of the first string for which `pred` is false,
+    /// assuming that the list is sorted and that `pred` is true on a prefix of
+    /// the list; if the index is smaller than the length of the list, `result`
+    /// will contain the corresponding string.
+    ///
+    /// The block is located by a binary search on the first string of each
+    /// block, followed by a linear scan of the block.
+    fn partition_point_in_place(
+        &self,
+        pred: impl Fn(&[u8]) -> bool,
+        result: &mut Vec<u8>,
+    ) -> usize {
+        let data = self.data.as_ref();
+        let pointers = self.pointers.as_ref();
+        // first do a binary search on the blocks to find the block
+        let block_idx = pointers.partition_point(|&block_ptr| {
+            let (len, head) = decode_int(&data[block_ptr..]);
+            pred(&head[..len])
         });
-
-        if let Ok(block_idx) = block_idx {
-            return Some(block_idx * self.k);
+        result.clear();
+        if block_idx == 0 {
+            // the first string does not satisfy the predicate
+            if self.len != 0 {
+                copy_bytes(data, result);
+            }
+            return 0;
         }
+        let block_idx = block_idx - 1;
+        // finish by a linear search on the block
+        let start = pointers[block_idx];
+        let mut data = copy_bytes(&data[start..], result);
+        let in_block = (self.k - 1).min(self.len - block_idx * self.k - 1);
+        for idx in 0..in_block {
+            data = copy_suffix::<C>(data, result);
 
-        let mut block_idx = block_idx.unwrap_err();
-        if block_idx == 0 || block_idx > self.pointers.as_ref().len() {
-            // the string is before the first block
-            return None;
+            if !pred(result) {
+                return block_idx * self.k + idx + 1;
+            }
         }
-        block_idx -= 1;
-        // finish by a linear search on the block
+        // the answer is the first string of the next block
+        let index = block_idx * self.k + in_block + 1;
+        if index < self.len {
+            result.clear();
+            copy_bytes(data, result);
+        }
+        index
+    }
+
+    fn index_of_sorted(&self, value: impl Borrow<I>) -> Option<usize> {
+        let string = value.borrow().as_bytes();
         let mut result = Vec::with_capacity(128);
-        let start = self.pointers.as_ref()[block_idx];
-        let data = &self.data.as_ref()[start..];
+        let index = self.partition_point_in_place(|s| s < string, &mut result);
+        (index < self.len && result == string).then_some(index)
+    }
 
-        // decode the first string in the block
-        let mut data = strcpy(data, &mut result);
-        let in_block = (self.k - 1).min(self.len - block_idx * self.k - 1);
-        for idx in 0..in_block {
-            // get how much data to throw away
-            let (len, tmp) = decode_int(data);
-            let lcp = result.len() - len;
-            // throw away the data
-            result.resize(lcp, 0);
-            // copy the new suffix
-            let tmp = strcpy(tmp, &mut result);
-            data = tmp;
+    /// Returns the index of the first string greater than or equal to
+    /// `string`, or the length of the list if there is no such string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strings in the list are not sorted.
+    pub fn lower_bound(&self, string: impl AsRef<I>) -> usize {
+        assert!(self.is_sorted, "The strings in the list are not sorted");
+        let string = string.as_ref().as_bytes();
+        self.partition_point_in_place(|s| s < string, &mut Vec::with_capacity(128))
+    }
 
-            // TODO!: this can be optimized to avoid the copy
-            match strcmp_rust(string, &result) {
-                core::cmp::Ordering::Less => {}
-                core::cmp::Ordering::Equal => return Some(block_idx * self.k + idx + 1),
-                core::cmp::Ordering::Greater => return None,
+    /// Returns the index of the first string greater than `string`, or the
+    /// length of the list if there is no such string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strings in the list are not sorted.
+    pub fn upper_bound(&self, string: impl AsRef<I>) -> usize {
+        assert!(self.is_sorted, "The strings in the list are not sorted");
+        let string = string.as_ref().as_bytes();
+        self.partition_point_in_place(|s| s <= string, &mut Vec::with_capacity(128))
+    }
+
+    /// Returns the range of indices of the strings starting with `prefix`.
+    ///
+    /// If no string starts with `prefix`, the returned range is empty and
+    /// starts at the position at which `prefix` would be inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strings in the list are not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// for s in ["aa", "aab", "abc", "abdd", "abde", "abdf", "b"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    ///
+    /// assert_eq!(rcl.prefix_range("abd"), 3..6);
+    /// assert_eq!(rcl.prefix_range("ac"), 6..6);
+    /// assert_eq!(rcl.lower_bound("ab"), 2);
+    /// assert_eq!(rcl.upper_bound("abc"), 3);
+    /// ```
+    pub fn prefix_range(&self, prefix: impl AsRef<I>) -> Range<usize> {
+        let start = self.lower_bound(prefix.as_ref());
+        let prefix = prefix.as_ref().as_bytes();
+        let end = self.partition_point_in_place(
+            |s| &s[..s.len().min(prefix.len())] <= prefix,
+            &mut Vec::with_capacity(128),
+        );
+        start..end
+    }
+
+    /// Returns a [`Lender`] over the strings with index in the given range.
+    ///
+    /// Combined with [`prefix_range`](RearCodedList::prefix_range), this
+    /// method makes it possible to enumerate the strings starting with a
+    /// given prefix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is not contained in the indices of the list.
+    pub fn lend_range(&self, range: Range<usize>) -> Lend<'_, I, C, D, P> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Range out of bounds: {:?} for length {}",
+            range,
+            self.len
+        );
+        if range.start == range.end {
+            return Lend {
+                rca: self,
+                buffer: Vec::new(),
+                data: &[],
+                index: range.start,
+                end: range.end,
+            };
+        }
+        let mut lend = Lend::new_from(self, range.start);
+        lend.end = range.end;
+        lend
+    }
+
+    /// Prints in a human-readable format the statistics of the strings in
+    /// the list.
+    ///
+    /// The statistics are computed by scanning the encoded data, and they are
+    /// the same printed by [`RearCodedListBuilder::print_stats`] when the list
+    /// was built.
+    pub fn print_stats(&self) {
+        self.stats().print(
+            self.len,
+            self.data.as_ref().len(),
+            self.pointers.as_ref().len(),
+            core::mem::size_of::<Self>(),
+        );
+    }
+
+    /// Computes the statistics that the builder gathered while encoding the
+    /// strings of the list.
+    fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        let data = self.data.as_ref();
+        let mut last_ptr = 0;
+        let mut string = Vec::with_capacity(128);
+        for (block, &ptr) in self.pointers.as_ref().iter().enumerate() {
+            // as in the builder, the size of a block is known when the next
+            // one starts
+            let block_bytes = ptr - last_ptr;
+            stats.max_block_bytes = stats.max_block_bytes.max(block_bytes);
+            stats.sum_block_bytes += block_bytes;
+            last_ptr = ptr;
+
+            let (len, tail) = decode_int(&data[ptr..]);
+            let head = &tail[..len];
+            if block != 0 {
+                let (lcp, _) = longest_common_prefix(&string, head);
+                stats.redundancy += (lcp + encode_int_len(len)) as isize;
+                stats.redundancy -= C::encoded_len(string.len() - lcp, len - lcp) as isize;
+            }
+            stats.code_bytes += encode_int_len(len);
+            stats.suffixes_bytes += len;
+            stats.max_str_len = stats.max_str_len.max(len);
+            stats.sum_str_len += len;
+            string.clear();
+            string.extend_from_slice(head);
+
+            let mut tail = &tail[len..];
+            for _ in 1..self.k.min(self.len - block * self.k) {
+                let (rear_length, suffix_length, _) = C::decode(tail);
+                let lcp = string.len() - rear_length;
+                let code_bytes = C::encoded_len(rear_length, suffix_length);
+                stats.max_lcp = stats.max_lcp.max(lcp);
+                stats.sum_lcp += lcp;
+                stats.vbyte_code_bytes += VByteCoder::encoded_len(rear_length, suffix_length);
+                stats.nibble_code_bytes += NibbleCoder::encoded_len(rear_length, suffix_length);
+                stats.byte_escape_code_bytes +=
+                    ByteEscapeCoder::encoded_len(rear_length, suffix_length);
+                stats.code_bytes += code_bytes;
+                stats.suffixes_bytes += suffix_length;
+
+                tail = copy_suffix::<C>(tail, &mut string);
+                stats.max_str_len = stats.max_str_len.max(string.len());
+                stats.sum_str_len += string.len();
             }
         }
-        None
+        stats
+    }
+
+    /// Returns a new list containing the strings of this list followed by the
+    /// strings of `other`.
+    ///
+    /// The block size of the result is that of this list. If the two lists
+    /// have the same block size and the length of this list is a multiple of
+    /// the block size, the encoded data is just concatenated; otherwise, the
+    /// strings of `other` are encoded again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::{IndexedDict, IndexedSeq};
+    /// use sux::dict::RearCodedListBuilder;
+    /// let mut rclb = RearCodedListBuilder::new(2);
+    /// rclb.push("aa");
+    /// rclb.push("ab");
+    /// let first = rclb.build();
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(2);
+    /// rclb.push("b");
+    /// rclb.push("bc");
+    /// rclb.push("bd");
+    /// let second = rclb.build();
+    ///
+    /// let rcl = first.concat(&second);
+    /// assert_eq!(rcl.len(), 5);
+    /// assert_eq!(rcl.get(3), "bc");
+    /// assert_eq!(rcl.index_of("bd"), Some(4));
+    /// ```
+    pub fn concat<D2: AsRef<[u8]>, P2: AsRef<[usize]>>(
+        &self,
+        other: &RearCodedList<I, C, D2, P2>,
+    ) -> RearCodedList<I, C, Box<[u8]>, Box<[usize]>> {
+        let mut builder = RearCodedListBuilder::from_list(self);
+        if self.k == other.k && self.len.is_multiple_of(self.k) {
+            builder.append(RearCodedListBuilder::from_list(other));
+        } else {
+            let mut lender = other.into_lender();
+            while let Some(string) = lender.next() {
+                builder.push(string);
+            }
+        }
+        builder.build()
     }
 }
 
-impl<D: AsRef<[u8]>, P: AsRef<[usize]>> Types for RearCodedList<D, P> {
-    type Output = String;
-    type Input = str;
+impl<I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>> Types
+    for RearCodedList<I, C, D, P>
+{
+    type Output = I::Owned;
+    type Input = I;
 }
 
-impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedSeq for RearCodedList<D, P> {
+impl<I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedSeq
+    for RearCodedList<I, C, D, P>
+{
     #[inline(always)]
     unsafe fn get_unchecked(&self, index: usize) -> Self::Output {
         let mut result = Vec::with_capacity(128);
         self.get_in_place(index, &mut result);
-        String::from_utf8(result).unwrap()
+        I::from_vec_unchecked(result)
     }
 
     #[inline(always)]
@@ -236,7 +800,9 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedSeq for RearCodedList<D, P> {
     }
 }
 
-impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedDict for RearCodedList<D, P> {
+impl<I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    IndexedDict for RearCodedList<I, C, D, P>
+{
     /// If the strings in the list are sorted this is done with a binary search,
     /// otherwise it is done with a linear search.
     #[inline(always)]
@@ -253,35 +819,49 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedDict for RearCodedList<D, P> {
     }
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> IntoLender for &'a RearCodedList<D, P> {
-    type Lender = Lend<'a, D, P>;
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    IntoLender for &'a RearCodedList<I, C, D, P>
+{
+    type Lender = Lend<'a, I, C, D, P>;
     #[inline(always)]
-    fn into_lender(self) -> Lend<'a, D, P> {
+    fn into_lender(self) -> Lend<'a, I, C, D, P> {
         Lend::new(self)
     }
 }
 
 /// Sequential [`Iterator`] over the strings.
-#[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct Iter<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> {
-    iter: Lend<'a, D, P>,
+#[derive(Debug, MemDbg, MemSize)]
+pub struct Iter<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> {
+    iter: Lend<'a, I, C, D, P>,
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::ExactSizeIterator for Iter<'a, D, P> {
+impl<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> Clone for Iter<'a, I, C, D, P> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    std::iter::ExactSizeIterator for Iter<'a, I, C, D, P>
+{
     #[inline(always)]
     fn len(&self) -> usize {
         self.iter.len()
     }
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::Iterator for Iter<'a, D, P> {
-    type Item = String;
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    std::iter::Iterator for Iter<'a, I, C, D, P>
+{
+    type Item = I::Owned;
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         self.iter
             .next()
-            .map(|v| unsafe { String::from_utf8_unchecked(Vec::from(v)) })
+            .map(|v| unsafe { I::from_vec_unchecked(Vec::from(v.as_bytes())) })
     }
 
     #[inline(always)]
@@ -290,9 +870,11 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::Iterator for Iter<'a, D,
     }
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> IntoIterator for &'a RearCodedList<D, P> {
-    type Item = String;
-    type IntoIter = Iter<'a, D, P>;
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    IntoIterator for &'a RearCodedList<I, C, D, P>
+{
+    type Item = I::Owned;
+    type IntoIter = Iter<'a, I, C, D, P>;
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
         Iter {
@@ -302,25 +884,42 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> IntoIterator for &'a RearCodedList<D
 }
 
 /// Sequential [`Lender`] over the strings.
-#[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct Lend<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> {
-    rca: &'a RearCodedList<D, P>,
+#[derive(Debug, MemDbg, MemSize)]
+pub struct Lend<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> {
+    rca: &'a RearCodedList<I, C, D, P>,
     buffer: Vec<u8>,
     data: &'a [u8],
     index: usize,
+    /// The index at which the lender stops.
+    end: usize,
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lend<'a, D, P> {
-    pub fn new(rca: &'a RearCodedList<D, P>) -> Self {
+impl<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> Clone for Lend<'a, I, C, D, P> {
+    fn clone(&self) -> Self {
+        Self {
+            rca: self.rca,
+            buffer: self.buffer.clone(),
+            data: self.data,
+            index: self.index,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    Lend<'a, I, C, D, P>
+{
+    pub fn new(rca: &'a RearCodedList<I, C, D, P>) -> Self {
         Self {
             rca,
             buffer: Vec::with_capacity(128),
             data: rca.data.as_ref(),
             index: 0,
+            end: rca.len(),
         }
     }
 
-    pub fn new_from(rca: &'a RearCodedList<D, P>, from: usize) -> Self {
+    pub fn new_from(rca: &'a RearCodedList<I, C, D, P>, from: usize) -> Self {
         let block = from / rca.k;
         let offset = from % rca.k;
 
@@ -328,6 +927,7 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lend<'a, D, P> {
         let mut res = Lend {
             rca,
             index: block * rca.k,
+            end: rca.len(),
             data: &rca.data.as_ref()[start..],
             buffer: Vec::with_capacity(128),
         };
@@ -338,32 +938,40 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lend<'a, D, P> {
     }
 }
 
-impl<'a, 'b, D: AsRef<[u8]>, P: AsRef<[usize]>> Lending<'a> for Lend<'b, D, P> {
-    type Lend = &'a str;
+impl<
+        'a,
+        'b,
+        I: ?Sized + RearCodedElement,
+        C: RearLengthCoder,
+        D: AsRef<[u8]>,
+        P: AsRef<[usize]>,
+    > Lending<'a> for Lend<'b, I, C, D, P>
+{
+    type Lend = &'a I;
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender for Lend<'a, D, P> {
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender
+    for Lend<'a, I, C, D, P>
+{
     #[inline]
     /// A next that returns a reference to the inner buffer containg the string.
     /// This is useful to avoid allocating a new string for every query if you
     /// don't need to keep the string around.
-    fn next(&mut self) -> Option<&'_ str> {
-        if self.index >= self.rca.len() {
+    fn next(&mut self) -> Option<&'_ I> {
+        if self.index >= self.end {
             return None;
         }
 
         if self.index % self.rca.k == 0 {
             // just copy the data
             self.buffer.clear();
-            self.data = strcpy(self.data, &mut self.buffer);
+            self.data = copy_bytes(self.data, &mut self.buffer);
         } else {
-            let (len, tmp) = decode_int(self.data);
-            self.buffer.resize(self.buffer.len() - len, 0);
-            self.data = strcpy(tmp, &mut self.buffer);
+            self.data = copy_suffix::<C>(self.data, &mut self.buffer);
         }
         self.index += 1;
 
-        Some(unsafe { std::str::from_utf8_unchecked(&self.buffer) })
+        Some(unsafe { I::from_bytes_unchecked(&self.buffer) })
     }
 
     #[inline(always)]
@@ -372,23 +980,151 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender for Lend<'a, D, P> {
     }
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> ExactSizeLender for Lend<'a, D, P> {
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    ExactSizeLender for Lend<'a, I, C, D, P>
+{
     #[inline(always)]
     fn len(&self) -> usize {
-        self.rca.len() - self.index
+        self.end - self.index
+    }
+}
+
+/// A stateful cursor providing random access to the strings of a
+/// [`RearCodedList`].
+///
+/// Differently from [`get_in_place`](RearCodedList::get_in_place), which
+/// decodes from the first string of the block at each call, a cursor
+/// remembers the last string it returned: if the next requested string is in
+/// the same block and has a larger index, only the missing suffixes are
+/// decoded. Moreover, the first string of a block is returned as a slice of
+/// the encoded data, without copying it.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::dict::RearCodedListBuilder;
+/// let mut rclb = RearCodedListBuilder::new(4);
+/// for s in ["aa", "aab", "abc", "abdd", "abde", "abdf"] {
+///     rclb.push(s);
+/// }
+/// let rcl = rclb.build();
+///
+/// let mut cursor = rcl.cursor();
+/// assert_eq!(cursor.get(1), "aab");
+/// // Decodes just the suffixes of the strings of index 2 and 3
+/// assert_eq!(cursor.get(3), "abdd");
+/// // Borrowed from the encoded data
+/// assert_eq!(cursor.get(4), "abde");
+/// assert_eq!(cursor.get(0), "aa");
+/// assert_eq!(cursor.index(), Some(0));
+/// ```
+#[derive(Debug, MemDbg, MemSize)]
+pub struct RearCodedListCursor<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> {
+    rcl: &'a RearCodedList<I, C, D, P>,
+    /// The current string, if it is not the first string of its block.
+    buffer: Vec<u8>,
+    /// The current string, if it is the first string of its block.
+    head: Option<&'a [u8]>,
+    /// The encoded data following the current string.
+    data: &'a [u8],
+    /// The index of the current string, if any.
+    index: Option<usize>,
+}
+
+impl<'a, I: ?Sized, C, D: AsRef<[u8]>, P: AsRef<[usize]>> Clone
+    for RearCodedListCursor<'a, I, C, D, P>
+{
+    fn clone(&self) -> Self {
+        Self {
+            rcl: self.rcl,
+            buffer: self.buffer.clone(),
+            head: self.head,
+            data: self.data,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, I: ?Sized + RearCodedElement, C: RearLengthCoder, D: AsRef<[u8]>, P: AsRef<[usize]>>
+    RearCodedListCursor<'a, I, C, D, P>
+{
+    pub fn new(rcl: &'a RearCodedList<I, C, D, P>) -> Self {
+        Self {
+            rcl,
+            buffer: Vec::with_capacity(128),
+            head: None,
+            data: &[],
+            index: None,
+        }
+    }
+
+    /// Returns the index of the last string returned by
+    /// [`get`](RearCodedListCursor::get), if any.
+    #[inline(always)]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the string of given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn get(&mut self, index: usize) -> &I {
+        if index >= self.rcl.len {
+            panic!("Index out of bounds: {} >= {}", index, self.rcl.len)
+        }
+        let k = self.rcl.k;
+        let mut current = match self.index {
+            Some(current) if current <= index && current / k == index / k => current,
+            _ => {
+                // restart from the first string of the block
+                let block = index / k;
+                let start = self.rcl.pointers.as_ref()[block];
+                let (len, data) = decode_int(&self.rcl.data.as_ref()[start..]);
+                self.head = Some(&data[..len]);
+                self.data = &data[len..];
+                block * k
+            }
+        };
+
+        if current < index {
+            if let Some(head) = self.head.take() {
+                self.buffer.clear();
+                self.buffer.extend_from_slice(head);
+            }
+            while current < index {
+                self.data = copy_suffix::<C>(self.data, &mut self.buffer);
+                current += 1;
+            }
+        }
+        self.index = Some(index);
+
+        let bytes = match self.head {
+            Some(head) => head,
+            None => &self.buffer,
+        };
+        unsafe { I::from_bytes_unchecked(bytes) }
     }
 }
 
 /// Builder for a rear-coded list.
-#[derive(Debug, Clone, MemDbg, MemSize)]
-pub struct RearCodedListBuilder {
+///
+/// The type parameter `I` is the type of the strings in the list; use
+/// [`new`](RearCodedListBuilder::new) to build a list of [`str`] and
+/// [`new_bytes`](RearCodedListBuilder::new_bytes) to build a list of `[u8]`.
+/// The type parameter `C` is the [coder of the lengths](RearLengthCoder);
+/// to use a coder different from the default one, use
+/// [`with_block_size`](RearCodedListBuilder::with_block_size).
+#[derive(Debug, MemDbg, MemSize)]
+pub struct RearCodedListBuilder<I: ?Sized = str, C = VByteCoder> {
     /// The number of strings in a block; this value trades compression for speed.
     k: usize,
     /// Number of encoded strings.
     len: usize,
     /// Whether the strings are sorted.
     is_sorted: bool,
-    /// The encoded strings, `\0`-terminated.
+    /// The encoded strings, length-prefixed.
     data: Vec<u8>,
     /// The pointer to the starting string of each block.
     pointers: Vec<usize>,
@@ -396,56 +1132,81 @@ pub struct RearCodedListBuilder {
     stats: Stats,
     /// Cache of the last encoded string for incremental encoding.
     last_str: Vec<u8>,
+    _marker: PhantomData<I>,
+    _coder: PhantomData<C>,
 }
 
-/// Copies a string until the first `\0` from `data` to `result` and return the
-/// remaining data.
-#[inline(always)]
-fn strcpy<'a>(mut data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
-    loop {
-        let c = data[0];
-        data = &data[1..];
-        if c == 0 {
-            break;
+impl<I: ?Sized, C> Clone for RearCodedListBuilder<I, C> {
+    fn clone(&self) -> Self {
+        Self {
+            k: self.k,
+            len: self.len,
+            is_sorted: self.is_sorted,
+            data: self.data.clone(),
+            pointers: self.pointers.clone(),
+            stats: self.stats.clone(),
+            last_str: self.last_str.clone(),
+            _marker: PhantomData,
+            _coder: PhantomData,
         }
-        result.push(c);
     }
-    data
 }
 
+/// Copies a length-prefixed string from `data` to `result` and returns the
+/// remaining data.
 #[inline(always)]
-/// Like strcmp, but `string` is a Rust string and data is a `\0`-terminated string.
-fn strcmp(string: &[u8], data: &[u8]) -> core::cmp::Ordering {
-    for (i, c) in string.iter().enumerate() {
-        let ord = c.cmp(&data[i]);
-        if ord != core::cmp::Ordering::Equal {
-            return ord;
-        }
-    }
+fn copy_bytes<'a>(data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
+    let (len, data) = decode_int(data);
+    result.extend_from_slice(&data[..len]);
+    &data[len..]
+}
 
-    if data[string.len()] == 0 {
-        core::cmp::Ordering::Equal
-    } else {
-        core::cmp::Ordering::Less
+/// Replaces the suffix of the string in `result` with the one encoded in
+/// `data` using the coder `C`, and returns the remaining data.
+#[inline(always)]
+fn copy_suffix<'a, C: RearLengthCoder>(data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
+    let (rear_length, suffix_length, data) = C::decode(data);
+    // throw away the rear of the previous string
+    result.truncate(result.len() - rear_length);
+    // copy the new suffix
+    result.extend_from_slice(&data[..suffix_length]);
+    &data[suffix_length..]
+}
+
+impl RearCodedListBuilder {
+    /// Creates a builder for a rear-coded list of strings with a block size
+    /// of `k`.
+    pub fn new(k: usize) -> Self {
+        Self::with_block_size(k)
     }
 }
 
-#[inline(always)]
-/// Like strcmp, but both string are Rust strings.
-fn strcmp_rust(string: &[u8], other: &[u8]) -> core::cmp::Ordering {
-    for (i, c) in string.iter().enumerate() {
-        match other.get(i).unwrap_or(&0).cmp(c) {
-            core::cmp::Ordering::Equal => {}
-            ord => return ord,
-        }
+impl RearCodedListBuilder<[u8]> {
+    /// Creates a builder for a rear-coded list of byte strings with a block
+    /// size of `k`.
+    pub fn new_bytes(k: usize) -> Self {
+        Self::with_block_size(k)
     }
-    // string has an implicit final \0
-    other.len().cmp(&string.len())
 }
 
-impl RearCodedListBuilder {
+impl<I: ?Sized + RearCodedElement, C: RearLengthCoder> RearCodedListBuilder<I, C> {
     /// Creates a builder for a rear-coded list with a block size of `k`.
-    pub fn new(k: usize) -> Self {
+    ///
+    /// Since all type parameters are free, they must usually be specified
+    /// explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::{NibbleCoder, RearCodedListBuilder};
+    /// let mut rclb = RearCodedListBuilder::<str, NibbleCoder>::with_block_size(4);
+    /// rclb.push("aa");
+    /// rclb.push("aab");
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.get(1), "aab");
+    /// ```
+    pub fn with_block_size(k: usize) -> Self {
         Self {
             data: Vec::with_capacity(1024),
             last_str: Vec::with_capacity(1024),
@@ -454,33 +1215,56 @@ impl RearCodedListBuilder {
             is_sorted: true,
             k,
             stats: Default::default(),
+            _marker: PhantomData,
+            _coder: PhantomData,
         }
     }
 
+    /// Returns a builder containing the strings of a list.
+    ///
+    /// Statistics about the strings of the list are not available.
+    fn from_list<D: AsRef<[u8]>, P: AsRef<[usize]>>(list: &RearCodedList<I, C, D, P>) -> Self {
+        let mut builder = Self::with_block_size(list.k);
+        builder.data.extend_from_slice(list.data.as_ref());
+        builder.pointers.extend_from_slice(list.pointers.as_ref());
+        builder.len = list.len;
+        builder.is_sorted = list.is_sorted;
+        if list.len != 0 {
+            list.get_in_place(list.len - 1, &mut builder.last_str);
+        }
+        builder
+    }
+
     /// Builds the rear-coded list.
-    pub fn build(self) -> RearCodedList<Box<[u8]>, Box<[usize]>> {
+    pub fn build(self) -> RearCodedList<I, C, Box<[u8]>, Box<[usize]>> {
         RearCodedList {
             data: self.data.into(),
             pointers: self.pointers.into(),
             len: self.len,
             is_sorted: self.is_sorted,
             k: self.k,
+            _marker: PhantomData,
+            _coder: PhantomData,
         }
     }
 
     /// Appends a string to the end of the list.
-    pub fn push(&mut self, string: impl AsRef<str>) {
-        let string = string.as_ref();
+    pub fn push(&mut self, string: impl AsRef<I>) {
+        let string = string.as_ref().as_bytes();
         // update stats
         self.stats.max_str_len = self.stats.max_str_len.max(string.len());
         self.stats.sum_str_len += string.len();
 
-        let (lcp, order) = longest_common_prefix(&self.last_str, string.as_bytes());
+        let (lcp, order) = longest_common_prefix(&self.last_str, string);
 
         if order == core::cmp::Ordering::Greater {
             self.is_sorted = false;
         }
 
+        let rear_length = self.last_str.len() - lcp;
+        let suffix_length = string.len() - lcp;
+        let prev_len = self.data.len();
+
         // at every multiple of k we just encode the string as is
         let to_encode = if self.len % self.k == 0 {
             // compute the size in bytes of the previous block
@@ -493,35 +1277,34 @@ impl RearCodedListBuilder {
             self.pointers.push(self.data.len());
 
             // compute the redundancy
-            let rear_length = self.last_str.len() - lcp;
             if self.len != 0 {
-                self.stats.redundancy += lcp as isize;
-                self.stats.redundancy -= encode_int_len(rear_length) as isize;
+                self.stats.redundancy += (lcp + encode_int_len(string.len())) as isize;
+                self.stats.redundancy -= C::encoded_len(rear_length, suffix_length) as isize;
             }
-            // just encode the whole string
-            string.as_bytes()
+            // encode the length of the whole string
+            encode_int(string.len(), &mut self.data);
+            string
         } else {
             // update the stats
             self.stats.max_lcp = self.stats.max_lcp.max(lcp);
             self.stats.sum_lcp += lcp;
-            // encode the len of the bytes in data
-            let rear_length = self.last_str.len() - lcp;
-            let prev_len = self.data.len();
-            encode_int(rear_length, &mut self.data);
-            // update stats
-            self.stats.code_bytes += self.data.len() - prev_len;
+            self.stats.vbyte_code_bytes += VByteCoder::encoded_len(rear_length, suffix_length);
+            self.stats.nibble_code_bytes += NibbleCoder::encoded_len(rear_length, suffix_length);
+            self.stats.byte_escape_code_bytes +=
+                ByteEscapeCoder::encoded_len(rear_length, suffix_length);
+            // encode the rear length and the length of the suffix
+            C::encode(rear_length, suffix_length, &mut self.data);
             // return the delta suffix
-            &string.as_bytes()[lcp..]
+            &string[lcp..]
         };
-        // Write the data to the buffer
+        self.stats.code_bytes += self.data.len() - prev_len;
+        // write the data to the buffer
         self.data.extend_from_slice(to_encode);
-        // push the \0 terminator
-        self.data.push(0);
-        self.stats.suffixes_bytes += to_encode.len() + 1;
+        self.stats.suffixes_bytes += to_encode.len();
 
         // put the string as last_str for the next iteration
         self.last_str.clear();
-        self.last_str.extend_from_slice(string.as_bytes());
+        self.last_str.extend_from_slice(string);
         self.len += 1;
     }
 
@@ -551,7 +1334,7 @@ impl RearCodedListBuilder {
     /// rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
     /// let rcl = rclb.build();
     /// ```
-    pub fn extend<S: Borrow<str>, L: IntoLender>(&mut self, into_lender: L)
+    pub fn extend<S: Borrow<I>, L: IntoLender>(&mut self, into_lender: L)
     where
         L::Lender: for<'lend> Lending<'lend, Lend = S>,
     {
@@ -560,80 +1343,132 @@ impl RearCodedListBuilder {
         });
     }
 
-    /// Prints in a human-readable format the statistics of the
-    /// strings currently in the builder.
-    pub fn print_stats(&self) {
-        println!(
-            "{:>20}: {:>10}",
-            "max_block_bytes", self.stats.max_block_bytes
-        );
-        println!(
-            "{:>20}: {:>10.3}",
-            "avg_block_bytes",
-            self.stats.sum_block_bytes as f64 / self.len as f64
-        );
+    /// Appends in parallel all the strings of a slice to the end of the list.
+    ///
+    /// The slice is split at block boundaries, and each part is encoded
+    /// independently; the resulting data is then concatenated, rebasing the
+    /// pointers.
+    ///
+    /// If the feature "rayon" is enabled, this method is computed in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::RearCodedListBuilder;
+    /// let words = (0..1000).map(|i| format!("{:05}", i)).collect::<Vec<_>>();
+    /// let mut rclb = RearCodedListBuilder::new(8);
+    /// rclb.par_extend(&words);
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.get(123), "00123");
+    /// ```
+    pub fn par_extend<S: AsRef<I> + Sync>(&mut self, strings: &[S])
+    where
+        I: Send,
+        C: Send,
+    {
+        // complete the current block sequentially
+        let head = ((self.k - self.len % self.k) % self.k).min(strings.len());
+        for string in &strings[..head] {
+            self.push(string);
+        }
+        let strings = &strings[head..];
+        if strings.is_empty() {
+            return;
+        }
 
-        println!("{:>20}: {:>10}", "max_lcp", self.stats.max_lcp);
-        println!(
-            "{:>20}: {:>10.3}",
-            "avg_lcp",
-            self.stats.sum_lcp as f64 / self.len as f64
-        );
+        let encode = |chunk: &[S]| {
+            let mut builder = Self::with_block_size(self.k);
+            for string in chunk {
+                builder.push(string);
+            }
+            builder
+        };
 
-        println!("{:>20}: {:>10}", "max_str_len", self.stats.max_str_len);
-        println!(
-            "{:>20}: {:>10.3}",
-            "avg_str_len",
-            self.stats.sum_str_len as f64 / self.len as f64
-        );
+        #[cfg(feature = "rayon")]
+        let builders = {
+            // a few parts per thread, each made of whole blocks
+            let parts = rayon::current_num_threads() * 4;
+            let chunk_len = strings.len().div_ceil(parts).next_multiple_of(self.k);
+            strings
+                .par_chunks(chunk_len)
+                .map(encode)
+                .collect::<Vec<_>>()
+        };
 
-        let ptr_size: usize = self.pointers.len() * core::mem::size_of::<usize>();
+        #[cfg(not(feature = "rayon"))]
+        let builders = [encode(strings)];
 
-        fn human(key: &str, x: usize) {
-            const UOM: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-            let mut y = x as f64;
-            let mut uom_idx = 0;
-            while y > 1000.0 {
-                uom_idx += 1;
-                y /= 1000.0;
-            }
-            println!("{:>20}:{:>10.3}{}{:>20} ", key, y, UOM[uom_idx], x);
+        for builder in builders {
+            self.append(builder);
         }
+    }
 
-        let total_size = ptr_size + self.data.len() + core::mem::size_of::<Self>();
-        human("data_bytes", self.data.len());
-        human("codes_bytes", self.stats.code_bytes);
-        human("suffixes_bytes", self.stats.suffixes_bytes);
-        human("ptrs_bytes", ptr_size);
-        human("uncompressed_size", self.stats.sum_str_len);
-        human("total_size", total_size);
+    /// Appends the strings encoded by another builder with the same block
+    /// size.
+    ///
+    /// The number of strings in this builder must be a multiple of the block
+    /// size, so that the blocks of `other` can be copied as they are.
+    fn append(&mut self, other: Self) {
+        debug_assert_eq!(self.k, other.k);
+        debug_assert_eq!(self.len % self.k, 0);
+        if other.len == 0 {
+            return;
+        }
+        if self.len != 0 {
+            // the previous block is now complete
+            let last_ptr = self.pointers.last().copied().unwrap_or(0);
+            let block_bytes = self.data.len() - last_ptr;
+            self.stats.max_block_bytes = self.stats.max_block_bytes.max(block_bytes);
+            self.stats.sum_block_bytes += block_bytes;
 
-        human(
-            "optimal_size",
-            (self.data.len() as isize - self.stats.redundancy) as usize,
-        );
-        human("redundancy", self.stats.redundancy as usize);
-        let overhead = self.stats.redundancy + ptr_size as isize;
-        println!(
-            "overhead_ratio: {:>10}",
-            overhead as f64 / (overhead + self.data.len() as isize) as f64
-        );
-        println!(
-            "no_overhead_compression_ratio: {:.3}",
-            (self.data.len() as isize - self.stats.redundancy) as f64
-                / self.stats.sum_str_len as f64
-        );
+            // compare the last string with the first string of other
+            let (len, first) = decode_int(&other.data);
+            let (lcp, order) = longest_common_prefix(&self.last_str, &first[..len]);
+            if order == core::cmp::Ordering::Greater {
+                self.is_sorted = false;
+            }
+            self.stats.redundancy += (lcp + encode_int_len(len)) as isize;
+            self.stats.redundancy -= C::encoded_len(self.last_str.len() - lcp, len - lcp) as isize;
+        }
 
-        println!(
-            "compression_ratio: {:.3}",
-            (ptr_size + self.data.len()) as f64 / self.stats.sum_str_len as f64
+        let offset = self.data.len();
+        self.data.extend_from_slice(&other.data);
+        self.pointers
+            .extend(other.pointers.iter().map(|&ptr| ptr + offset));
+        self.len += other.len;
+        self.is_sorted &= other.is_sorted;
+        self.last_str = other.last_str;
+
+        self.stats.max_block_bytes = self.stats.max_block_bytes.max(other.stats.max_block_bytes);
+        self.stats.sum_block_bytes += other.stats.sum_block_bytes;
+        self.stats.max_lcp = self.stats.max_lcp.max(other.stats.max_lcp);
+        self.stats.sum_lcp += other.stats.sum_lcp;
+        self.stats.max_str_len = self.stats.max_str_len.max(other.stats.max_str_len);
+        self.stats.sum_str_len += other.stats.sum_str_len;
+        self.stats.code_bytes += other.stats.code_bytes;
+        self.stats.suffixes_bytes += other.stats.suffixes_bytes;
+        self.stats.vbyte_code_bytes += other.stats.vbyte_code_bytes;
+        self.stats.nibble_code_bytes += other.stats.nibble_code_bytes;
+        self.stats.byte_escape_code_bytes += other.stats.byte_escape_code_bytes;
+        self.stats.redundancy += other.stats.redundancy;
+    }
+
+    /// Prints in a human-readable format the statistics of the
+    /// strings currently in the builder.
+    pub fn print_stats(&self) {
+        self.stats.print(
+            self.len,
+            self.data.len(),
+            self.pointers.len(),
+            core::mem::size_of::<Self>(),
         );
     }
 }
 
 #[inline(always)]
 /// Computes the longest common prefix between two strings as bytes.
-fn longest_common_prefix(a: &[u8], b: &[u8]) -> (usize, core::cmp::Ordering) {
+pub(crate) fn longest_common_prefix(a: &[u8], b: &[u8]) -> (usize, core::cmp::Ordering) {
     let min_len = a.len().min(b.len());
     // normal lcp computation
     let mut i = 0;
@@ -672,7 +1507,7 @@ const UPPER_BOUND_8: usize = 128_usize.pow(8) + UPPER_BOUND_7;
 
 /// VByte encode an integer
 #[inline(always)]
-fn encode_int(mut value: usize, data: &mut Vec<u8>) {
+pub(crate) fn encode_int(mut value: usize, data: &mut Vec<u8>) {
     if value < UPPER_BOUND_1 {
         data.push(value as u8);
         return;
@@ -759,7 +1594,7 @@ fn encode_int(mut value: usize, data: &mut Vec<u8>) {
 }
 
 #[inline(always)]
-fn decode_int(data: &[u8]) -> (usize, &[u8]) {
+pub(crate) fn decode_int(data: &[u8]) -> (usize, &[u8]) {
     let x = data[0];
     if x < 0x80 {
         return (x as usize, &data[1..]);
@@ -838,25 +1673,6 @@ fn decode_int(data: &[u8]) -> (usize, &[u8]) {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_strcmp() {
-        assert_eq!(strcmp(b"abcd", b"abcd\0"), core::cmp::Ordering::Equal);
-        assert_eq!(strcmp(b"abcd", b"abbd\0"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"abcd", b"abdd\0"), core::cmp::Ordering::Less);
-
-        assert_eq!(strcmp(b"a", b"b\0"), core::cmp::Ordering::Less);
-        assert_eq!(strcmp(b"b", b"a\0"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"abc", b"abc\0"), core::cmp::Ordering::Equal);
-        assert_eq!(strcmp(b"abc", b"abc\0abc"), core::cmp::Ordering::Equal);
-        assert_eq!(strcmp(b"abc", b"abc\0ab"), core::cmp::Ordering::Equal);
-        assert_eq!(strcmp(b"abc", b"ab\0"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"abc", b"ab\0abc"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"abc", b"ab\0ab"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"abc", b"ab\0"), core::cmp::Ordering::Greater);
-        assert_eq!(strcmp(b"a", b"ab\0"), core::cmp::Ordering::Less);
-        assert_eq!(strcmp(b"ab", b"ab\0"), core::cmp::Ordering::Equal);
-    }
-
     #[test]
     fn test_encode_decode_int() {
         let values = [
@@ -909,6 +1725,59 @@ mod tests {
         }
     }
 
+    fn check_coder<C: RearLengthCoder>() {
+        let values = [0, 1, 14, 15, 16, 127, 128, 254, 255, 256, 1000, 1 << 20];
+        let mut buffer = Vec::with_capacity(128);
+        for &rear_length in &values {
+            for &suffix_length in &values {
+                C::encode(rear_length, suffix_length, &mut buffer);
+            }
+        }
+        let mut data = &buffer[..];
+        for &rear_length in &values {
+            for &suffix_length in &values {
+                let (r, s, tmp) = C::decode(data);
+                assert_eq!((r, s), (rear_length, suffix_length));
+                assert_eq!(
+                    data.len() - tmp.len(),
+                    C::encoded_len(rear_length, suffix_length)
+                );
+                data = tmp;
+            }
+        }
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_rear_length_coders() {
+        check_coder::<VByteCoder>();
+        check_coder::<NibbleCoder>();
+        check_coder::<ByteEscapeCoder>();
+    }
+
+    #[test]
+    fn test_stats() {
+        let words = [
+            "a",
+            "aa",
+            "aab",
+            "abc",
+            "b",
+            "bbbbbbbbbbbbbbbbbbbb",
+            "bc",
+            "c",
+        ];
+        for k in [1, 2, 3, 8, 16] {
+            let mut builder = RearCodedListBuilder::<str, NibbleCoder>::with_block_size(k);
+            for word in words {
+                builder.push(word);
+            }
+            let stats = builder.stats.clone();
+            let rcl = builder.build();
+            assert_eq!(format!("{:?}", rcl.stats()), format!("{:?}", stats));
+        }
+    }
+
     #[test]
     fn test_longest_common_prefix() {
         let str1 = b"absolutely";
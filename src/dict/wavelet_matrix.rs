@@ -0,0 +1,312 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Wavelet matrices.
+//!
+//! A wavelet matrix represents a sequence of *n* integers of *w* bits using
+//! *w* bit vectors of length *n* with rank and selection support, and makes it
+//! possible to access the elements of the sequence, and to rank and select
+//! symbols, in time proportional to *w*. Moreover, it supports queries on
+//! ranges of positions, such as counting the elements within a given range of
+//! values or finding the *k*-th smallest element.
+//!
+//! Wavelet matrices have been introduced by Francisco Claude, Gonzalo Navarro,
+//! and Alberto Ordóñez in “[The wavelet
+//! matrix](https://doi.org/10.1016/j.is.2014.06.002)”, _Information Systems_,
+//! 47:15–32, 2015.
+
+use crate::prelude::*;
+use epserde::*;
+use mem_dbg::*;
+use std::ops::{Index, Range};
+
+/// The default structure used for the levels of a [`WaveletMatrix`].
+pub type WaveletMatrixLevel = SelectZeroAdaptConst<SelectAdaptConst<Rank9<BitVec<Box<[usize]>>>>>;
+
+/// A wavelet matrix.
+///
+/// The matrix is generic over the structure `B` used for each level, which
+/// must implement [`Rank`], [`RankZero`], [`SelectUnchecked`], and
+/// [`SelectZeroUnchecked`], and give access to its bits using [`Index`]. The
+/// default structure is [`WaveletMatrixLevel`], which combines [`Rank9`] with
+/// [`SelectAdaptConst`] and [`SelectZeroAdaptConst`]; different combinations
+/// can be used by building the matrix with
+/// [`WaveletMatrix::new_with`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::bit_field_vec;
+/// # use sux::dict::WaveletMatrix;
+/// # use sux::traits::IndexedSeq;
+/// let seq = bit_field_vec![3; 5, 2, 7, 2, 0, 5, 1];
+/// let wm = WaveletMatrix::new(&seq);
+///
+/// assert_eq!(wm.get(2), 7);
+/// assert_eq!(wm.rank(2, 4), 2);
+/// assert_eq!(wm.select(5, 1), Some(5));
+/// // Elements with values in [2..6) among positions [1..6)
+/// assert_eq!(wm.range_count(1..6, 2..6), 3);
+/// // The smallest element among positions [1..4)
+/// assert_eq!(wm.range_quantile(1..4, 0), Some(2));
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct WaveletMatrix<B = WaveletMatrixLevel, L = Box<[B]>, Z = Box<[usize]>> {
+    /// The number of elements.
+    len: usize,
+    /// The bit width of the elements, which is also the number of levels.
+    bit_width: usize,
+    /// The levels, from the most significant bit to the least significant.
+    levels: L,
+    /// The number of zeros in each level.
+    zeros: Z,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl WaveletMatrix {
+    /// Creates a new wavelet matrix with default levels containing the same
+    /// elements of the given slice.
+    pub fn new(seq: &impl BitFieldSlice<usize>) -> Self {
+        Self::new_with(seq, |bits| {
+            SelectZeroAdaptConst::new(SelectAdaptConst::new(Rank9::new(bits)))
+        })
+    }
+}
+
+impl<B> WaveletMatrix<B> {
+    /// Creates a new wavelet matrix containing the same elements of the given
+    /// slice, using `build` to turn the bit vector of each level into a
+    /// structure supporting ranking and selection.
+    pub fn new_with(
+        seq: &impl BitFieldSlice<usize>,
+        mut build: impl FnMut(BitVec<Box<[usize]>>) -> B,
+    ) -> Self {
+        let len = seq.len();
+        let bit_width = seq.bit_width();
+        let mut levels = Vec::with_capacity(bit_width);
+        let mut zeros = Vec::with_capacity(bit_width);
+        let mut values = (0..len)
+            .map(|i| unsafe { seq.get_unchecked(i) })
+            .collect::<Vec<_>>();
+        let mut ones = Vec::with_capacity(len);
+
+        for level in 0..bit_width {
+            let shift = bit_width - 1 - level;
+            let mut bits = BitVec::new(len);
+            // Stable partition of the values by the current bit
+            let mut num_zeros = 0;
+            for i in 0..len {
+                let value = values[i];
+                if (value >> shift) & 1 == 0 {
+                    values[num_zeros] = value;
+                    num_zeros += 1;
+                } else {
+                    bits.set(i, true);
+                    ones.push(value);
+                }
+            }
+            values.truncate(num_zeros);
+            values.append(&mut ones);
+            levels.push(build(bits.into()));
+            zeros.push(num_zeros);
+        }
+
+        Self {
+            len,
+            bit_width,
+            levels: levels.into(),
+            zeros: zeros.into(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<B, L, Z> WaveletMatrix<B, L, Z> {
+    /// Returns the number of elements.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the bit width of the elements, which is also the number of
+    /// levels.
+    #[inline(always)]
+    pub fn bit_width(&self) -> usize {
+        self.bit_width
+    }
+}
+
+impl<
+        B: Rank + RankZero + SelectUnchecked + SelectZeroUnchecked + Index<usize, Output = bool>,
+        L: AsRef<[B]>,
+        Z: AsRef<[usize]>,
+    > WaveletMatrix<B, L, Z>
+{
+    /// Maps a position at the given level to the next level, following the
+    /// given bit.
+    #[inline(always)]
+    fn descend(&self, level: usize, pos: usize, bit: bool) -> usize {
+        let bits = unsafe { self.levels.as_ref().get_unchecked(level) };
+        if bit {
+            unsafe { *self.zeros.as_ref().get_unchecked(level) + bits.rank(pos) }
+        } else {
+            bits.rank_zero(pos)
+        }
+    }
+
+    /// Returns the number of occurrences of `symbol` before position `pos`.
+    ///
+    /// If `pos` is greater than the length of the sequence, the number of
+    /// occurrences of `symbol` in the whole sequence is returned.
+    pub fn rank(&self, symbol: usize, pos: usize) -> usize {
+        if self.bit_width < usize::BITS as usize && symbol >> self.bit_width != 0 {
+            return 0;
+        }
+        let mut start = 0;
+        let mut end = pos.min(self.len);
+        for level in 0..self.bit_width {
+            let bit = (symbol >> (self.bit_width - 1 - level)) & 1 != 0;
+            start = self.descend(level, start, bit);
+            end = self.descend(level, end, bit);
+        }
+        end - start
+    }
+
+    /// Returns the position of the occurrence of `symbol` of given rank, or
+    /// `None` if there are not enough occurrences.
+    pub fn select(&self, symbol: usize, rank: usize) -> Option<usize> {
+        if self.bit_width < usize::BITS as usize && symbol >> self.bit_width != 0 {
+            return None;
+        }
+        let mut start = 0;
+        let mut end = self.len;
+        for level in 0..self.bit_width {
+            let bit = (symbol >> (self.bit_width - 1 - level)) & 1 != 0;
+            start = self.descend(level, start, bit);
+            end = self.descend(level, end, bit);
+        }
+        if rank >= end - start {
+            return None;
+        }
+
+        let mut pos = start + rank;
+        for level in (0..self.bit_width).rev() {
+            let bits = unsafe { self.levels.as_ref().get_unchecked(level) };
+            pos = if (symbol >> (self.bit_width - 1 - level)) & 1 != 0 {
+                unsafe { bits.select_unchecked(pos - *self.zeros.as_ref().get_unchecked(level)) }
+            } else {
+                unsafe { bits.select_zero_unchecked(pos) }
+            };
+        }
+        Some(pos)
+    }
+
+    /// Returns the number of elements smaller than `value` among the positions
+    /// in `range`.
+    fn count_less(&self, range: &Range<usize>, value: usize) -> usize {
+        let (mut start, mut end) = (range.start, range.end);
+        if self.bit_width < usize::BITS as usize && value >> self.bit_width != 0 {
+            return end - start;
+        }
+        let mut count = 0;
+        for level in 0..self.bit_width {
+            let bit = (value >> (self.bit_width - 1 - level)) & 1 != 0;
+            if bit {
+                let bits = unsafe { self.levels.as_ref().get_unchecked(level) };
+                count += bits.rank_zero(end) - bits.rank_zero(start);
+            }
+            start = self.descend(level, start, bit);
+            end = self.descend(level, end, bit);
+        }
+        count
+    }
+
+    /// Returns the number of elements with value in `values` among the
+    /// positions in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is not contained in the positions of the sequence.
+    pub fn range_count(&self, range: Range<usize>, values: Range<usize>) -> usize {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Range out of bounds: {:?} for length {}",
+            range,
+            self.len
+        );
+        if values.start >= values.end {
+            return 0;
+        }
+        self.count_less(&range, values.end) - self.count_less(&range, values.start)
+    }
+
+    /// Returns the element of given rank (starting from zero) in increasing
+    /// order among the positions in `range`, or `None` if the rank is greater
+    /// than or equal to the length of the range.
+    ///
+    /// For example, a rank equal to zero yields the minimum, and a rank equal
+    /// to half the length of the range yields the median.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is not contained in the positions of the sequence.
+    pub fn range_quantile(&self, range: Range<usize>, mut rank: usize) -> Option<usize> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Range out of bounds: {:?} for length {}",
+            range,
+            self.len
+        );
+        if rank >= range.end - range.start {
+            return None;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        let mut value = 0;
+        for level in 0..self.bit_width {
+            let bits = unsafe { self.levels.as_ref().get_unchecked(level) };
+            let zeros = bits.rank_zero(end) - bits.rank_zero(start);
+            let bit = rank >= zeros;
+            if bit {
+                rank -= zeros;
+            }
+            value = (value << 1) | bit as usize;
+            start = self.descend(level, start, bit);
+            end = self.descend(level, end, bit);
+        }
+        Some(value)
+    }
+}
+
+impl<B, L, Z> Types for WaveletMatrix<B, L, Z> {
+    type Input = usize;
+    type Output = usize;
+}
+
+impl<
+        B: Rank + RankZero + SelectUnchecked + SelectZeroUnchecked + Index<usize, Output = bool>,
+        L: AsRef<[B]>,
+        Z: AsRef<[usize]>,
+    > IndexedSeq for WaveletMatrix<B, L, Z>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    unsafe fn get_unchecked(&self, mut index: usize) -> usize {
+        let mut value = 0;
+        for level in 0..self.bit_width {
+            let bit = self.levels.as_ref().get_unchecked(level)[index];
+            value = (value << 1) | bit as usize;
+            index = self.descend(level, index, bit);
+        }
+        value
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Fuse 3-hypergraphs and their peeling.
+//!
+//! A *fuse graph* is a 3-hypergraph whose vertices are divided into `l` + 2
+//! segments of equal size: each edge chooses a segment `s` in [0..`l`) and
+//! then a random vertex in segments `s`, `s` + 1, and `s` + 2. Fuse graphs are
+//! peelable with high probability even when the number of vertices is just
+//! slightly larger than the number of edges. They have been introduced by
+//! Martin Dietzfelbinger and Stefan Walzer in “[Dense Peelable Random Uniform
+//! Hypergraphs](https://doi.org/10.4230/LIPIcs.ESA.2019.38)”, _27th Annual
+//! European Symposium on Algorithms (ESA 2019)_, volume 144 of LIPIcs, pages
+//! 38:1−38:16, 2019.
+//!
+//! The sizing heuristics are those used by Thomas Mueller Graf and Daniel
+//! Lemire in “[Binary Fuse Filters: Fast and Smaller Than Xor
+//! Filters](https://doi.org/10.1145/3510449)”, _ACM J. Exp. Algorithmics_,
+//! 27:1−15, 2022.
+
+/// The maximum base-2 logarithm of the segment size.
+const MAX_LOG2_SEG_SIZE: u32 = 18;
+
+/// Returns the base-2 logarithm of the segment size and the number of
+/// segments minus two (i.e., the number of possible first segments of an
+/// edge) for a fuse graph with `n` edges.
+pub(crate) fn fuse_params(n: usize) -> (u32, usize) {
+    let n = n.max(2) as f64;
+    let log2_seg_size = ((n.ln() / 3.33_f64.ln() + 2.25).floor() as u32).min(MAX_LOG2_SEG_SIZE);
+    let size_factor = f64::max(1.125, 0.875 + 0.25 * 1E6_f64.ln() / n.ln());
+    let num_vertices = (n * size_factor).ceil() as usize;
+    let l = num_vertices
+        .div_ceil(1 << log2_seg_size)
+        .saturating_sub(2)
+        .max(1);
+    (log2_seg_size, l)
+}
+
+/// Returns the three vertices of the edge associated with a signature in a
+/// fuse graph with the given parameters.
+///
+/// The highest bits of `sig[0]` are not used, as they are used to select
+/// chunks.
+#[inline(always)]
+pub(crate) fn edge(sig: &[u64; 2], log2_seg_size: u32, l: usize) -> [usize; 3] {
+    let first_segment = ((sig[0] & 0xFFFF_FFFF) * l as u64) >> 32;
+    let start = (first_segment << log2_seg_size) as usize;
+    let seg_size = 1_usize << log2_seg_size;
+    let seg_mask = seg_size as u64 - 1;
+    [
+        start + (sig[1] & seg_mask) as usize,
+        start + seg_size + ((sig[1] >> 21) & seg_mask) as usize,
+        start + 2 * seg_size + ((sig[1] >> 42) & seg_mask) as usize,
+    ]
+}
+
+/// Peels a 3-hypergraph with `num_vertices` vertices and the given edges.
+///
+/// If the hypergraph is peelable, returns the peeling order as a vector of
+/// pairs given by an edge index and the vertex (the *hinge*) that was of
+/// degree one when the edge was peeled. Otherwise, returns `None`.
+///
+/// Processing the edges in reverse peeling order, each edge is the only one
+/// among the remaining ones containing its hinge.
+pub(crate) fn peel(num_vertices: usize, edges: &[[usize; 3]]) -> Option<Vec<(usize, usize)>> {
+    let mut degree = vec![0_u32; num_vertices];
+    let mut xor_edges = vec![0_usize; num_vertices];
+    for (i, edge) in edges.iter().enumerate() {
+        for &v in edge {
+            degree[v] += 1;
+            xor_edges[v] ^= i;
+        }
+    }
+
+    let mut stack = Vec::with_capacity(edges.len());
+    let mut to_visit = Vec::new();
+    for v in 0..num_vertices {
+        if degree[v] != 1 {
+            continue;
+        }
+        to_visit.push(v);
+        while let Some(v) = to_visit.pop() {
+            // The degree might have been decreased in the meantime
+            if degree[v] != 1 {
+                continue;
+            }
+            let e = xor_edges[v];
+            stack.push((e, v));
+            for &w in &edges[e] {
+                degree[w] -= 1;
+                xor_edges[w] ^= e;
+                if degree[w] == 1 {
+                    to_visit.push(w);
+                }
+            }
+        }
+    }
+
+    if stack.len() == edges.len() {
+        Some(stack)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_peel() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for n in [0, 1, 2, 10, 100, 1000, 100_000] {
+            let (log2_seg_size, l) = fuse_params(n);
+            let num_vertices = (l + 2) << log2_seg_size;
+            assert!(num_vertices >= n);
+            let edges = (0..n)
+                .map(|_| edge(&[rng.next_u64(), rng.next_u64()], log2_seg_size, l))
+                .collect::<Vec<_>>();
+            for e in &edges {
+                assert!(e.iter().all(|&v| v < num_vertices));
+            }
+            // Fuse graphs of this size peel with very high probability
+            let order = peel(num_vertices, &edges).unwrap();
+            assert_eq!(order.len(), n);
+        }
+    }
+}
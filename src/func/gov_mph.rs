@@ -0,0 +1,355 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Minimal perfect hash functions.
+
+use super::fuse::{edge, fuse_params, peel};
+use crate::bits::BitFieldVec;
+use crate::traits::bit_field_slice::*;
+use crate::utils::*;
+use anyhow::{bail, Result};
+use dsi_progress_logger::ProgressLog;
+use epserde::prelude::*;
+use mem_dbg::*;
+use rand::prelude::*;
+use std::marker::PhantomData;
+
+/// The base-2 logarithm of the number of buckets of the [`SigStore`] used
+/// during construction.
+const LOG2_BUCKETS: u32 = 8;
+/// The maximum number of high bits used to define chunks.
+const MAX_CHUNK_HIGH_BITS: u32 = 12;
+/// The base-2 logarithm of the target size of a chunk.
+const LOG2_TARGET_CHUNK_SIZE: u32 = 16;
+/// The number of words of 2-bit values between two rank counters.
+const WORDS_PER_COUNT: usize = 8;
+/// A mask selecting the lower bit of each 2-bit value in a word.
+const LOW_BITS: usize = 0x5555_5555_5555_5555;
+
+/// Remixes a signature with a chunk seed.
+///
+/// A seed equal to zero leaves the signature unchanged.
+#[inline(always)]
+fn remix(sig: [u64; 2], seed: u8) -> [u64; 2] {
+    if seed == 0 {
+        sig
+    } else {
+        let h = spooky_short_rehash(&[sig[0], sig[1], 0, 0], seed as u64);
+        [h[0], h[1]]
+    }
+}
+
+/// Returns the number of 2-bit values different from 3, which marks vertices
+/// that are not the hinge of any edge, among the lowest `n` values in `word`.
+#[inline(always)]
+fn count_used(word: usize, n: usize) -> usize {
+    let mask = if n == usize::BITS as usize / 2 {
+        usize::MAX
+    } else {
+        (1 << (2 * n)) - 1
+    };
+    n - (word & (word >> 1) & LOW_BITS & mask).count_ones() as usize
+}
+
+/// A minimal perfect hash function.
+///
+/// A minimal perfect hash function maps a set of *n* keys bijectively onto the
+/// interval [0..*n*). The keys are not stored: querying a key outside of the
+/// set will return an arbitrary value.
+///
+/// This implementation follows the approach of Fabiano C. Botelho, Rasmus
+/// Pagh, and Nivio Ziviani in “[Simple and Space-Efficient Minimal Perfect Hash
+/// Functions](https://doi.org/10.1007/978-3-540-73951-7_13)”, _Algorithms and
+/// Data Structures, 10th International Workshop, WADS 2007_, volume 4619 of
+/// Lecture Notes in Computer Science, pages 139–150, Springer, 2007, with the
+/// improvements described by Marco Genuzio, Giuseppe Ottaviano, and Sebastiano
+/// Vigna in “[Fast Scalable Construction of (Minimal Perfect Hash)
+/// Functions](https://doi.org/10.1007/978-3-319-38851-9_23)”, _Experimental
+/// Algorithms, 15th International Symposium, SEA 2016_, volume 9685 of Lecture
+/// Notes in Computer Science, pages 339–352, Springer, 2016.
+///
+/// Keys are hashed to signatures, which are grouped in chunks by their high
+/// bits using a [`SigStore`]. The signatures of each chunk define a fuse
+/// 3-hypergraph that is peeled to assign to each vertex a 2-bit value, so that
+/// the sum modulo 3 of the values of the vertices of an edge identifies a
+/// vertex that is the hinge of that edge, and of that edge only. The output is
+/// then the number of hinges preceding the vertex, which is computed using a
+/// lightweight ranking structure. If the hypergraph of a chunk is not
+/// peelable, the signatures of the chunk are remixed with a different 8-bit
+/// seed. The space used is ≈2.5 bits per key.
+///
+/// Instances are built using a [`GovMphBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::func::GovMphBuilder;
+/// # use sux::utils::LineLender;
+/// # use dsi_progress_logger::ProgressLogger;
+/// let mph = GovMphBuilder::<str>::default().build(
+///     LineLender::from_path("tests/data/wordlist.100")?,
+///     &mut Option::<ProgressLogger>::None,
+/// )?;
+///
+/// let mut seen = vec![false; mph.len()];
+/// for word in std::fs::read_to_string("tests/data/wordlist.100")?.lines() {
+///     let h = mph.get(word);
+///     assert!(!seen[h]);
+///     seen[h] = true;
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Epserde, Debug, MemDbg, MemSize)]
+pub struct GovMph<K: ?Sized + ToSig, D: AsRef<[usize]> = Box<[usize]>, S: AsRef<[u8]> = Box<[u8]>> {
+    /// The seed used to compute signatures.
+    seed: u64,
+    /// The number of keys.
+    num_keys: usize,
+    /// The number of high bits defining a chunk.
+    chunk_high_bits: u32,
+    /// A mask for the lowest `chunk_high_bits` bits.
+    chunk_mask: u64,
+    /// The base-2 logarithm of the size of a segment.
+    log2_seg_size: u32,
+    /// The number of segments of a chunk minus two.
+    l: usize,
+    /// The seeds used to remix the signatures of each chunk.
+    chunk_seeds: S,
+    /// The 2-bit values associated with the vertices.
+    values: D,
+    /// The number of hinges before each block of [`WORDS_PER_COUNT`] words of
+    /// `values`.
+    counts: D,
+    _marker: PhantomData<K>,
+}
+
+impl<K: ?Sized + ToSig, D: AsRef<[usize]>, S: AsRef<[u8]>> GovMph<K, D, S> {
+    #[inline(always)]
+    fn value(&self, vertex: usize) -> usize {
+        let word = unsafe { *self.values.as_ref().get_unchecked(vertex / 32) };
+        (word >> (2 * (vertex % 32))) & 3
+    }
+
+    /// Returns the number of hinges preceding the given vertex.
+    #[inline(always)]
+    fn rank(&self, vertex: usize) -> usize {
+        let values = self.values.as_ref();
+        let word = vertex / 32;
+        let block = word / WORDS_PER_COUNT;
+        let mut rank = unsafe { *self.counts.as_ref().get_unchecked(block) };
+        for w in block * WORDS_PER_COUNT..word {
+            rank += count_used(unsafe { *values.get_unchecked(w) }, 32);
+        }
+        rank + count_used(unsafe { *values.get_unchecked(word) }, vertex % 32)
+    }
+
+    /// Returns the value associated with the given signature.
+    ///
+    /// This method is mainly useful in the construction of compound functions.
+    #[inline(always)]
+    pub fn get_by_sig(&self, sig: &[u64; 2]) -> usize {
+        let chunk = (sig[0].rotate_left(self.chunk_high_bits) & self.chunk_mask) as usize;
+        let chunk_offset = chunk * ((self.l + 2) << self.log2_seg_size);
+        let chunk_seed = unsafe { *self.chunk_seeds.as_ref().get_unchecked(chunk) };
+        let vertices =
+            edge(&remix(*sig, chunk_seed), self.log2_seg_size, self.l).map(|v| v + chunk_offset);
+        let hinge = vertices.iter().map(|&v| self.value(v)).sum::<usize>() % 3;
+        self.rank(vertices[hinge])
+    }
+
+    /// Returns the value associated with the given key, or an arbitrary value
+    /// if the key was not in the original set.
+    #[inline]
+    pub fn get(&self, key: &K) -> usize {
+        self.get_by_sig(&K::to_sig(key, self.seed))
+    }
+
+    /// Returns the number of keys in the function.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.num_keys
+    }
+
+    /// Returns whether the function has no keys.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.num_keys == 0
+    }
+}
+
+/// A builder for [`GovMph`].
+///
+/// Keys are provided by a [rewindable I/O lender](RewindableIoLender), which
+/// is read once and then rewound and read again only in the unlikely case in
+/// which some chunk cannot be peeled with any chunk seed.
+///
+/// Keys must be distinct: duplicate keys will make the construction fail.
+#[derive(Debug, Clone)]
+pub struct GovMphBuilder<K: ?Sized> {
+    seed: u64,
+    _marker: PhantomData<Box<K>>,
+}
+
+impl<K: ?Sized> Default for GovMphBuilder<K> {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: ?Sized + ToSig> GovMphBuilder<K> {
+    /// Sets the initial seed used to compute signatures.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builds a [`GovMph`] on the keys returned by `keys`.
+    pub fn build(
+        self,
+        mut keys: impl RewindableIoLender<K>,
+        pl: &mut impl ProgressLog,
+    ) -> Result<GovMph<K>> {
+        let mut rng = SmallRng::seed_from_u64(self.seed);
+        let mut seed = self.seed;
+        loop {
+            pl.item_name("key");
+            pl.start("Reading input...");
+            let mut sig_store = SigStore::<()>::new(LOG2_BUCKETS, MAX_CHUNK_HIGH_BITS)?;
+            while let Some(key) = keys.next() {
+                sig_store.push(&SigVal {
+                    sig: K::to_sig(key?, seed),
+                    val: (),
+                })?;
+                pl.light_update();
+            }
+            pl.done();
+
+            if let Some(mph) = Self::try_build(seed, sig_store, pl)? {
+                return Ok(mph);
+            }
+
+            seed = rng.next_u64();
+            pl.info(format_args!("Peeling failed, retrying with seed {}", seed));
+            keys = keys.rewind()?;
+        }
+    }
+
+    /// Tries to build a [`GovMph`] from the given signatures, returning `None`
+    /// if some chunk cannot be peeled.
+    fn try_build(
+        seed: u64,
+        sig_store: SigStore<()>,
+        pl: &mut impl ProgressLog,
+    ) -> Result<Option<GovMph<K>>> {
+        let num_keys = sig_store.len();
+        let chunk_high_bits = (num_keys.max(1).ilog2())
+            .saturating_sub(LOG2_TARGET_CHUNK_SIZE)
+            .min(MAX_CHUNK_HIGH_BITS);
+        let mut chunk_store = sig_store.into_chunk_store(chunk_high_bits)?;
+        let max_chunk_size = chunk_store.chunk_sizes().iter().copied().max().unwrap_or(0);
+        let (log2_seg_size, l) = fuse_params(max_chunk_size);
+        let num_vertices = (l + 2) << log2_seg_size;
+        let total_vertices = num_vertices << chunk_high_bits;
+        // All vertices are initially unused
+        let mut data = unsafe {
+            BitFieldVec::<usize>::from_raw_parts(
+                vec![usize::MAX; total_vertices.div_ceil(32)],
+                2,
+                total_vertices,
+            )
+        };
+        let mut chunk_seeds = vec![0_u8; 1 << chunk_high_bits];
+
+        pl.item_name("chunk");
+        pl.expected_updates(Some(1 << chunk_high_bits));
+        pl.start("Peeling chunks...");
+        for (chunk_index, mut chunk) in chunk_store.iter()? {
+            // Duplicate keys would make peeling fail forever
+            let chunk = chunk.to_mut();
+            chunk.sort_unstable_by_key(|sig_val| sig_val.sig);
+            if chunk.windows(2).any(|w| w[0].sig == w[1].sig) {
+                bail!("Duplicate key");
+            }
+
+            let mut chunk_seed = 0_u8;
+            let (edges, order) = loop {
+                let edges = chunk
+                    .iter()
+                    .map(|sig_val| edge(&remix(sig_val.sig, chunk_seed), log2_seg_size, l))
+                    .collect::<Vec<_>>();
+                if let Some(order) = peel(num_vertices, &edges) {
+                    break (edges, order);
+                }
+                if chunk_seed == u8::MAX {
+                    pl.done();
+                    return Ok(None);
+                }
+                chunk_seed += 1;
+            };
+            chunk_seeds[chunk_index] = chunk_seed;
+
+            let chunk_offset = chunk_index * num_vertices;
+            for &(e, hinge) in order.iter().rev() {
+                // Unused vertices have value 3, which is 0 modulo 3
+                let mut pos = 0;
+                let mut sum = 0;
+                for (i, &v) in edges[e].iter().enumerate() {
+                    if v == hinge {
+                        pos = i;
+                    } else {
+                        sum += data.get(chunk_offset + v);
+                    }
+                }
+                data.set(chunk_offset + hinge, (pos + 6 - sum % 3) % 3);
+            }
+            pl.update();
+        }
+        pl.done();
+
+        let values = data.into_raw_parts().0;
+        let mut counts = Vec::with_capacity(values.len() / WORDS_PER_COUNT + 1);
+        let mut rank = 0;
+        for block in values.chunks(WORDS_PER_COUNT) {
+            counts.push(rank);
+            rank += block.iter().map(|&w| count_used(w, 32)).sum::<usize>();
+        }
+        counts.push(rank);
+        // Padding vertices in the last word are unused
+        debug_assert_eq!(rank, num_keys);
+
+        Ok(Some(GovMph {
+            seed,
+            num_keys,
+            chunk_high_bits,
+            chunk_mask: (1 << chunk_high_bits) - 1,
+            log2_seg_size,
+            l,
+            chunk_seeds: chunk_seeds.into(),
+            values: values.into(),
+            counts: counts.into(),
+            _marker: PhantomData,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_used() {
+        assert_eq!(count_used(usize::MAX, 32), 0);
+        assert_eq!(count_used(0, 32), 32);
+        assert_eq!(count_used(0, 5), 5);
+        assert_eq!(count_used(0b11_10_01_00, 4), 3);
+        assert_eq!(count_used(0b11_10_01_00, 3), 3);
+        assert_eq!(count_used(0b11_10_01_11, 1), 0);
+    }
+}
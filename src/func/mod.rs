@@ -0,0 +1,22 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Static functions and minimal perfect hash functions.
+//!
+//! Static functions map a given set of keys to values, but they do not store
+//! the keys: on keys outside of the set they return arbitrary values. They are
+//! built by hashing the keys to [signatures](crate::utils::sig_store) and
+//! peeling random hypergraphs. Minimal perfect hash functions map a given set
+//! of *n* keys bijectively onto [0..*n*).
+
+mod fuse;
+
+pub mod vfunc;
+pub use vfunc::*;
+
+pub mod gov_mph;
+pub use gov_mph::*;
@@ -0,0 +1,255 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Static functions.
+
+use super::fuse::{edge, fuse_params, peel};
+use crate::bits::BitFieldVec;
+use crate::traits::bit_field_slice::*;
+use crate::utils::*;
+use anyhow::{bail, Result};
+use dsi_progress_logger::ProgressLog;
+use epserde::prelude::*;
+use mem_dbg::*;
+use rand::prelude::*;
+use std::marker::PhantomData;
+
+/// The base-2 logarithm of the number of buckets of the [`SigStore`] used
+/// during construction.
+const LOG2_BUCKETS: u32 = 8;
+/// The maximum number of high bits used to define chunks.
+const MAX_CHUNK_HIGH_BITS: u32 = 12;
+/// The base-2 logarithm of the target size of a chunk.
+const LOG2_TARGET_CHUNK_SIZE: u32 = 16;
+
+/// A static function mapping a set of keys to values.
+///
+/// A [`VFunc`] stores a function from a given set of keys implementing
+/// [`ToSig`] to values of a type `T` implementing [`Word`]. The keys are not
+/// stored: querying a key outside of the set will return an arbitrary value.
+/// The space used is ≈1.125*b* bits per key for large key sets, where *b* is
+/// the bit width of the largest value.
+///
+/// Keys are hashed to signatures, which are grouped in chunks by their high
+/// bits using a [`SigStore`]. The signatures of each chunk define a fuse
+/// 3-hypergraph on a separate set of vertices; the value associated with a
+/// key is the exclusive or of the values stored at the three vertices of its
+/// edge, and values are assigned to vertices by peeling the hypergraph.
+///
+/// Instances are built using a [`VFuncBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::func::VFuncBuilder;
+/// # use sux::utils::FromIntoIterator;
+/// # use dsi_progress_logger::ProgressLogger;
+/// let func = VFuncBuilder::<usize, usize>::default().build(
+///     FromIntoIterator::from(0..100),
+///     FromIntoIterator::from((0..100).map(|x| x * 3)),
+///     &mut Option::<ProgressLogger>::None,
+/// )?;
+///
+/// for i in 0..100 {
+///     assert_eq!(func.get(&i), i * 3);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Epserde, Debug, MemDbg, MemSize)]
+pub struct VFunc<
+    T: Word + ZeroCopy + SerializeInner + DeserializeInner,
+    K: ?Sized + ToSig,
+    D: BitFieldSlice<T> = BitFieldVec<T, Box<[T]>>,
+> {
+    /// The seed used to compute signatures.
+    seed: u64,
+    /// The number of keys.
+    num_keys: usize,
+    /// The number of high bits defining a chunk.
+    chunk_high_bits: u32,
+    /// A mask for the lowest `chunk_high_bits` bits.
+    chunk_mask: u64,
+    /// The base-2 logarithm of the size of a segment.
+    log2_seg_size: u32,
+    /// The number of segments of a chunk minus two.
+    l: usize,
+    /// The values associated with the vertices.
+    values: D,
+    _marker_t: PhantomData<T>,
+    _marker_k: PhantomData<K>,
+}
+
+impl<
+        T: Word + ZeroCopy + SerializeInner + DeserializeInner,
+        K: ?Sized + ToSig,
+        D: BitFieldSlice<T>,
+    > VFunc<T, K, D>
+{
+    /// Returns the value associated with the given signature.
+    ///
+    /// This method is mainly useful in the construction of compound functions.
+    #[inline(always)]
+    pub fn get_by_sig(&self, sig: &[u64; 2]) -> T {
+        let chunk = (sig[0].rotate_left(self.chunk_high_bits) & self.chunk_mask) as usize;
+        let chunk_offset = chunk * ((self.l + 2) << self.log2_seg_size);
+        let [v0, v1, v2] = edge(sig, self.log2_seg_size, self.l);
+        unsafe {
+            self.values.get_unchecked(chunk_offset + v0)
+                ^ self.values.get_unchecked(chunk_offset + v1)
+                ^ self.values.get_unchecked(chunk_offset + v2)
+        }
+    }
+
+    /// Returns the value associated with the given key, or an arbitrary value
+    /// if the key was not in the original set.
+    #[inline]
+    pub fn get(&self, key: &K) -> T {
+        self.get_by_sig(&K::to_sig(key, self.seed))
+    }
+
+    /// Returns the number of keys in the function.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.num_keys
+    }
+
+    /// Returns whether the function has no keys.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.num_keys == 0
+    }
+}
+
+/// A builder for [`VFunc`].
+///
+/// Keys and values are provided by [rewindable I/O
+/// lenders](RewindableIoLender), which will be rewound and read again if the
+/// construction fails with the current seed. Values are provided in the same
+/// order as the keys they are associated with.
+///
+/// Keys must be distinct: duplicate keys will make the construction fail.
+#[derive(Debug, Clone)]
+pub struct VFuncBuilder<T, K: ?Sized> {
+    seed: u64,
+    _marker: PhantomData<(T, Box<K>)>,
+}
+
+impl<T, K: ?Sized> Default for VFuncBuilder<T, K> {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Word + ZeroCopy + SerializeInner + DeserializeInner + Send + Sync, K: ?Sized + ToSig>
+    VFuncBuilder<T, K>
+{
+    /// Sets the initial seed used to compute signatures.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builds a [`VFunc`] mapping the keys returned by `keys` to the values
+    /// returned by `values`.
+    pub fn build(
+        self,
+        mut keys: impl RewindableIoLender<K>,
+        mut values: impl RewindableIoLender<T>,
+        pl: &mut impl ProgressLog,
+    ) -> Result<VFunc<T, K>> {
+        let mut rng = SmallRng::seed_from_u64(self.seed);
+        let mut seed = self.seed;
+        loop {
+            pl.item_name("key");
+            pl.start("Reading input...");
+            let mut sig_store = SigStore::<T>::new(LOG2_BUCKETS, MAX_CHUNK_HIGH_BITS)?;
+            let mut max_value = T::ZERO;
+            while let Some(key) = keys.next() {
+                let key = key?;
+                let Some(value) = values.next() else {
+                    bail!("There are fewer values than keys");
+                };
+                let value = *value?;
+                max_value = Ord::max(max_value, value);
+                sig_store.push(&SigVal {
+                    sig: K::to_sig(key, seed),
+                    val: value,
+                })?;
+                pl.light_update();
+            }
+            pl.done();
+
+            let num_keys = sig_store.len();
+            let chunk_high_bits = (num_keys.max(1).ilog2())
+                .saturating_sub(LOG2_TARGET_CHUNK_SIZE)
+                .min(MAX_CHUNK_HIGH_BITS);
+            let mut chunk_store = sig_store.into_chunk_store(chunk_high_bits)?;
+            let max_chunk_size = chunk_store.chunk_sizes().iter().copied().max().unwrap_or(0);
+            let (log2_seg_size, l) = fuse_params(max_chunk_size);
+            let num_vertices = (l + 2) << log2_seg_size;
+            let bit_width = T::BITS - max_value.leading_zeros() as usize;
+            let mut data = BitFieldVec::<T>::new(bit_width, num_vertices << chunk_high_bits);
+
+            pl.item_name("chunk");
+            pl.expected_updates(Some(1 << chunk_high_bits));
+            pl.start("Peeling chunks...");
+            let mut peeled = true;
+            for (chunk_index, mut chunk) in chunk_store.iter()? {
+                // Duplicate keys would make peeling fail forever
+                let chunk = chunk.to_mut();
+                chunk.sort_unstable_by_key(|sig_val| sig_val.sig);
+                if chunk.windows(2).any(|w| w[0].sig == w[1].sig) {
+                    bail!("Duplicate key");
+                }
+
+                let edges = chunk
+                    .iter()
+                    .map(|sig_val| edge(&sig_val.sig, log2_seg_size, l))
+                    .collect::<Vec<_>>();
+                let Some(order) = peel(num_vertices, &edges) else {
+                    peeled = false;
+                    break;
+                };
+
+                let chunk_offset = chunk_index * num_vertices;
+                for &(e, hinge) in order.iter().rev() {
+                    // The value at the hinge is still zero
+                    let value = edges[e]
+                        .iter()
+                        .fold(chunk[e].val, |x, &v| x ^ data.get(chunk_offset + v));
+                    if value != T::ZERO {
+                        data.set(chunk_offset + hinge, value);
+                    }
+                }
+                pl.update();
+            }
+            pl.done();
+
+            if peeled {
+                return Ok(VFunc {
+                    seed,
+                    num_keys,
+                    chunk_high_bits,
+                    chunk_mask: (1 << chunk_high_bits) - 1,
+                    log2_seg_size,
+                    l,
+                    values: data.into(),
+                    _marker_t: PhantomData,
+                    _marker_k: PhantomData,
+                });
+            }
+
+            seed = rng.next_u64();
+            pl.info(format_args!("Peeling failed, retrying with seed {}", seed));
+            keys = keys.rewind()?;
+            values = values.rewind()?;
+        }
+    }
+}
@@ -15,8 +15,10 @@ compile_error!("`target_pointer_width` must be 64");
 
 pub mod bits;
 pub mod dict;
+pub mod func;
 pub mod rank_sel;
 pub mod traits;
+pub mod tree;
 pub mod utils;
 
 #[cfg(feature = "fuzz")]
@@ -27,10 +29,12 @@ pub mod prelude {
     pub use crate::bit_vec;
     pub use crate::bits::*;
     pub use crate::dict::*;
+    pub use crate::func::*;
     pub use crate::rank_sel::*;
     pub use crate::rank_small;
     pub use crate::traits::bit_field_slice;
     pub use crate::traits::*;
+    pub use crate::tree::*;
     pub use crate::DivCeilUnchecked;
 }
 
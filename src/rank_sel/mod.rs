@@ -121,3 +121,6 @@ pub use select9::*;
 
 mod simple_select;
 pub use simple_select::*;
+
+mod rrr;
+pub use rrr::*;
@@ -13,6 +13,7 @@ use mem_dbg::*;
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_RankHinted;
 use crate::traits::rank_sel::ambassador_impl_Select;
@@ -70,6 +71,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
 #[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
@@ -128,7 +130,7 @@ impl<B, C> Rank9<B, C> {
     /// new backend is identical to the old one as a bit vector.
     pub unsafe fn map<B1>(self, f: impl FnOnce(B) -> B1) -> Rank9<B1, C>
     where
-        B1: AsRef<[usize]> + BitLength,
+        B1: AsWords + BitLength,
     {
         Rank9 {
             bits: f(self.bits),
@@ -149,7 +151,7 @@ impl<B: BitLength, C> Rank9<B, C> {
     }
 }
 
-impl<B: AsRef<[usize]> + BitLength> Rank9<B, Box<[BlockCounters]>> {
+impl<B: AsWords + BitLength> Rank9<B, Box<[BlockCounters]>> {
     /// Creates a new Rank9 structure from a given bit vector.
     pub fn new(bits: B) -> Self {
         let num_bits = bits.len();
@@ -166,13 +168,13 @@ impl<B: AsRef<[usize]> + BitLength> Rank9<B, Box<[BlockCounters]>> {
                 absolute: num_ones,
                 relative: 0,
             };
-            num_ones += bits.as_ref()[i].count_ones() as usize;
+            num_ones += unsafe { bits.usize_word_unchecked(i) }.count_ones() as usize;
 
             for j in 1..8 {
                 let rel_count = num_ones - count.absolute;
                 count.set_rel(j, rel_count);
                 if i + j < num_words {
-                    num_ones += bits.as_ref()[i + j].count_ones() as usize;
+                    num_ones += unsafe { bits.usize_word_unchecked(i + j) }.count_ones() as usize;
                 }
             }
 
@@ -206,7 +208,7 @@ impl<B: BitLength, C: AsRef<[BlockCounters]>> BitCount for Rank9<B, C> {
     }
 }
 
-impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> RankUnchecked for Rank9<B, C> {
+impl<B: AsWords + BitLength, C: AsRef<[BlockCounters]>> RankUnchecked for Rank9<B, C> {
     /// # Safety
     ///
     /// The implementation of [`RankUnchecked`] for [`Rank9`] has an weakened
@@ -244,15 +246,15 @@ impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> RankUnchecked for
         // When pos is equal to the length of the underlying bit vector and
         // there is at least one unused bit, this access is safe as there
         // is a word of index word_pos.
-        let word = self.bits.as_ref().get_unchecked(word_pos);
+        let word = self.bits.usize_word_unchecked(word_pos);
         let counts = self.counts.as_ref().get_unchecked(block);
 
         counts.absolute + counts.rel(offset) + (word & ((1 << bit_pos) - 1)).count_ones() as usize
     }
 }
 
-impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> Rank for Rank9<B, C> {}
-impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> RankZero for Rank9<B, C> {}
+impl<B: AsWords + BitLength, C: AsRef<[BlockCounters]>> Rank for Rank9<B, C> {}
+impl<B: AsWords + BitLength, C: AsRef<[BlockCounters]>> RankZero for Rank9<B, C> {}
 
 #[cfg(test)]
 mod test {
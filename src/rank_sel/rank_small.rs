@@ -13,11 +13,12 @@ use std::ptr::{addr_of, read_unaligned, write_unaligned};
 
 use crate::{
     prelude::{BitLength, BitVec, Rank, RankHinted, RankUnchecked, RankZero},
-    traits::{BitCount, NumBits},
+    traits::{AsWords, BitCount, NumBits},
 };
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_RankHinted;
 use crate::traits::rank_sel::ambassador_impl_Select;
@@ -100,6 +101,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
 #[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
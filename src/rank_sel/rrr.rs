@@ -0,0 +1,407 @@
+/*
+ *
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::prelude::*;
+use common_traits::SelectInWord;
+use epserde::*;
+use mem_dbg::*;
+use std::ops::Index;
+
+/// The number of bits in a block.
+const BLOCK_BITS: usize = 63;
+/// The number of bits used to store the class of a block.
+const CLASS_BITS: usize = 6;
+/// The number of blocks between two samples.
+const BLOCKS_PER_SAMPLE: usize = 32;
+
+/// Binomial coefficients: `BINOMIAL[n][k]` is *n* choose *k*, for *n* and *k*
+/// in [0..64).
+static BINOMIAL: [[usize; 64]; 64] = {
+    let mut binomial = [[0; 64]; 64];
+    let mut n = 0;
+    while n < 64 {
+        binomial[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            binomial[n][k] = binomial[n - 1][k - 1] + binomial[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    binomial
+};
+
+/// The number of bits of the offset of a block of each class.
+static OFFSET_BITS: [usize; BLOCK_BITS + 1] = {
+    let mut offset_bits = [0; BLOCK_BITS + 1];
+    let mut class = 0;
+    while class <= BLOCK_BITS {
+        let num_blocks = BINOMIAL[BLOCK_BITS][class];
+        offset_bits[class] = usize::BITS as usize - (num_blocks - 1).leading_zeros() as usize;
+        class += 1;
+    }
+    offset_bits
+};
+
+/// Returns the index of a block of given class in the colexicographical
+/// enumeration of the blocks of the same class.
+fn encode(mut block: usize, class: usize) -> usize {
+    let mut offset = 0;
+    let mut k = class;
+    while block != 0 {
+        let pos = usize::BITS as usize - 1 - block.leading_zeros() as usize;
+        offset += BINOMIAL[pos][k];
+        k -= 1;
+        block ^= 1 << pos;
+    }
+    offset
+}
+
+/// Returns the block of given class with given offset, inverting [`encode`].
+#[inline(always)]
+fn decode(mut offset: usize, class: usize) -> usize {
+    if class == BLOCK_BITS {
+        return (1 << BLOCK_BITS) - 1;
+    }
+    let mut block = 0;
+    let mut k = class;
+    let mut pos = BLOCK_BITS;
+    while k != 0 {
+        pos -= 1;
+        let binomial = BINOMIAL[pos][k];
+        if offset >= binomial {
+            block |= 1 << pos;
+            offset -= binomial;
+            k -= 1;
+        }
+    }
+    block
+}
+
+/// Reads `width` < 64 bits starting at bit position `pos`.
+#[inline(always)]
+fn read_bits(data: &[usize], pos: usize, width: usize) -> usize {
+    if width == 0 {
+        return 0;
+    }
+    let word = pos / usize::BITS as usize;
+    let bit = pos % usize::BITS as usize;
+    let mut value = unsafe { *data.get_unchecked(word) } >> bit;
+    if bit + width > usize::BITS as usize {
+        value |= unsafe { *data.get_unchecked(word + 1) } << (usize::BITS as usize - bit);
+    }
+    value & ((1 << width) - 1)
+}
+
+/// An entropy-compressed bit vector supporting rank and selection.
+///
+/// The bit vector is divided in blocks of 63 bits. Each block is represented
+/// by its _class_, that is, its number of ones, and by its _offset_, that is,
+/// its index in the enumeration of the blocks of the same class; the offset of
+/// a block of class *c* is written using ⌈lg <sup>63</sup>C<sub>*c*</sub>⌉
+/// bits, which is very small when *c* is close to 0 or 63. Every 32 blocks we
+/// store the number of preceding ones and the position of the offset of the
+/// first block. The space used is thus close to the empirical zero-order
+/// entropy of the bit vector, plus ≈12% for classes and samples, making this
+/// structure convenient for very sparse or very dense bit vectors.
+///
+/// This structure has been described by Rajeev Raman, Venkatesh Raman, and
+/// Srinivasa Rao Satti in “[Succinct indexable dictionaries with applications
+/// to encoding *k*-ary trees, prefix sums and
+/// multisets](https://doi.org/10.1145/1290672.1290680)”, _ACM Transactions on
+/// Algorithms_, 3(4):43, 2007. The implementation follows the practical
+/// approach proposed by Francisco Claude and Gonzalo Navarro in “[Practical
+/// Rank/Select Queries over Arbitrary
+/// Sequences](https://doi.org/10.1007/978-3-540-89097-3_18)”, _String
+/// Processing and Information Retrieval, 15th International Symposium, SPIRE
+/// 2008_, volume 5280 of Lecture Notes in Computer Science, pages 176–187,
+/// Springer, 2008.
+///
+/// Differently from the other structures in this module, an [`Rrr`] replaces
+/// the underlying bit vector rather than wrapping it: it implements directly
+/// [`BitLength`], [`BitCount`], [`NumBits`], [`Index`], and all ranking and
+/// selection traits (selection is performed by binary search on the samples),
+/// so it can be used, for example, as the high bits of an
+/// [`EliasFano`](crate::dict::EliasFano) structure. It does not, however,
+/// provide access to the words of a bit vector, so it cannot be wrapped by
+/// other structures of this module.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::bit_vec;
+/// use sux::rank_sel::Rrr;
+/// use sux::traits::{Rank, Select, SelectZero};
+///
+/// let rrr = Rrr::new(bit_vec![1, 0, 1, 1, 0, 1, 0, 1]);
+/// assert_eq!(rrr.rank(4), 3);
+/// assert_eq!(rrr.select(3), Some(5));
+/// assert_eq!(rrr.select_zero(1), Some(4));
+/// assert!(rrr[0]);
+/// assert!(!rrr[1]);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct Rrr<C = BitFieldVec<usize, Box<[usize]>>, D = Box<[usize]>> {
+    /// The length of the bit vector.
+    len: usize,
+    /// The number of ones in the bit vector.
+    num_ones: usize,
+    /// The class of each block.
+    classes: C,
+    /// The offsets of the blocks.
+    offsets: D,
+    /// For each sample, the number of preceding ones and the bit position in
+    /// `offsets` of the first block, interleaved; there is a final sample
+    /// after the last block.
+    samples: D,
+}
+
+impl<C, D> Rrr<C, D> {
+    /// Returns the number of bits in the bit vector.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Rrr {
+    /// Creates a new [`Rrr`] structure with the same content of a given bit
+    /// vector.
+    pub fn new(bits: impl AsRef<[usize]> + BitLength) -> Self {
+        let len = bits.len();
+        let words = bits.as_ref();
+        let num_blocks = len.div_ceil(BLOCK_BITS);
+        let mut classes = BitFieldVec::<usize>::new(CLASS_BITS, num_blocks);
+        let mut offsets = BitVec::new(0);
+        let mut samples = Vec::with_capacity(2 * (num_blocks / BLOCKS_PER_SAMPLE + 2));
+        let mut num_ones = 0;
+
+        for block_idx in 0..num_blocks {
+            if block_idx % BLOCKS_PER_SAMPLE == 0 {
+                samples.push(num_ones);
+                samples.push(offsets.len());
+            }
+            let pos = block_idx * BLOCK_BITS;
+            let block = read_bits(words, pos, BLOCK_BITS.min(len - pos));
+            let class = block.count_ones() as usize;
+            classes.set(block_idx, class);
+            num_ones += class;
+            let offset = encode(block, class);
+            for i in 0..OFFSET_BITS[class] {
+                offsets.push(offset & (1 << i) != 0);
+            }
+        }
+        samples.push(num_ones);
+        samples.push(offsets.len());
+        // An additional word makes it possible to read the offsets without
+        // bound checks
+        let (mut offsets, _) = offsets.into_raw_parts();
+        offsets.push(0);
+
+        Self {
+            len,
+            num_ones,
+            classes: classes.into(),
+            offsets: offsets.into(),
+            samples: samples.into(),
+        }
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> Rrr<C, D> {
+    /// Returns the number of ones preceding the given sample and the position
+    /// of the offset of its first block.
+    #[inline(always)]
+    fn sample(&self, sample: usize) -> (usize, usize) {
+        let samples = self.samples.as_ref();
+        unsafe {
+            (
+                *samples.get_unchecked(2 * sample),
+                *samples.get_unchecked(2 * sample + 1),
+            )
+        }
+    }
+
+    /// Returns the number of samples, including the final one.
+    #[inline(always)]
+    fn num_samples(&self) -> usize {
+        self.samples.as_ref().len() / 2
+    }
+
+    /// Returns the block of given index and class whose offset starts at the
+    /// given bit position.
+    #[inline(always)]
+    fn block(&self, offset_pos: usize, class: usize) -> usize {
+        decode(
+            read_bits(self.offsets.as_ref(), offset_pos, OFFSET_BITS[class]),
+            class,
+        )
+    }
+
+    /// Returns the bit of given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    pub fn get(&self, pos: usize) -> bool {
+        if pos >= self.len {
+            panic!("Index out of bounds: {} >= {}", pos, self.len);
+        }
+        let block_idx = pos / BLOCK_BITS;
+        let (_, mut offset_pos) = self.sample(block_idx / BLOCKS_PER_SAMPLE);
+        for i in block_idx / BLOCKS_PER_SAMPLE * BLOCKS_PER_SAMPLE..block_idx {
+            offset_pos += OFFSET_BITS[unsafe { self.classes.get_unchecked(i) }];
+        }
+        let class = unsafe { self.classes.get_unchecked(block_idx) };
+        self.block(offset_pos, class) & (1 << (pos % BLOCK_BITS)) != 0
+    }
+
+    /// Returns the index of the last sample before the given rank of ones
+    /// (zeros if `ZERO` is true).
+    #[inline(always)]
+    fn find_sample<const ZERO: bool>(&self, rank: usize) -> usize {
+        // The last sample is not a valid answer, as it follows all blocks
+        let (mut lo, mut hi) = (0, self.num_samples() - 1);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let (ones, _) = self.sample(mid);
+            let count = if ZERO {
+                mid * BLOCKS_PER_SAMPLE * BLOCK_BITS - ones
+            } else {
+                ones
+            };
+            if count <= rank {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+}
+
+impl<C, D> BitLength for Rrr<C, D> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<C, D> NumBits for Rrr<C, D> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.num_ones
+    }
+}
+
+impl<C, D> BitCount for Rrr<C, D> {
+    #[inline(always)]
+    fn count_ones(&self) -> usize {
+        self.num_ones
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> Index<usize> for Rrr<C, D> {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            false => &false,
+            true => &true,
+        }
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> RankUnchecked for Rrr<C, D> {
+    #[inline(always)]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        let block_idx = pos / BLOCK_BITS;
+        let (mut rank, mut offset_pos) = self.sample(block_idx / BLOCKS_PER_SAMPLE);
+        for i in block_idx / BLOCKS_PER_SAMPLE * BLOCKS_PER_SAMPLE..block_idx {
+            let class = self.classes.get_unchecked(i);
+            rank += class;
+            offset_pos += OFFSET_BITS[class];
+        }
+        let bit = pos % BLOCK_BITS;
+        if bit != 0 {
+            let block = self.block(offset_pos, self.classes.get_unchecked(block_idx));
+            rank += (block & ((1 << bit) - 1)).count_ones() as usize;
+        }
+        rank
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> Rank for Rrr<C, D> {}
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> RankZero for Rrr<C, D> {}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> SelectUnchecked for Rrr<C, D> {
+    unsafe fn select_unchecked(&self, mut rank: usize) -> usize {
+        let sample = self.find_sample::<false>(rank);
+        let (ones, mut offset_pos) = self.sample(sample);
+        rank -= ones;
+        let mut block_idx = sample * BLOCKS_PER_SAMPLE;
+        loop {
+            let class = self.classes.get_unchecked(block_idx);
+            if rank < class {
+                let block = self.block(offset_pos, class);
+                return block_idx * BLOCK_BITS + block.select_in_word(rank);
+            }
+            rank -= class;
+            offset_pos += OFFSET_BITS[class];
+            block_idx += 1;
+        }
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> Select for Rrr<C, D> {}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> SelectZeroUnchecked for Rrr<C, D> {
+    unsafe fn select_zero_unchecked(&self, mut rank: usize) -> usize {
+        let sample = self.find_sample::<true>(rank);
+        let (ones, mut offset_pos) = self.sample(sample);
+        let mut block_idx = sample * BLOCKS_PER_SAMPLE;
+        rank -= block_idx * BLOCK_BITS - ones;
+        loop {
+            let class = self.classes.get_unchecked(block_idx);
+            // Padding zeros of the last block are never selected
+            let zeros = BLOCK_BITS - class;
+            if rank < zeros {
+                let block = self.block(offset_pos, class);
+                return block_idx * BLOCK_BITS + (!block).select_in_word(rank);
+            }
+            rank -= zeros;
+            offset_pos += OFFSET_BITS[class];
+            block_idx += 1;
+        }
+    }
+}
+
+impl<C: BitFieldSlice<usize>, D: AsRef<[usize]>> SelectZero for Rrr<C, D> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let block = rng.gen::<usize>() & rng.gen::<usize>() & ((1 << BLOCK_BITS) - 1);
+            let class = block.count_ones() as usize;
+            let offset = encode(block, class);
+            assert!(offset < BINOMIAL[BLOCK_BITS][class]);
+            assert_eq!(decode(offset, class), block);
+        }
+        assert_eq!(decode(0, 0), 0);
+        assert_eq!(encode((1 << BLOCK_BITS) - 1, BLOCK_BITS), 0);
+        assert_eq!(decode(0, BLOCK_BITS), (1 << BLOCK_BITS) - 1);
+    }
+}
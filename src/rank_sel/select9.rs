@@ -10,7 +10,7 @@ use super::rank9::BlockCounters;
 use super::Rank9;
 use crate::{
     prelude::SelectUnchecked,
-    traits::{BitLength, NumBits, Select},
+    traits::{AsWords, BitLength, NumBits, Select},
 };
 use ambassador::Delegate;
 use common_traits::SelectInWord;
@@ -45,6 +45,7 @@ macro_rules! ULEQ_STEP_16 {
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitCount;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_NumBits;
@@ -116,6 +117,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "rank9")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "rank9")]
 #[delegate(Index<usize>, target = "rank9")]
 #[delegate(crate::traits::rank_sel::BitCount, target = "rank9")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "rank9")]
@@ -13,12 +13,13 @@ use mem_dbg::{MemDbg, MemSize};
 use std::cmp::{max, min};
 
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
+    prelude::{AsWords, BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
     traits::{NumBits, SelectUnchecked},
 };
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitCount;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_NumBits;
@@ -209,6 +210,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
@@ -352,7 +354,7 @@ impl<B: BitLength, C> SelectAdapt<B, C> {
     }
 }
 
-impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
+impl<B: AsWords + BitCount> SelectAdapt<B, Box<[usize]>> {
     /// Creates a new selection structure over a bit vecotr using a
     /// [default target inventory
     /// span](SelectAdapt::DEFAULT_TARGET_INVENTORY_SPAN).
@@ -489,7 +491,8 @@ impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
         let mut spilled = 0;
 
         // First phase: we build an inventory for each one out of ones_per_inventory.
-        for (i, word) in bits.as_ref().iter().copied().enumerate() {
+        for i in 0..bits.len().div_ceil(usize::BITS as usize) {
+            let word = unsafe { bits.usize_word_unchecked(i) };
             let ones_in_word = word.count_ones() as usize;
 
             while past_ones + ones_in_word > next_quantum {
@@ -604,7 +607,7 @@ impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
             let bit_idx = start_bit_idx % usize::BITS as usize;
 
             // Clear the lower bits
-            let mut word = (bits.as_ref()[word_idx] >> bit_idx) << bit_idx;
+            let mut word = (unsafe { bits.usize_word_unchecked(word_idx) } >> bit_idx) << bit_idx;
 
             'outer: loop {
                 let ones_in_word = word.count_ones() as usize;
@@ -707,7 +710,7 @@ impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
                 }
 
                 // Read the next word
-                word = bits.as_ref()[word_idx];
+                word = unsafe { bits.usize_word_unchecked(word_idx) };
             }
 
             // If we are in the U32 case, we need to update the number of used
@@ -736,7 +739,7 @@ impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
     }
 }
 
-impl<B: AsRef<[usize]> + BitLength + SelectHinted, I: AsRef<[usize]>> SelectUnchecked
+impl<B: AsWords + BitLength + SelectHinted, I: AsRef<[usize]>> SelectUnchecked
     for SelectAdapt<B, I>
 {
     unsafe fn select_unchecked(&self, rank: usize) -> usize {
@@ -814,7 +817,7 @@ impl<B: AsRef<[usize]> + BitLength + SelectHinted, I: AsRef<[usize]>> SelectUnch
     }
 }
 
-impl<B: SelectHinted + AsRef<[usize]> + NumBits, I: AsRef<[usize]>> Select for SelectAdapt<B, I> {}
+impl<B: SelectHinted + AsWords + NumBits, I: AsRef<[usize]>> Select for SelectAdapt<B, I> {}
 
 #[cfg(test)]
 mod tests {
@@ -14,12 +14,13 @@ use mem_dbg::{MemDbg, MemSize};
 use std::cmp::{max, min};
 
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
+    prelude::{AsWords, BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
     traits::{NumBits, SelectUnchecked},
 };
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitCount;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_NumBits;
@@ -144,6 +145,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
@@ -14,6 +14,7 @@ use mem_dbg::{MemDbg, MemSize};
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitCount;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_NumBits;
@@ -87,6 +88,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "rank_small")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "rank_small")]
 #[delegate(Index<usize>, target = "rank_small")]
 #[delegate(crate::traits::rank_sel::BitCount, target = "rank_small")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "rank_small")]
@@ -8,7 +8,7 @@
 
 use super::{Inventory, SpanType};
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength},
+    prelude::{AsWords, BitCount, BitFieldSlice, BitLength},
     traits::{NumBits, SelectZero, SelectZeroHinted, SelectZeroUnchecked},
 };
 use ambassador::Delegate;
@@ -19,6 +19,7 @@ use std::cmp::{max, min};
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_AsWords;
 use crate::traits::rank_sel::ambassador_impl_BitCount;
 use crate::traits::rank_sel::ambassador_impl_BitLength;
 use crate::traits::rank_sel::ambassador_impl_NumBits;
@@ -127,6 +128,7 @@ use std::ops::Index;
 
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
@@ -71,8 +71,13 @@ use std::marker::PhantomData;
 
 /// A derived trait that the types used as a parameter for [`BitFieldSlice`] must satisfy.
 /// To be usable in an [`AtomicBitFieldSlice`], the type must also implement [`IntoAtomic`].
-pub trait Word: UnsignedInt + FiniteRangeNumber + AsBytes {}
-impl<W: UnsignedInt + FiniteRangeNumber + AsBytes> Word for W {}
+///
+/// The same trait is used for the words of [bit vectors](crate::traits::AsWords).
+pub trait Word:
+    UnsignedInt + FiniteRangeNumber + AsBytes + SelectInWord + CastableInto<usize>
+{
+}
+impl<W: UnsignedInt + FiniteRangeNumber + AsBytes + SelectInWord + CastableInto<usize>> Word for W {}
 
 /// Common methods for [`BitFieldSlice`], [`BitFieldSliceMut`], and [`AtomicBitFieldSlice`].
 ///
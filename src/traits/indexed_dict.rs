@@ -26,6 +26,12 @@
 //! - [`PredUnchecked`]/[`Pred`] provide the predecessor of a value in a sorted
 //!   dictionary.
 //!
+//! [`Succ`] and [`Pred`] provide also batch versions of their queries
+//! ([`Succ::succ_batch`], [`Pred::pred_batch`]), and adaptors mapping a
+//! nondecreasing sequence of values to their successors or predecessors
+//! ([`Succ::succ_sorted`], [`Pred::pred_sorted`]), which might be
+//! significantly faster than independent queries.
+//!
 //! [`IndexedSeq`], [`IndexedDict`], [`SuccUnchecked`], and [`PredUnchecked`]
 //! are independent. A structure may implement any combination of them, provided
 //! it implements [`Types`].
@@ -166,6 +172,38 @@ where
             Some(unsafe { self.succ_unchecked::<true>(value) })
         }
     }
+
+    /// Return the results of [`succ`](Succ::succ) on a batch of values.
+    ///
+    /// The default implementation calls [`succ`](Succ::succ) on each value,
+    /// but implementations might take advantage of the locality of sorted
+    /// batches (see [`succ_sorted`](Succ::succ_sorted)).
+    fn succ_batch<V: Borrow<Self::Input>>(
+        &self,
+        values: &[V],
+    ) -> Vec<Option<(usize, Self::Output)>> {
+        values
+            .iter()
+            .map(|value| self.succ(value.borrow()))
+            .collect()
+    }
+
+    /// Return an iterator over the results of [`succ`](Succ::succ) on a
+    /// nondecreasing sequence of values.
+    ///
+    /// The default implementation calls [`succ`](Succ::succ) on each value,
+    /// but implementations might reuse the state of a query to speed up the
+    /// following one.
+    ///
+    /// # Panics
+    ///
+    /// Implementations might panic if the values are not nondecreasing.
+    fn succ_sorted<V: Borrow<Self::Input>>(
+        &self,
+        values: impl IntoIterator<Item = V>,
+    ) -> impl Iterator<Item = Option<(usize, Self::Output)>> {
+        values.into_iter().map(move |value| self.succ(value))
+    }
 }
 
 /// Unchecked predecessor computation for dictionaries whose values are monotonically increasing.
@@ -231,4 +269,36 @@ where
             Some(unsafe { self.pred_unchecked::<true>(value) })
         }
     }
+
+    /// Return the results of [`pred`](Pred::pred) on a batch of values.
+    ///
+    /// The default implementation calls [`pred`](Pred::pred) on each value,
+    /// but implementations might take advantage of the locality of sorted
+    /// batches (see [`pred_sorted`](Pred::pred_sorted)).
+    fn pred_batch<V: Borrow<Self::Input>>(
+        &self,
+        values: &[V],
+    ) -> Vec<Option<(usize, Self::Output)>> {
+        values
+            .iter()
+            .map(|value| self.pred(value.borrow()))
+            .collect()
+    }
+
+    /// Return an iterator over the results of [`pred`](Pred::pred) on a
+    /// nondecreasing sequence of values.
+    ///
+    /// The default implementation calls [`pred`](Pred::pred) on each value,
+    /// but implementations might reuse the state of a query to speed up the
+    /// following one.
+    ///
+    /// # Panics
+    ///
+    /// Implementations might panic if the values are not nondecreasing.
+    fn pred_sorted<V: Borrow<Self::Input>>(
+        &self,
+        values: impl IntoIterator<Item = V>,
+    ) -> impl Iterator<Item = Option<(usize, Self::Output)>> {
+        values.into_iter().map(move |value| self.pred(value))
+    }
 }
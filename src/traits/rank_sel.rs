@@ -14,7 +14,9 @@
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::traits::Word;
 use ambassador::{delegatable_trait, Delegate};
+use common_traits::{AsBytes, AtomicUnsignedInt, CastableInto, IntoAtomic};
 use epserde::Epserde;
 use impl_tools::autoimpl;
 use mem_dbg::{MemDbg, MemSize};
@@ -22,8 +24,8 @@ use std::ops::Index;
 
 /// A trait expressing a length in bits.
 ///
-/// This trait is typically used in conjunction with `AsRef<[usize]>` to provide
-/// word-based access to a bit vector.
+/// This trait is typically used in conjunction with [`AsWords`] (or
+/// `AsRef<[usize]>`) to provide word-based access to a bit vector.
 #[autoimpl(for<T: trait + ?Sized> &T, &mut T, Box<T>)]
 #[delegatable_trait]
 pub trait BitLength {
@@ -31,6 +33,175 @@ pub trait BitLength {
     fn len(&self) -> usize;
 }
 
+/// Word-based access to a bit vector (possibly underlying a succinct data
+/// structure) whose bits are stored in words of type [`AsWords::Word`].
+///
+/// Bit `i` of the bit vector is bit `i % Word::BITS` of word `i / Word::BITS`,
+/// so the same bits can be stored in words of any type, and bit vectors
+/// produced by other tools using, say, `u8` or `u32` words, can be used
+/// directly.
+///
+/// Structures that need to access bits in words of fixed size, such as
+/// [`Rank9`](crate::rank_sel::Rank9), use
+/// [`usize_word_unchecked`](AsWords::usize_word_unchecked), which provides a
+/// view of the bits as if they were stored in `usize` words.
+///
+/// This trait is implemented for slices, arrays and vectors of words, and it
+/// is forwarded by references, boxes, bit vectors, and all structures in
+/// [`rank_sel`](crate::rank_sel).
+#[autoimpl(for<T: trait + ?Sized> &T, &mut T, Box<T>)]
+#[delegatable_trait]
+pub trait AsWords {
+    /// The type of the words.
+    type Word: Word;
+
+    /// Returns the words containing the bits.
+    fn as_words(&self) -> &[Self::Word];
+
+    /// Returns the `index`-th `usize` word of the bits, that is, the bits from
+    /// position `index * usize::BITS` (included) to position
+    /// `(index + 1) * usize::BITS` (excluded), in the same order as if they
+    /// were stored in a slice of `usize`. Bits beyond the end of the words
+    /// are zero.
+    ///
+    /// # Safety
+    ///
+    /// `index * usize::BITS` must be less than the number of bits in the
+    /// words.
+    #[inline(always)]
+    unsafe fn usize_word_unchecked(&self, index: usize) -> usize {
+        let words = self.as_words();
+        let word_bits = Self::Word::BITS;
+        let usize_bits = usize::BITS as usize;
+        if word_bits == usize_bits {
+            (*words.get_unchecked(index)).cast()
+        } else if word_bits > usize_bits {
+            let ratio = word_bits / usize_bits;
+            (*words.get_unchecked(index / ratio) >> ((index % ratio) * usize_bits)).cast()
+        } else {
+            let ratio = usize_bits / word_bits;
+            let start = index * ratio;
+            let end = words.len().min(start + ratio);
+            let mut result = 0;
+            for (i, &word) in words.get_unchecked(start..end).iter().enumerate() {
+                result |= word.cast() << (i * word_bits);
+            }
+            result
+        }
+    }
+}
+
+/// Mutable word-based access to a bit vector.
+///
+/// See [`AsWords`].
+#[autoimpl(for<T: trait + ?Sized> &mut T, Box<T>)]
+pub trait AsWordsMut: AsWords {
+    /// Returns the words containing the bits as a mutable slice.
+    fn as_words_mut(&mut self) -> &mut [Self::Word];
+}
+
+/// Word-based access to a bit vector whose bits are stored in atomic words.
+///
+/// This trait is the atomic analogue of [`AsWords`], and it is implemented
+/// for slices, arrays and vectors of atomic words.
+#[autoimpl(for<T: trait + ?Sized> &T, &mut T, Box<T>)]
+pub trait AsAtomicWords {
+    /// The (non-atomic) type of the words.
+    type Word: Word + IntoAtomic<AtomicType = Self::AtomicWord>;
+    /// The atomic type of the words.
+    type AtomicWord: AtomicUnsignedInt<NonAtomicType = Self::Word>;
+
+    /// Returns the atomic words containing the bits.
+    fn as_atomic_words(&self) -> &[Self::AtomicWord];
+}
+
+impl<W: Word> AsWords for [W] {
+    type Word = W;
+
+    #[inline(always)]
+    fn as_words(&self) -> &[W] {
+        self
+    }
+}
+
+impl<W: Word> AsWordsMut for [W] {
+    #[inline(always)]
+    fn as_words_mut(&mut self) -> &mut [W] {
+        self
+    }
+}
+
+impl<W: Word, const N: usize> AsWords for [W; N] {
+    type Word = W;
+
+    #[inline(always)]
+    fn as_words(&self) -> &[W] {
+        self
+    }
+}
+
+impl<W: Word, const N: usize> AsWordsMut for [W; N] {
+    #[inline(always)]
+    fn as_words_mut(&mut self) -> &mut [W] {
+        self
+    }
+}
+
+impl<W: Word> AsWords for Vec<W> {
+    type Word = W;
+
+    #[inline(always)]
+    fn as_words(&self) -> &[W] {
+        self
+    }
+}
+
+impl<W: Word> AsWordsMut for Vec<W> {
+    #[inline(always)]
+    fn as_words_mut(&mut self) -> &mut [W] {
+        self
+    }
+}
+
+impl<A: AtomicUnsignedInt> AsAtomicWords for [A]
+where
+    A::NonAtomicType: Word,
+{
+    type Word = A::NonAtomicType;
+    type AtomicWord = A;
+
+    #[inline(always)]
+    fn as_atomic_words(&self) -> &[A] {
+        self
+    }
+}
+
+impl<A: AtomicUnsignedInt, const N: usize> AsAtomicWords for [A; N]
+where
+    A::NonAtomicType: Word,
+{
+    type Word = A::NonAtomicType;
+    type AtomicWord = A;
+
+    #[inline(always)]
+    fn as_atomic_words(&self) -> &[A] {
+        self
+    }
+}
+
+impl<A: AtomicUnsignedInt> AsAtomicWords for Vec<A>
+where
+    A::NonAtomicType: Word,
+{
+    type Word = A::NonAtomicType;
+    type AtomicWord = A;
+
+    #[inline(always)]
+    fn as_atomic_words(&self) -> &[A] {
+        self
+    }
+}
+
 /// Potentially expensive bit-counting methods.
 ///
 /// The methods in this trait compute the number of ones or zeros
@@ -265,6 +436,7 @@ pub trait SelectZeroHinted {
 /// for example, [`SelectAdapt`](crate::rank_sel::SelectAdapt).
 #[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
 #[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(crate::traits::rank_sel::AsWords, target = "bits")]
 #[delegate(Index<usize>, target = "bits")]
 #[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
 #[delegate(crate::traits::rank_sel::Rank, target = "bits")]
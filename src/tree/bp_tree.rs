@@ -0,0 +1,552 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Ordinal trees represented by balanced parentheses.
+//!
+//! An ordinal tree with *n* nodes can be represented by a sequence of 2*n*
+//! balanced parentheses, obtained by visiting the tree in preorder and writing
+//! an open parenthesis when entering a node and a closed parenthesis when
+//! leaving it. Each node is identified by the position of its open
+//! parenthesis.
+//!
+//! Navigation is reduced to searching for positions with a given *excess*
+//! (the number of open parentheses minus the number of closed parentheses in
+//! a prefix of the sequence). Excess is computed in constant time by ranking,
+//! and searches are accelerated by a *range min-max tree*, which in our case
+//! is a complete binary tree storing the minimum excess of each block of 512
+//! parentheses and of each subtree. Within a block, searches proceed a byte
+//! at a time using lookup tables.
+//!
+//! The range min-max tree has been introduced by Gonzalo Navarro and Kunihiko
+//! Sadakane in “[Fully Functional Static and Dynamic Succinct
+//! Trees](https://doi.org/10.1145/2601073)”, _ACM Transactions on
+//! Algorithms_, 10(3):16, 2014.
+
+use crate::prelude::*;
+use epserde::*;
+use mem_dbg::*;
+
+/// The logarithm of the number of parentheses in a block of the range min-max
+/// tree.
+const LOG2_BLOCK_SIZE: usize = 9;
+/// The number of parentheses in a block of the range min-max tree.
+const BLOCK_SIZE: usize = 1 << LOG2_BLOCK_SIZE;
+
+/// The excess of each byte.
+const BYTE_EXCESS: [i8; 256] = byte_excess();
+/// The minimum excess of the nonempty prefixes of each byte.
+const BYTE_MIN_FWD: [i8; 256] = byte_min_fwd();
+/// The minimum negated excess of the nonempty suffixes of each byte.
+const BYTE_MIN_BWD: [i8; 256] = byte_min_bwd();
+
+const fn byte_excess() -> [i8; 256] {
+    let mut table = [0; 256];
+    let mut b = 0;
+    while b < 256 {
+        table[b] = 2 * (b as u8).count_ones() as i8 - 8;
+        b += 1;
+    }
+    table
+}
+
+const fn byte_min_fwd() -> [i8; 256] {
+    let mut table = [0; 256];
+    let mut b = 0;
+    while b < 256 {
+        let (mut excess, mut min) = (0, i8::MAX);
+        let mut i = 0;
+        while i < 8 {
+            excess += if b >> i & 1 != 0 { 1 } else { -1 };
+            if excess < min {
+                min = excess;
+            }
+            i += 1;
+        }
+        table[b] = min;
+        b += 1;
+    }
+    table
+}
+
+const fn byte_min_bwd() -> [i8; 256] {
+    let mut table = [0; 256];
+    let mut b = 0;
+    while b < 256 {
+        let (mut excess, mut min) = (0, i8::MAX);
+        let mut i = 8;
+        while i > 0 {
+            i -= 1;
+            excess -= if b >> i & 1 != 0 { 1 } else { -1 };
+            if excess < min {
+                min = excess;
+            }
+        }
+        table[b] = min;
+        b += 1;
+    }
+    table
+}
+
+#[inline(always)]
+fn bit(words: &[usize], pos: usize) -> bool {
+    (words[pos / usize::BITS as usize] >> (pos % usize::BITS as usize)) & 1 != 0
+}
+
+/// Returns the byte starting at the given (byte-aligned) position.
+#[inline(always)]
+fn byte(words: &[usize], pos: usize) -> usize {
+    (words[pos / usize::BITS as usize] >> (pos % usize::BITS as usize)) & 0xFF
+}
+
+/// Scans the parentheses in positions `[start..end)`, starting from excess
+/// `excess` at position `start`, and returns the first position after `start`
+/// at which the excess is at most `target`, or the excess at `end`.
+fn fwd_scan(
+    words: &[usize],
+    mut pos: usize,
+    end: usize,
+    mut excess: isize,
+    target: isize,
+) -> Result<usize, isize> {
+    while pos < end && pos & 7 != 0 {
+        excess += if bit(words, pos) { 1 } else { -1 };
+        pos += 1;
+        if excess <= target {
+            return Ok(pos);
+        }
+    }
+    while pos + 8 <= end {
+        let b = byte(words, pos);
+        if excess + BYTE_MIN_FWD[b] as isize <= target {
+            break;
+        }
+        excess += BYTE_EXCESS[b] as isize;
+        pos += 8;
+    }
+    while pos < end {
+        excess += if bit(words, pos) { 1 } else { -1 };
+        pos += 1;
+        if excess <= target {
+            return Ok(pos);
+        }
+    }
+    Err(excess)
+}
+
+/// Scans backwards the parentheses in positions `[start..end)`, starting
+/// from excess `excess` at position `end`, and returns the last position
+/// before `end` at which the excess is at most `target`.
+fn bwd_scan(
+    words: &[usize],
+    start: usize,
+    mut pos: usize,
+    mut excess: isize,
+    target: isize,
+) -> Option<usize> {
+    while pos > start && pos & 7 != 0 {
+        pos -= 1;
+        excess -= if bit(words, pos) { 1 } else { -1 };
+        if excess <= target {
+            return Some(pos);
+        }
+    }
+    while pos >= start + 8 {
+        let b = byte(words, pos - 8);
+        if excess + BYTE_MIN_BWD[b] as isize <= target {
+            break;
+        }
+        excess -= BYTE_EXCESS[b] as isize;
+        pos -= 8;
+    }
+    while pos > start {
+        pos -= 1;
+        excess -= if bit(words, pos) { 1 } else { -1 };
+        if excess <= target {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// Returns the minimum excess at positions `(start..end]`, starting from
+/// excess `excess` at position `start`, and the excess at `end`.
+fn min_scan(words: &[usize], mut pos: usize, end: usize, mut excess: isize) -> (isize, isize) {
+    let mut min = isize::MAX;
+    while pos < end && pos & 7 != 0 {
+        excess += if bit(words, pos) { 1 } else { -1 };
+        min = min.min(excess);
+        pos += 1;
+    }
+    while pos + 8 <= end {
+        let b = byte(words, pos);
+        min = min.min(excess + BYTE_MIN_FWD[b] as isize);
+        excess += BYTE_EXCESS[b] as isize;
+        pos += 8;
+    }
+    while pos < end {
+        excess += if bit(words, pos) { 1 } else { -1 };
+        min = min.min(excess);
+        pos += 1;
+    }
+    (min, excess)
+}
+
+/// An ordinal tree (or forest) represented by balanced parentheses, with a
+/// range min-max tree supporting navigation.
+///
+/// Nodes are identified by the position of their open parenthesis; in
+/// particular, the root of a nonempty tree is 0. The methods accepting a node
+/// have unspecified results if they are passed a position that is not that of
+/// an open parenthesis.
+///
+/// The underlying bit vector `B` (in which ones are open parentheses) must
+/// provide [ranking](Rank) and [selection](SelectUnchecked). By default, it
+/// is a [`Rank9`] wrapped by a [`SelectAdapt`], but other structures can be
+/// used through [`BpTree::from_bits`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::bit_vec;
+/// # use sux::tree::BpTree;
+/// // A root with two children, the first of which has a child
+/// let tree = BpTree::new(bit_vec![1, 1, 1, 0, 0, 1, 0, 0]);
+///
+/// assert_eq!(tree.num_nodes(), 4);
+/// assert_eq!(tree.first_child(0), Some(1));
+/// assert_eq!(tree.next_sibling(1), Some(5));
+/// assert_eq!(tree.next_sibling(5), None);
+/// assert_eq!(tree.parent(2), Some(1));
+/// assert_eq!(tree.parent(0), None);
+/// assert_eq!(tree.subtree_size(1), 2);
+/// assert_eq!(tree.depth(2), 2);
+/// assert_eq!(tree.lca(2, 5), Some(0));
+/// assert_eq!(tree.preorder_rank(5), 3);
+/// assert_eq!(tree.preorder_select(2), Some(2));
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct BpTree<
+    B = SelectAdapt<Rank9<BitVec<Box<[usize]>>>>,
+    M = BitFieldVec<usize, Box<[usize]>>,
+> {
+    /// The parentheses.
+    bits: B,
+    /// The number of leaves of the range min-max tree (a power of two).
+    num_leaves: usize,
+    /// The range min-max tree, in heap order starting from index 1.
+    min_excess: M,
+}
+
+impl BpTree {
+    /// Creates a new tree from a sequence of balanced parentheses, using
+    /// [`Rank9`] and [`SelectAdapt`] for ranking and selection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parentheses are not balanced.
+    pub fn new(bits: impl Into<BitVec<Box<[usize]>>>) -> Self {
+        Self::from_bits(SelectAdapt::new(Rank9::new(bits.into()), 3))
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength + Rank + SelectUnchecked> BpTree<B> {
+    /// Creates a new tree from a sequence of balanced parentheses supporting
+    /// ranking and selection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parentheses are not balanced.
+    pub fn from_bits(bits: B) -> Self {
+        let len = bits.len();
+        let words = bits.as_ref();
+        let num_blocks = (len + 1).div_ceil(BLOCK_SIZE);
+        let num_leaves = num_blocks.next_power_of_two();
+        // Excess values are in [0..len / 2]; larger values are padding
+        let bit_width = (len / 2 + 1).ilog2() as usize + 1;
+        let padding = usize::MAX >> (usize::BITS as usize - bit_width);
+        let mut min_excess = BitFieldVec::<usize>::new(bit_width, 2 * num_leaves);
+
+        let mut excess = 0_isize;
+        let mut min = 0;
+        for pos in 0..len {
+            excess += if bit(words, pos) { 1 } else { -1 };
+            assert!(excess >= 0, "The parentheses are not balanced");
+            if (pos + 1) & (BLOCK_SIZE - 1) == 0 {
+                min_excess.set(num_leaves + pos / BLOCK_SIZE, min as usize);
+                min = excess;
+            } else {
+                min = min.min(excess);
+            }
+        }
+        assert!(excess == 0, "The parentheses are not balanced");
+        min_excess.set(num_leaves + num_blocks - 1, min as usize);
+
+        for leaf in num_blocks..num_leaves {
+            min_excess.set(num_leaves + leaf, padding);
+        }
+        for node in (1..num_leaves).rev() {
+            min_excess.set(
+                node,
+                min_excess.get(2 * node).min(min_excess.get(2 * node + 1)),
+            );
+        }
+
+        Self {
+            bits,
+            num_leaves,
+            min_excess: min_excess.into(),
+        }
+    }
+}
+
+impl<B, M> BpTree<B, M> {
+    /// Returns the underlying bit vector.
+    pub fn into_inner(self) -> B {
+        self.bits
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength + Rank + SelectUnchecked, M: BitFieldSlice<usize>> BpTree<B, M> {
+    /// Returns the number of nodes.
+    #[inline(always)]
+    pub fn num_nodes(&self) -> usize {
+        self.bits.len() / 2
+    }
+
+    /// Returns the excess before the given position.
+    #[inline(always)]
+    fn excess(&self, pos: usize) -> isize {
+        2 * self.bits.rank(pos) as isize - pos as isize
+    }
+
+    /// Returns the minimum excess stored at the given node of the range
+    /// min-max tree.
+    #[inline(always)]
+    fn min(&self, node: usize) -> isize {
+        unsafe { self.min_excess.get_unchecked(node) as isize }
+    }
+
+    /// Returns the first position greater than or equal to `pos` at which the
+    /// excess is at most `target`.
+    fn fwd_search(&self, pos: usize, target: isize) -> Option<usize> {
+        let len = self.bits.len();
+        let words = self.bits.as_ref();
+        let excess = self.excess(pos);
+        if excess <= target {
+            return Some(pos);
+        }
+        let block = pos / BLOCK_SIZE;
+        let end = ((block + 1) * BLOCK_SIZE).min(len);
+        if let Ok(pos) = fwd_scan(words, pos, end, excess, target) {
+            return Some(pos);
+        }
+
+        // Go up until we find a right sibling with small enough minimum
+        let mut node = self.num_leaves + block;
+        loop {
+            if node == 1 {
+                return None;
+            }
+            if node & 1 == 0 && self.min(node + 1) <= target {
+                node += 1;
+                break;
+            }
+            node /= 2;
+        }
+        // Go down to the leftmost leaf with small enough minimum
+        while node < self.num_leaves {
+            node = if self.min(2 * node) <= target {
+                2 * node
+            } else {
+                2 * node + 1
+            };
+        }
+
+        let start = (node - self.num_leaves) * BLOCK_SIZE;
+        let excess = self.excess(start);
+        if excess <= target {
+            return Some(start);
+        }
+        fwd_scan(words, start, (start + BLOCK_SIZE).min(len), excess, target).ok()
+    }
+
+    /// Returns the last position smaller than or equal to `pos` at which the
+    /// excess is at most `target`.
+    fn bwd_search(&self, pos: usize, target: isize) -> Option<usize> {
+        let len = self.bits.len();
+        let words = self.bits.as_ref();
+        let excess = self.excess(pos);
+        if excess <= target {
+            return Some(pos);
+        }
+        let block = pos / BLOCK_SIZE;
+        if let Some(pos) = bwd_scan(words, block * BLOCK_SIZE, pos, excess, target) {
+            return Some(pos);
+        }
+
+        // Go up until we find a left sibling with small enough minimum
+        let mut node = self.num_leaves + block;
+        loop {
+            if node == 1 {
+                return None;
+            }
+            if node & 1 == 1 && self.min(node - 1) <= target {
+                node -= 1;
+                break;
+            }
+            node /= 2;
+        }
+        // Go down to the rightmost leaf with small enough minimum
+        while node < self.num_leaves {
+            node = if self.min(2 * node + 1) <= target {
+                2 * node + 1
+            } else {
+                2 * node
+            };
+        }
+
+        let start = (node - self.num_leaves) * BLOCK_SIZE;
+        let last = (start + BLOCK_SIZE - 1).min(len);
+        let excess = self.excess(last);
+        if excess <= target {
+            return Some(last);
+        }
+        bwd_scan(words, start, last, excess, target)
+    }
+
+    /// Returns the minimum excess at positions `[start..=end]`.
+    fn min_excess(&self, start: usize, end: usize) -> isize {
+        let words = self.bits.as_ref();
+        let first_block = start / BLOCK_SIZE;
+        let last_block = end / BLOCK_SIZE;
+        let excess = self.excess(start);
+        if first_block == last_block {
+            return excess.min(min_scan(words, start, end, excess).0);
+        }
+
+        let mut min = excess.min(min_scan(words, start, (first_block + 1) * BLOCK_SIZE, excess).0);
+        // Full blocks in between
+        let (mut a, mut b) = (
+            self.num_leaves + first_block + 1,
+            self.num_leaves + last_block,
+        );
+        while a < b {
+            if a & 1 == 1 {
+                min = min.min(self.min(a));
+                a += 1;
+            }
+            if b & 1 == 1 {
+                b -= 1;
+                min = min.min(self.min(b));
+            }
+            a /= 2;
+            b /= 2;
+        }
+        let start = last_block * BLOCK_SIZE;
+        let excess = self.excess(start);
+        min.min(excess).min(min_scan(words, start, end, excess).0)
+    }
+
+    /// Returns the position of the closed parenthesis matching the open
+    /// parenthesis of the given node.
+    pub fn find_close(&self, node: usize) -> usize {
+        debug_assert!(bit(self.bits.as_ref(), node));
+        // Since the parentheses are balanced, the search cannot fail
+        self.fwd_search(node + 1, self.excess(node)).unwrap() - 1
+    }
+
+    /// Returns the position of the open parenthesis (i.e., the node) matching
+    /// the closed parenthesis at the given position.
+    pub fn find_open(&self, pos: usize) -> usize {
+        debug_assert!(!bit(self.bits.as_ref(), pos));
+        self.bwd_search(pos, self.excess(pos + 1)).unwrap()
+    }
+
+    /// Returns the parent of the given node, or `None` if the node is a root.
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        debug_assert!(bit(self.bits.as_ref(), node));
+        let excess = self.excess(node);
+        if excess == 0 {
+            None
+        } else {
+            self.bwd_search(node, excess - 1)
+        }
+    }
+
+    /// Returns the first child of the given node, or `None` if the node is a
+    /// leaf.
+    pub fn first_child(&self, node: usize) -> Option<usize> {
+        debug_assert!(bit(self.bits.as_ref(), node));
+        // Since node is an open parenthesis, node + 1 is within bounds
+        bit(self.bits.as_ref(), node + 1).then_some(node + 1)
+    }
+
+    /// Returns the next sibling of the given node, or `None` if the node is
+    /// the last child of its parent.
+    ///
+    /// For the roots of a forest, this method returns the next root.
+    pub fn next_sibling(&self, node: usize) -> Option<usize> {
+        let next = self.find_close(node) + 1;
+        (next < self.bits.len() && bit(self.bits.as_ref(), next)).then_some(next)
+    }
+
+    /// Returns whether the given node is a leaf.
+    pub fn is_leaf(&self, node: usize) -> bool {
+        self.first_child(node).is_none()
+    }
+
+    /// Returns the number of nodes in the subtree rooted at the given node,
+    /// including the node itself.
+    pub fn subtree_size(&self, node: usize) -> usize {
+        (self.find_close(node) - node) / 2 + 1
+    }
+
+    /// Returns the depth of the given node; roots have depth zero.
+    pub fn depth(&self, node: usize) -> usize {
+        debug_assert!(bit(self.bits.as_ref(), node));
+        self.excess(node) as usize
+    }
+
+    /// Returns whether `ancestor` is an ancestor of `node`; every node is an
+    /// ancestor of itself.
+    pub fn is_ancestor(&self, ancestor: usize, node: usize) -> bool {
+        ancestor <= node && node < self.find_close(ancestor)
+    }
+
+    /// Returns the lowest common ancestor of the given nodes, or `None` if
+    /// they belong to different trees of a forest.
+    pub fn lca(&self, u: usize, v: usize) -> Option<usize> {
+        let (u, v) = (u.min(v), u.max(v));
+        if self.is_ancestor(u, v) {
+            return Some(u);
+        }
+        // The minimum excess between the two nodes is attained at the
+        // children of the lowest common ancestor
+        let min = self.min_excess(u + 1, v);
+        if min == 0 {
+            None
+        } else {
+            self.bwd_search(v, min - 1)
+        }
+    }
+
+    /// Returns the preorder rank of the given node.
+    #[inline(always)]
+    pub fn preorder_rank(&self, node: usize) -> usize {
+        self.bits.rank(node)
+    }
+
+    /// Returns the node of given preorder rank, or `None` if the rank is
+    /// greater than or equal to the number of nodes.
+    #[inline(always)]
+    pub fn preorder_select(&self, rank: usize) -> Option<usize> {
+        if rank >= self.num_nodes() {
+            None
+        } else {
+            Some(unsafe { self.bits.select_unchecked(rank) })
+        }
+    }
+}
@@ -0,0 +1,15 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Succinct trees.
+//!
+//! The structures in this module represent ordinal trees using a number of
+//! bits close to the information-theoretical lower bound, and support
+//! navigation using the [ranking and selection structures](crate::rank_sel)
+//! of this crate.
+
+pub mod bp_tree;
+pub use bp_tree::*;
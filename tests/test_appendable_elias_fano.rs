@@ -0,0 +1,112 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+/// Checks all queries against a sorted vector.
+fn check(aef: &AppendableEliasFano, values: &[usize], rng: &mut SmallRng) {
+    let n = values.len();
+    assert_eq!(aef.len(), n);
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(aef.get(i), value);
+    }
+
+    // The index and the value at a given position, if any
+    let at = |i: usize| (i < n).then(|| (i, values[i]));
+    let max = values.last().copied().unwrap_or(0) + 10;
+    let mut queries = (0..200).map(|_| rng.gen_range(0..=max)).collect::<Vec<_>>();
+    queries.extend(values.iter().step_by(7).copied());
+    for x in queries {
+        let succ = values.partition_point(|&v| v < x);
+        let succ_strict = values.partition_point(|&v| v <= x);
+        assert_eq!(aef.succ(x), at(succ));
+        assert_eq!(aef.succ_strict(x), at(succ_strict));
+        assert_eq!(aef.pred(x), succ_strict.checked_sub(1).and_then(at));
+        assert_eq!(aef.pred_strict(x), succ.checked_sub(1).and_then(at));
+        assert_eq!(
+            aef.index_of(x),
+            at(succ).filter(|&(_, v)| v == x).map(|(i, _)| i)
+        );
+    }
+}
+
+#[test]
+fn test_appendable_elias_fano() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (l, max_gap) in [(0, 1), (0, 10), (3, 10), (5, 1000), (10, 10)] {
+        let mut aef = AppendableEliasFano::new(l);
+        let mut values = vec![];
+        check(&aef, &values, &mut rng);
+        let mut value = 0;
+        for batch in [1, 10, 100, 1000, 1000, 5000, 10_000] {
+            for _ in 0..batch {
+                value += rng.gen_range(0..=max_gap);
+                aef.push(value);
+                values.push(value);
+            }
+            // Usually, there is an unindexed tail here
+            check(&aef, &values, &mut rng);
+        }
+        aef.rebuild();
+        assert_eq!(aef.num_indexed(), values.len());
+        check(&aef, &values, &mut rng);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_with_estimate() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut aef = AppendableEliasFano::with_estimate(100, 10_000);
+    let mut values = (0..1000)
+        .map(|_| rng.gen_range(0..100_000))
+        .collect::<Vec<_>>();
+    values.sort();
+    for &value in &values {
+        aef.push(value);
+    }
+    check(&aef, &values, &mut rng);
+}
+
+#[test]
+#[should_panic]
+fn test_non_monotone() {
+    let mut aef = AppendableEliasFano::new(2);
+    aef.push(1);
+    aef.push(0);
+}
+
+#[test]
+fn test_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut aef = AppendableEliasFano::new(4);
+    let mut values = (0..10_000)
+        .map(|_| rng.gen_range(0..100_000))
+        .collect::<Vec<_>>();
+    values.sort();
+    for &value in &values {
+        aef.push(value);
+    }
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_aef.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    aef.serialize(&mut file)?;
+    drop(file);
+
+    let mut c = AppendableEliasFano::load_full(&tmp_file)?;
+    check(&c, &values, &mut rng);
+
+    // The deserialized structure can be extended
+    c.push(200_000);
+    values.push(200_000);
+    check(&c, &values, &mut rng);
+    Ok(())
+}
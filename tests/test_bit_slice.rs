@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+fn random_bit_vec(len: usize, rng: &mut SmallRng) -> BitVec {
+    (0..len).map(|_| rng.gen_bool(0.3)).collect()
+}
+
+#[test]
+fn test_bit_slice() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 200, 1000] {
+        let b = random_bit_vec(len, &mut rng);
+        let bits = b.iter().collect::<Vec<_>>();
+        for _ in 0..50 {
+            let start = rng.gen_range(0..=len);
+            let end = rng.gen_range(start..=len);
+            let s = b.slice(start..end);
+            let expected = &bits[start..end];
+            assert_eq!(s.len(), end - start);
+            assert_eq!(s.is_empty(), start == end);
+            assert_eq!(s.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(s.into_iter().len(), end - start);
+            for (i, &bit) in expected.iter().enumerate() {
+                assert_eq!(s[i], bit);
+                assert_eq!(s.get(i), bit);
+            }
+            assert_eq!(s.count_ones(), expected.iter().filter(|&&bit| bit).count());
+            assert_eq!(
+                s.iter_ones().collect::<Vec<_>>(),
+                (0..end - start)
+                    .filter(|&i| expected[i])
+                    .collect::<Vec<_>>()
+            );
+            let c = s.to_bit_vec();
+            assert_eq!(c.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(c.slice(..), s);
+
+            // Slices of slices
+            let sub_start = rng.gen_range(0..=s.len());
+            let sub_end = rng.gen_range(sub_start..=s.len());
+            assert_eq!(
+                s.slice(sub_start..sub_end).iter().collect::<Vec<_>>(),
+                &expected[sub_start..sub_end]
+            );
+            assert_eq!(
+                s.slice(sub_start..sub_end),
+                b.slice(start + sub_start..start + sub_end)
+            );
+        }
+    }
+
+    let b = bit_vec![0, 1, 1, 0];
+    assert_eq!(b.slice(..).len(), 4);
+    assert_eq!(b.slice(1..).len(), 3);
+    assert_eq!(b.slice(..=1).len(), 2);
+    assert_ne!(b.slice(0..2), b.slice(1..3));
+}
+
+#[test]
+fn test_get_set_bits() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1000;
+    let mut b = random_bit_vec(len, &mut rng);
+    let mut bits = b.iter().collect::<Vec<_>>();
+    for _ in 0..10_000 {
+        let width = rng.gen_range(0..=64);
+        let pos = rng.gen_range(0..=len - width);
+        let expected = (0..width).fold(0, |acc, i| acc | (bits[pos + i] as usize) << i);
+        assert_eq!(b.get_bits(pos, width), expected);
+        assert_eq!(b.slice(pos..).get_bits(0, width), expected);
+
+        let value = if width == 64 {
+            rng.gen::<usize>()
+        } else {
+            rng.gen_range(0..1 << width)
+        };
+        b.set_bits(pos, width, value);
+        for i in 0..width {
+            bits[pos + i] = (value >> i) & 1 != 0;
+        }
+        assert_eq!(b.get_bits(pos, width), value);
+    }
+    assert_eq!(b.iter().collect::<Vec<_>>(), bits);
+
+    // Bits outside the bit vector are not modified
+    let mut b = unsafe { BitVec::from_raw_parts(vec![usize::MAX; 2], 100) };
+    b.set_bits(60, 40, 0);
+    assert_eq!(b.count_ones(), 60);
+    assert_eq!(b.into_raw_parts().0[1], !((1 << 36) - 1));
+}
+
+#[test]
+fn test_copy_from_slice() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1000;
+    let src = random_bit_vec(len, &mut rng);
+    let mut dst = random_bit_vec(len, &mut rng);
+    let mut bits = dst.iter().collect::<Vec<_>>();
+    for _ in 0..1000 {
+        let src_start = rng.gen_range(0..=len);
+        let src_end = rng.gen_range(src_start..=len);
+        let dst_start = rng.gen_range(0..=len - (src_end - src_start));
+        let dst_end = dst_start + src_end - src_start;
+        dst.copy_from_slice(dst_start..dst_end, &src.slice(src_start..src_end));
+        for i in 0..src_end - src_start {
+            bits[dst_start + i] = src[src_start + i];
+        }
+        assert_eq!(dst.iter().collect::<Vec<_>>(), bits);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_slice_out_of_bounds() {
+    let b = BitVec::new(10);
+    b.slice(5..11);
+}
+
+#[test]
+#[should_panic]
+fn test_slice_index_out_of_bounds() {
+    let b = BitVec::new(10);
+    let _ = b.slice(5..10)[5];
+}
+
+#[test]
+#[should_panic]
+fn test_get_bits_out_of_bounds() {
+    let b = BitVec::new(100);
+    b.get_bits(90, 11);
+}
+
+#[test]
+#[should_panic]
+fn test_set_bits_value_too_large() {
+    let mut b = BitVec::new(100);
+    b.set_bits(10, 3, 8);
+}
+
+#[test]
+#[should_panic]
+fn test_copy_from_slice_different_lengths() {
+    let src = BitVec::new(100);
+    let mut dst = BitVec::new(100);
+    dst.copy_from_slice(0..10, &src.slice(0..11));
+}
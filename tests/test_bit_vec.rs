@@ -274,6 +274,112 @@ fn test_atomic_flip() {
     }
 }
 
+/// Returns a random bit vector and a copy with all bits of the backend
+/// outside the bit vector set.
+fn random_bit_vecs(rng: &mut SmallRng, len: usize) -> (BitVec, BitVec) {
+    let bits = (0..len)
+        .map(|_| rng.next_u32().is_multiple_of(2))
+        .collect::<BitVec>();
+    let (mut words, _) = bits.clone().into_raw_parts();
+    if !len.is_multiple_of(usize::BITS as usize) {
+        *words.last_mut().unwrap() |= usize::MAX << (len % usize::BITS as usize);
+    }
+    words.push(usize::MAX);
+    (bits, unsafe { BitVec::from_raw_parts(words, len) })
+}
+
+/// Checks that the bits of the backend outside the bit vector are still set.
+fn assert_dirty(bits: BitVec) {
+    let (words, len) = bits.into_raw_parts();
+    let residual = len % usize::BITS as usize;
+    if residual != 0 {
+        assert_eq!(
+            words[len / usize::BITS as usize] >> residual,
+            usize::MAX >> residual
+        );
+    }
+    assert_eq!(*words.last().unwrap(), usize::MAX);
+}
+
+#[test]
+fn test_set_algebra() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 64, 65, 100, 127, 128, 1000] {
+        let (a, dirty_a) = random_bit_vecs(&mut rng, len);
+        let (b, dirty_b) = random_bit_vecs(&mut rng, len);
+
+        let check = |result: &BitVec, op: fn(bool, bool) -> bool| {
+            assert_eq!(result.len(), len);
+            for i in 0..len {
+                assert_eq!(result[i], op(a[i], b[i]), "{}", i);
+            }
+            assert_eq!(
+                result.count_ones(),
+                (0..len).filter(|&i| op(a[i], b[i])).count()
+            );
+        };
+
+        // Allocating operations and operators
+        check(&a.and(&b), |x, y| x & y);
+        check(&a.or(&b), |x, y| x | y);
+        check(&a.xor(&b), |x, y| x ^ y);
+        check(&a.and_not(&b), |x, y| x & !y);
+        check(&a.not(), |x, _| !x);
+        check(&(&a & &b), |x, y| x & y);
+        check(&(&a | &b), |x, y| x | y);
+        check(&(&a ^ &b), |x, y| x ^ y);
+        check(&!&a, |x, _| !x);
+        check(&dirty_a.and(&dirty_b), |x, y| x & y);
+        check(&dirty_a.not(), |x, _| !x);
+        // Bits outside the bit vector of the result are clean
+        assert_eq!(dirty_a.or(&dirty_b).as_ref(), a.or(&b).as_ref());
+
+        // In-place operations and operators
+        let mut c = dirty_a.clone();
+        c.and_assign(&dirty_b);
+        check(&c, |x, y| x & y);
+        let mut c = dirty_a.clone();
+        c |= &b;
+        check(&c, |x, y| x | y);
+        let mut c = dirty_a.clone();
+        c ^= &dirty_b;
+        check(&c, |x, y| x ^ y);
+        let mut c = dirty_a.clone();
+        c &= &b;
+        check(&c, |x, y| x & y);
+        let mut c = dirty_a.clone();
+        c.and_not_assign(&dirty_b);
+        check(&c, |x, y| x & !y);
+        // Bits outside the bit vector are not modified
+        assert_dirty(c);
+
+        // Atomic operations
+        for (op, f) in [
+            (
+                AtomicBitVec::fetch_or::<Vec<usize>> as fn(&_, &_, _),
+                (|x, y| x | y) as fn(bool, bool) -> bool,
+            ),
+            (AtomicBitVec::fetch_and::<Vec<usize>>, |x, y| x & y),
+            (AtomicBitVec::fetch_xor::<Vec<usize>>, |x, y| x ^ y),
+            (AtomicBitVec::fetch_and_not::<Vec<usize>>, |x, y| x & !y),
+        ] {
+            let c: AtomicBitVec = dirty_a.clone().into();
+            op(&c, &dirty_b, Ordering::Relaxed);
+            let c: BitVec = c.into();
+            check(&c, f);
+            assert_dirty(c);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_set_algebra_different_lengths() {
+    let a = BitVec::new(10);
+    let b = BitVec::new(11);
+    let _ = &a & &b;
+}
+
 #[test]
 fn test_iter() {
     let mut c = BitVec::new(100);
@@ -467,20 +573,108 @@ fn test_from() {
 #[test]
 fn test_iter_ones_zeros() {
     // Exit on bit found beyond bit length (diry vector)
-    let v = unsafe { BitVec::from_raw_parts(vec![1 << 63], 10) };
+    let v = unsafe { BitVec::from_raw_parts(vec![1_usize << 63], 10) };
     assert_eq!(v.iter_ones().next(), None);
 
-    let v = unsafe { BitVec::from_raw_parts(vec![!(1 << 63)], 10) };
+    let v = unsafe { BitVec::from_raw_parts(vec![!(1_usize << 63)], 10) };
     assert_eq!(v.iter_zeros().next(), None);
 
     // Exit on last word
-    let v = unsafe { BitVec::from_raw_parts(vec![0], 10) };
+    let v = unsafe { BitVec::from_raw_parts(vec![0_usize], 10) };
     assert_eq!(v.iter_ones().next(), None);
 
-    let v = unsafe { BitVec::from_raw_parts(vec![!0], 10) };
+    let v = unsafe { BitVec::from_raw_parts(vec![!0_usize], 10) };
     assert_eq!(v.iter_zeros().next(), None);
 }
 
+#[test]
+fn test_next_prev() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 200, 1000] {
+        for density in [1, 2, 100] {
+            let b = (0..len)
+                .map(|_| rng.next_u32() % density == 0)
+                .collect::<BitVec>();
+            // Ones and zeros outside the bit vector must be ignored
+            let (mut ones, _) = b.clone().into_raw_parts();
+            ones.iter_mut().for_each(|w| *w = !*w);
+            ones.push(usize::MAX);
+            let flipped = unsafe { BitVec::from_raw_parts(ones, len) };
+
+            let bits = b.iter().collect::<Vec<_>>();
+            for pos in 0..len + 70 {
+                let next = |v| (pos..len).find(|&i| bits[i] == v);
+                let prev = |v| (0..len.min(pos + 1)).rev().find(|&i| bits[i] == v);
+                assert_eq!(b.next_one(pos), next(true));
+                assert_eq!(b.next_zero(pos), next(false));
+                assert_eq!(b.prev_one(pos), prev(true));
+                assert_eq!(b.prev_zero(pos), prev(false));
+                assert_eq!(flipped.next_one(pos), next(false));
+                assert_eq!(flipped.next_zero(pos), next(true));
+                assert_eq!(flipped.prev_one(pos), prev(false));
+                assert_eq!(flipped.prev_zero(pos), prev(true));
+                assert_eq!(
+                    b.iter_ones_from(pos).collect::<Vec<_>>(),
+                    (pos..len).filter(|&i| bits[i]).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    flipped.iter_zeros_from(pos).collect::<Vec<_>>(),
+                    (pos..len).filter(|&i| bits[i]).collect::<Vec<_>>()
+                );
+            }
+            assert_eq!(b.prev_one(usize::MAX), bits.iter().rposition(|&b| b));
+            assert_eq!(b.prev_zero(usize::MAX), bits.iter().rposition(|&b| !b));
+        }
+    }
+}
+
+#[test]
+fn test_iter_ones_zeros_rev() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 200, 1000] {
+        let (clean, dirty) = random_bit_vecs(&mut rng, len);
+        let ones = clean.iter_ones().collect::<Vec<_>>();
+        let zeros = clean.iter_zeros().collect::<Vec<_>>();
+        assert_eq!(dirty.iter_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(
+            dirty.iter_ones().rev().collect::<Vec<_>>(),
+            ones.iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            clean.iter_zeros().rev().collect::<Vec<_>>(),
+            zeros.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        // Alternate between the two ends
+        let mut iter = dirty.iter_ones();
+        let mut front = 0;
+        let mut back = ones.len();
+        loop {
+            let next = if rng.next_u32() % 2 == 0 {
+                let next = iter.next();
+                if next.is_some() {
+                    assert_eq!(next, Some(ones[front]));
+                    front += 1;
+                }
+                next
+            } else {
+                let next = iter.next_back();
+                if next.is_some() {
+                    back -= 1;
+                    assert_eq!(next, Some(ones[back]));
+                }
+                next
+            };
+            if next.is_none() {
+                assert_eq!(front, back);
+                break;
+            }
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
+
 #[test]
 fn test_macro() {
     // Empty bit vector
@@ -513,3 +707,108 @@ fn test_macro() {
     assert_eq!(b[4], false);
     assert_eq!(b[5], false);
 }
+
+fn check_words<W: Word>() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 7, 8, 9, 63, 64, 65, 127, 128, 129, 1000] {
+        let bits = (0..len)
+            .map(|_| rng.next_u64() % 3 == 0)
+            .collect::<Vec<_>>();
+        let a = bits.iter().copied().collect::<BitVec>();
+        let mut b = bits.iter().copied().collect::<BitVec<Vec<W>>>();
+        assert_eq!(b.len(), len);
+        assert_eq!(b.as_ref().len(), len.div_ceil(W::BITS));
+        assert_eq!(b.count_ones(), a.count_ones());
+        assert_eq!(b.iter().collect::<Vec<_>>(), bits);
+        assert_eq!(
+            b.iter_ones().collect::<Vec<_>>(),
+            a.iter_ones().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            b.iter_zeros().rev().collect::<Vec<_>>(),
+            a.iter_zeros().rev().collect::<Vec<_>>()
+        );
+        for i in 0..len {
+            assert_eq!(b[i], bits[i]);
+            assert_eq!(b.next_one(i), a.next_one(i));
+            assert_eq!(b.prev_zero(i), a.prev_zero(i));
+        }
+        for r in 0..a.count_ones() {
+            assert_eq!(unsafe { b.select_hinted(r, 0, 0) }, unsafe {
+                a.select_hinted(r, 0, 0)
+            });
+        }
+        assert_eq!(b.to_string(), a.to_string());
+
+        let c = !&b;
+        assert_eq!(c.count_ones(), len - a.count_ones());
+        assert_eq!(b.and(&c).count_ones(), 0);
+        b ^= &c;
+        assert_eq!(b.count_ones(), len);
+        b.flip();
+        assert_eq!(b.count_ones(), 0);
+
+        for _ in 0..10 {
+            b.push(true);
+        }
+        b.resize(len + 20, false);
+        assert_eq!(b.count_ones(), 10);
+        assert_eq!(b.pop(), Some(false));
+        assert_eq!(b.len(), len + 19);
+    }
+}
+
+#[test]
+fn test_words() {
+    check_words::<u8>();
+    check_words::<u16>();
+    check_words::<u32>();
+    check_words::<u64>();
+    check_words::<u128>();
+    check_words::<usize>();
+}
+
+#[test]
+fn test_atomic_words() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1000;
+    let bits = (0..len)
+        .map(|_| rng.next_u64() % 2 == 0)
+        .collect::<BitVec<Vec<u32>>>();
+    let mut a: AtomicBitVec<Vec<core::sync::atomic::AtomicU32>> = bits.clone().into();
+    assert_eq!(a.count_ones(), bits.count_ones());
+    assert_eq!(
+        a.iter().collect::<Vec<_>>(),
+        bits.iter().collect::<Vec<_>>()
+    );
+
+    a.flip(Ordering::Relaxed);
+    for i in 0..len {
+        assert_eq!(a.get(i, Ordering::Relaxed), !bits[i]);
+    }
+    a.fetch_xor(&bits, Ordering::Relaxed);
+    assert_eq!(a.count_ones(), len);
+    assert!(a.swap(10, false, Ordering::Relaxed));
+    assert!(!a.get(10, Ordering::Relaxed));
+    a.fill(false, Ordering::Relaxed);
+    a.fetch_or(&bits, Ordering::Relaxed);
+
+    let b: BitVec<Vec<u32>> = a.into();
+    assert_eq!(b, bits);
+}
+
+#[test]
+fn test_epserde_words() -> anyhow::Result<()> {
+    let b = (0..100).map(|i| i % 3 == 0).collect::<BitVec<Vec<u32>>>();
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_bit_vec_u32.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    b.serialize(&mut file)?;
+    drop(file);
+
+    let c = <BitVec<Vec<u32>>>::load_full(&tmp_file)?;
+    assert_eq!(b, c);
+    // The word type is part of the serialized type
+    assert!(<BitVec<Vec<usize>>>::load_full(&tmp_file).is_err());
+    Ok(())
+}
@@ -0,0 +1,186 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+/// Returns the balanced parentheses of a tree given by the parents of the
+/// nodes in preorder (the parent of a node precedes it).
+fn parens(parents: &[Option<usize>]) -> BitVec {
+    let n = parents.len();
+    let mut children = vec![vec![]; n];
+    let mut roots = vec![];
+    for (node, &parent) in parents.iter().enumerate() {
+        match parent {
+            Some(p) => children[p].push(node),
+            None => roots.push(node),
+        }
+    }
+    let mut bits = BitVec::new(0);
+    let mut stack = roots
+        .into_iter()
+        .rev()
+        .map(|r| (r, false))
+        .collect::<Vec<_>>();
+    while let Some((node, closing)) = stack.pop() {
+        if closing {
+            bits.push(false);
+        } else {
+            bits.push(true);
+            stack.push((node, true));
+            stack.extend(children[node].iter().rev().map(|&c| (c, false)));
+        }
+    }
+    bits
+}
+
+/// Random parents such that the preorder visit of the tree is the
+/// identity: the parent of node `i` is on the rightmost path of the tree
+/// formed by nodes `[0..i)`.
+fn random_parents(
+    n: usize,
+    rng: &mut SmallRng,
+    depth_bias: f64,
+    forest: bool,
+) -> Vec<Option<usize>> {
+    let mut parents = Vec::with_capacity(n);
+    let mut path: Vec<usize> = vec![];
+    for node in 0..n {
+        if !path.is_empty() && !rng.gen_bool(depth_bias) {
+            let keep = rng.gen_range(if forest { 0 } else { 1 }..=path.len());
+            path.truncate(keep);
+        }
+        if !forest && node > 0 && path.is_empty() {
+            path.push(0);
+        }
+        parents.push(path.last().copied());
+        path.push(node);
+    }
+    parents
+}
+
+fn check(parents: &[Option<usize>], rng: &mut SmallRng) {
+    let n = parents.len();
+    let bits = parens(parents);
+    let tree = BpTree::new(bits.clone());
+    assert_eq!(tree.num_nodes(), n);
+
+    // Positions of the open parentheses, i.e., the node identifiers
+    let open = (0..2 * n).filter(|&i| bits[i]).collect::<Vec<_>>();
+    let mut close = vec![0; n];
+    let mut stack = vec![];
+    for (i, bit) in bits.into_iter().enumerate() {
+        if bit {
+            stack.push(tree.preorder_rank(i));
+        } else {
+            close[stack.pop().unwrap()] = i;
+        }
+    }
+    let mut depth = vec![0; n];
+    for i in 0..n {
+        depth[i] = parents[i].map_or(0, |p| depth[p] + 1);
+    }
+    let lca = |mut u: usize, mut v: usize| {
+        while depth[u] > depth[v] {
+            u = parents[u].unwrap();
+        }
+        while depth[v] > depth[u] {
+            v = parents[v].unwrap();
+        }
+        while u != v {
+            u = parents[u]?;
+            v = parents[v]?;
+        }
+        Some(u)
+    };
+
+    for i in 0..n {
+        let node = open[i];
+        assert_eq!(tree.preorder_rank(node), i);
+        assert_eq!(tree.preorder_select(i), Some(node));
+        assert_eq!(tree.find_close(node), close[i]);
+        assert_eq!(tree.find_open(close[i]), node);
+        assert_eq!(tree.parent(node), parents[i].map(|p| open[p]));
+        assert_eq!(tree.depth(node), depth[i]);
+        assert_eq!(tree.subtree_size(node), (close[i] - node) / 2 + 1);
+
+        let first_child = (i + 1 < n && parents[i + 1] == Some(i)).then(|| open[i + 1]);
+        assert_eq!(tree.first_child(node), first_child);
+        assert_eq!(tree.is_leaf(node), first_child.is_none());
+        let next_sibling = (close[i] + 1 < 2 * n && bits[close[i] + 1]).then(|| close[i] + 1);
+        assert_eq!(tree.next_sibling(node), next_sibling);
+    }
+    assert_eq!(tree.preorder_select(n), None);
+
+    for _ in 0..n.min(1000) {
+        let u = rng.gen_range(0..n);
+        let v = rng.gen_range(0..n);
+        let lca = lca(u, v);
+        assert_eq!(tree.lca(open[u], open[v]), lca.map(|a| open[a]));
+        assert_eq!(tree.is_ancestor(open[u], open[v]), lca == Some(u));
+    }
+}
+
+#[test]
+fn test_bp_tree() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [1, 2, 3, 10, 100, 1000, 10_000] {
+        for depth_bias in [0.0, 0.5, 0.9, 0.999, 1.0] {
+            let parents = random_parents(n, &mut rng, depth_bias, false);
+            check(&parents, &mut rng);
+        }
+    }
+}
+
+#[test]
+fn test_bp_forest() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [1, 10, 1000, 10_000] {
+        for depth_bias in [0.0, 0.5, 0.99] {
+            let parents = random_parents(n, &mut rng, depth_bias, true);
+            check(&parents, &mut rng);
+        }
+    }
+}
+
+#[test]
+fn test_bp_empty() {
+    let tree = BpTree::new(BitVec::new(0));
+    assert_eq!(tree.num_nodes(), 0);
+    assert_eq!(tree.preorder_select(0), None);
+}
+
+#[test]
+fn test_bp_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let parents = random_parents(10_000, &mut rng, 0.9, false);
+    let tree = BpTree::new(parens(&parents));
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_bp_tree.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    tree.serialize(&mut file)?;
+    drop(file);
+    let t = <BpTree>::load_full(&tmp_file)?;
+    for node in (0..20_000).filter(|&i| t.preorder_rank(i + 1) > t.preorder_rank(i)) {
+        assert_eq!(t.find_close(node), tree.find_close(node));
+        assert_eq!(t.parent(node), tree.parent(node));
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_bp_unbalanced() {
+    BpTree::new(sux::bit_vec![1, 0, 0, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_bp_unclosed() {
+    BpTree::new(sux::bit_vec![1, 1, 0]);
+}
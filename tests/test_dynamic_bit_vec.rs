@@ -0,0 +1,110 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use mem_dbg::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+/// Checks a structure against a vector of booleans using only the
+/// rank/select traits.
+fn check<T: Rank + RankZero + Select + SelectZero>(bits: &T, model: &[bool]) {
+    let num_ones = model.iter().filter(|&&b| b).count();
+    assert_eq!(bits.len(), model.len());
+    assert_eq!(bits.num_ones(), num_ones);
+    assert_eq!(bits.num_zeros(), model.len() - num_ones);
+
+    let mut ones = 0;
+    for (i, &bit) in model.iter().enumerate() {
+        assert_eq!(bits.rank(i), ones);
+        assert_eq!(bits.rank_zero(i), i - ones);
+        if bit {
+            assert_eq!(bits.select(ones), Some(i));
+            ones += 1;
+        } else {
+            assert_eq!(bits.select_zero(i - ones), Some(i));
+        }
+    }
+    assert_eq!(bits.rank(model.len()), num_ones);
+    assert_eq!(bits.select(num_ones), None);
+    assert_eq!(bits.select_zero(model.len() - num_ones), None);
+}
+
+#[test]
+fn test_dynamic_bit_vec() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut bits = DynamicBitVec::new();
+    let mut model = Vec::<bool>::new();
+
+    // Grow, shrink to empty, and grow again
+    for (ops, insert_prob) in [(100_000, 0.8), (150_000, 0.2), (50_000, 0.6)] {
+        for _ in 0..ops {
+            let op = rng.gen_range(0.0..1.0);
+            if model.is_empty() || op < insert_prob {
+                let pos = rng.gen_range(0..=model.len());
+                let bit = rng.gen_bool(0.5);
+                bits.insert(pos, bit);
+                model.insert(pos, bit);
+            } else if op < insert_prob + (1.0 - insert_prob) * 0.8 {
+                let pos = rng.gen_range(0..model.len());
+                assert_eq!(bits.remove(pos), model.remove(pos));
+            } else {
+                let pos = rng.gen_range(0..model.len());
+                let bit = rng.gen_bool(0.5);
+                bits.set(pos, bit);
+                model[pos] = bit;
+            }
+        }
+        for (i, &bit) in model.iter().enumerate() {
+            assert_eq!(bits[i], bit);
+        }
+        check(&bits, &model);
+    }
+}
+
+#[test]
+fn test_push_pop() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for density in [0.0, 0.1, 0.5, 0.9, 1.0] {
+        let model = (0..50_000)
+            .map(|_| rng.gen_bool(density))
+            .collect::<Vec<_>>();
+        let mut bits = model.iter().copied().collect::<DynamicBitVec>();
+        check(&bits, &model);
+
+        let bit_vec = model.iter().copied().collect::<BitVec>();
+        let from = DynamicBitVec::from(&bit_vec);
+        check(&from, &model);
+
+        for &bit in model.iter().rev() {
+            assert_eq!(bits.pop(), Some(bit));
+        }
+        assert_eq!(bits.pop(), None);
+        assert_eq!(bits.len(), 0);
+    }
+}
+
+#[test]
+fn test_empty() {
+    let bits = DynamicBitVec::new();
+    check(&bits, &[]);
+    assert!(bits.mem_size(SizeFlags::default()) > 0);
+}
+
+#[test]
+#[should_panic]
+fn test_insert_out_of_bounds() {
+    let mut bits = DynamicBitVec::new();
+    bits.push(true);
+    bits.insert(2, false);
+}
+
+#[test]
+#[should_panic]
+fn test_remove_out_of_bounds() {
+    let mut bits = DynamicBitVec::new();
+    bits.push(true);
+    bits.remove(1);
+}
@@ -8,6 +8,7 @@
 #![allow(clippy::type_complexity)]
 use anyhow::Result;
 use epserde::prelude::*;
+use mem_dbg::*;
 use rand::rngs::SmallRng;
 use rand::Rng;
 use rand::SeedableRng;
@@ -170,11 +171,94 @@ fn test_elias_fano() -> Result<()> {
         for upper_bound in 0..first {
             assert_eq!(None, ef.pred(upper_bound));
         }
+
+        // Values beyond the upper bound
+        let last_index = values.len() - 1;
+        let expected = Some((last_index, values[last_index]));
+        assert_eq!(expected, ef.pred(u + 1));
+        assert_eq!(expected, ef.pred_strict(u + 1));
+        assert_eq!(expected, ef.pred(usize::MAX));
     }
 
     Ok(())
 }
 
+#[test]
+fn test_elias_fano_cursor() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (1, 10),
+        (10, 1000),
+        (100, 100),
+        (1000, 100),
+        (1000, 1_000_000),
+        (10_000, 100_000_000),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        for value in values.iter() {
+            efb.push(*value);
+        }
+        let ef = efb.build_with_seq_and_dict();
+
+        // Forward and backward scans
+        let mut cursor = ef.cursor();
+        for (i, &v) in values.iter().enumerate() {
+            assert_eq!(cursor.index(), i);
+            assert_eq!(cursor.value(), Some(v));
+            cursor.next_value();
+        }
+        assert_eq!(cursor.index(), n);
+        assert_eq!(cursor.value(), None);
+        assert_eq!(cursor.next_value(), None);
+        for i in (0..n).rev() {
+            assert_eq!(cursor.prev_value(), Some(values[i]));
+            assert_eq!(cursor.index(), i);
+        }
+        assert_eq!(cursor.prev_value(), None);
+        assert_eq!(cursor.index(), 0);
+
+        for from in [0, n / 2, n] {
+            let cursor = ef.cursor_from(from);
+            assert_eq!(cursor.index(), from);
+            assert_eq!(cursor.value(), values[from..].first().copied());
+        }
+
+        // A random walk compared with a model
+        let mut cursor = ef.cursor();
+        let mut pos = 0;
+        for _ in 0..10_000 {
+            let x = rng.gen_range(0..u + 10);
+            if rng.gen_bool(0.6) {
+                if pos == n || values[pos] < x {
+                    pos += values[pos..].partition_point(|&v| v < x);
+                }
+                assert_eq!(cursor.advance_to(x), values[pos..].first().copied());
+            } else {
+                pos = pos.min(n - 1);
+                let expected = if values[pos] <= x {
+                    Some(values[pos])
+                } else {
+                    match values[..pos].partition_point(|&v| v <= x) {
+                        0 => {
+                            pos = 0;
+                            None
+                        }
+                        p => {
+                            pos = p - 1;
+                            Some(values[pos])
+                        }
+                    }
+                };
+                assert_eq!(cursor.retreat_to(x), expected);
+            }
+            assert_eq!(cursor.index(), pos);
+        }
+    }
+    Ok(())
+}
+
 #[test]
 #[should_panic]
 fn test_too_many_values() {
@@ -233,3 +317,113 @@ fn test_epserde() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_elias_fano_streaming() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 0),
+        (1, 10),
+        (100, 100),
+        (1000, 100),
+        (10_000, 1_000_000),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        values.sort();
+
+        let mut efsb = EliasFanoStreamingBuilder::new()?;
+        efsb.extend(values.iter().copied())?;
+        assert_eq!(efsb.len(), n);
+        let ef = efsb.build_with_seq_and_dict()?;
+
+        // Same structure obtained knowing n and u in advance
+        let mut efb = EliasFanoBuilder::new(n, values.last().copied().unwrap_or(0));
+        for &value in &values {
+            efb.push(value);
+        }
+        let expected = efb.build_with_seq_and_dict();
+        assert_eq!(
+            ef.mem_size(SizeFlags::default()),
+            expected.mem_size(SizeFlags::default())
+        );
+
+        assert_eq!(ef.len(), n);
+        assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), value);
+        }
+        if let Some(&last) = values.last() {
+            assert_eq!(ef.succ(last).map(|(_, v)| v), Some(last));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_streaming_non_monotone() {
+    let mut efsb = EliasFanoStreamingBuilder::new().unwrap();
+    efsb.push(1).unwrap();
+    efsb.push(0).unwrap();
+}
+
+#[test]
+fn test_elias_fano_batch() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 0),
+        (1, 10),
+        (100, 100),
+        (1000, 100),
+        (10_000, 1_000_000),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        for &value in &values {
+            efb.push(value);
+        }
+        let ef = efb.build_with_seq_and_dict();
+
+        let mut queries = (0..1000)
+            .map(|_| rng.gen_range(0..=u + 10))
+            .collect::<Vec<_>>();
+        queries.extend(values.iter().step_by(3).copied());
+        let succ = queries.iter().map(|&x| ef.succ(x)).collect::<Vec<_>>();
+        let pred = queries.iter().map(|&x| ef.pred(x)).collect::<Vec<_>>();
+        // Unsorted batches
+        assert_eq!(ef.succ_batch(&queries), succ);
+        assert_eq!(ef.pred_batch(&queries), pred);
+
+        queries.sort();
+        let succ = queries.iter().map(|&x| ef.succ(x)).collect::<Vec<_>>();
+        let pred = queries.iter().map(|&x| ef.pred(x)).collect::<Vec<_>>();
+        assert_eq!(ef.succ_batch(&queries), succ);
+        assert_eq!(ef.pred_batch(&queries), pred);
+        assert_eq!(ef.succ_sorted(&queries).collect::<Vec<_>>(), succ);
+        assert_eq!(ef.pred_sorted(queries.clone()).collect::<Vec<_>>(), pred);
+    }
+
+    // Values at the end of the range
+    let mut efb = EliasFanoBuilder::new(3, usize::MAX);
+    for value in [0, usize::MAX, usize::MAX] {
+        efb.push(value);
+    }
+    let ef = efb.build_with_seq_and_dict();
+    let queries = [0, 1, usize::MAX - 1, usize::MAX];
+    let pred = queries.iter().map(|&x| ef.pred(x)).collect::<Vec<_>>();
+    assert_eq!(ef.pred_sorted(queries).collect::<Vec<_>>(), pred);
+    let succ = queries.iter().map(|&x| ef.succ(x)).collect::<Vec<_>>();
+    assert_eq!(ef.succ_sorted(queries).collect::<Vec<_>>(), succ);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_succ_sorted_non_monotone() {
+    let mut efb = EliasFanoBuilder::new(2, 10);
+    efb.push(0);
+    efb.push(5);
+    let ef = efb.build_with_seq_and_dict();
+    ef.succ_sorted([3, 1]).for_each(drop);
+}
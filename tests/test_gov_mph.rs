@@ -0,0 +1,65 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_progress_logger::ProgressLogger;
+use epserde::prelude::*;
+use sux::func::{GovMph, GovMphBuilder};
+use sux::utils::{FromIntoIterator, LineLender};
+
+#[test]
+fn test_gov_mph() -> Result<()> {
+    for n in [0, 1, 10, 1000, 100_000, 200_000] {
+        let mph = GovMphBuilder::<usize>::default().build(
+            FromIntoIterator::from(0..n),
+            &mut Option::<ProgressLogger>::None,
+        )?;
+        assert_eq!(mph.len(), n);
+        let mut seen = vec![false; n];
+        for i in 0..n {
+            let h = mph.get(&i);
+            assert!(h < n);
+            assert!(!seen[h]);
+            seen[h] = true;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_gov_mph_strings() -> Result<()> {
+    let mph = GovMphBuilder::<str>::default().seed(42).build(
+        LineLender::from_path("tests/data/wordlist.10000")?,
+        &mut Option::<ProgressLogger>::None,
+    )?;
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_gov_mph.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    mph.serialize(&mut file)?;
+    drop(file);
+    let mapped = <GovMph<str>>::mmap(&tmp_file, epserde::deser::Flags::empty())?;
+
+    let words = std::fs::read_to_string("tests/data/wordlist.10000")?;
+    let mut seen = vec![false; mph.len()];
+    for word in words.lines() {
+        let h = mph.get(word);
+        assert_eq!(mapped.get(word), h);
+        assert!(!seen[h]);
+        seen[h] = true;
+    }
+    assert!(seen.iter().all(|&b| b));
+    Ok(())
+}
+
+#[test]
+fn test_gov_mph_duplicates() {
+    assert!(GovMphBuilder::<usize>::default()
+        .build(
+            FromIntoIterator::from([0, 1, 2, 1]),
+            &mut Option::<ProgressLogger>::None,
+        )
+        .is_err());
+}
@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+/// Random strings over a small alphabet, so that they share many prefixes.
+fn random_strings(n: usize, rng: &mut SmallRng) -> Vec<String> {
+    const ALPHABET: [char; 5] = ['a', 'b', 'c', 'è', '€'];
+    let mut strings = (0..n)
+        .map(|_| {
+            let len = rng.gen_range(0..12);
+            (0..len)
+                .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())])
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    strings.sort();
+    strings.dedup();
+    strings
+}
+
+fn build(strings: &[String]) -> LoudsTrie {
+    let mut builder = LoudsTrieBuilder::new();
+    for s in strings {
+        builder.push(s);
+    }
+    builder.build()
+}
+
+#[test]
+fn test_louds_trie() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [0, 1, 2, 10, 100, 1000, 10_000] {
+        let strings = random_strings(n, &mut rng);
+        let trie = build(&strings);
+
+        let mut rclb = RearCodedListBuilder::new(8);
+        for s in &strings {
+            rclb.push(s);
+        }
+        let rcl = rclb.build();
+
+        assert_eq!(trie.len(), strings.len());
+        for (i, s) in strings.iter().enumerate() {
+            assert_eq!(&trie.get(i), s);
+            assert_eq!(trie.get(i), rcl.get(i));
+            assert_eq!(trie.index_of(s.as_str()), Some(i));
+        }
+        assert_eq!(trie.iter().collect::<Vec<_>>(), strings);
+        assert_eq!((&trie).into_iter().count(), strings.len());
+
+        for query in random_strings(1000, &mut rng) {
+            let start = strings.partition_point(|s| s < &query);
+            let end = start + strings[start..].partition_point(|s| s.starts_with(&query));
+            assert_eq!(trie.prefix_range(&query), start..end, "{:?}", query);
+            assert_eq!(
+                trie.iter_prefix(&query).collect::<Vec<_>>(),
+                &strings[start..end]
+            );
+            let contained = strings.binary_search(&query).ok();
+            assert_eq!(trie.index_of(query.as_str()), contained);
+        }
+    }
+}
+
+#[test]
+fn test_louds_trie_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let strings = random_strings(1000, &mut rng);
+    let trie = build(&strings);
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_louds_trie.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    trie.serialize(&mut file)?;
+    drop(file);
+    let t = <LoudsTrie>::load_full(&tmp_file)?;
+    for (i, s) in strings.iter().enumerate() {
+        assert_eq!(&t.get(i), s);
+        assert_eq!(t.index_of(s.as_str()), Some(i));
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_louds_trie_unsorted() {
+    let mut builder = LoudsTrieBuilder::new();
+    builder.push("b");
+    builder.push("a");
+}
+
+#[test]
+#[should_panic]
+fn test_louds_trie_duplicate() {
+    let mut builder = LoudsTrieBuilder::new();
+    builder.push("a");
+    builder.push("a");
+}
@@ -0,0 +1,241 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use common_traits::CastableFrom;
+use core::sync::atomic::Ordering;
+use epserde::prelude::*;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+#[test]
+fn test_bit_vec() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let tmp_file = std::env::temp_dir().join("test_mmap_bit_vec.bin");
+    for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+        let mut b = BitVec::new_mmap(&tmp_file, len)?;
+        let mut c = BitVec::new(len);
+        assert_eq!(b.len(), len);
+        assert_eq!(b.count_ones(), 0);
+        for _ in 0..len / 3 {
+            let i = rng.gen_range(0..len);
+            b.set(i, true);
+            c.set(i, true);
+        }
+        assert_eq!(b, c);
+        b.flush()?;
+
+        let d = <BitVec<Vec<usize>>>::mmap(&tmp_file, Flags::empty())?;
+        assert_eq!(*d, c);
+        let d = <BitVec<Vec<usize>>>::load_full(&tmp_file)?;
+        assert_eq!(d, c);
+
+        // The vector can be modified and flushed again
+        if len > 0 {
+            b.flip();
+            c.flip();
+            b.flush()?;
+            let d = <BitVec<Vec<usize>>>::load_full(&tmp_file)?;
+            assert_eq!(d, c);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_atomic_bit_vec() -> Result<()> {
+    let tmp_file = std::env::temp_dir().join("test_mmap_atomic_bit_vec.bin");
+    let len = 10_000;
+    let b: AtomicBitVec<_> = BitVec::new_mmap(&tmp_file, len)?.into();
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let b = &b;
+            s.spawn(move || {
+                for i in (3 * t..len).step_by(4 * 3) {
+                    b.set(i, true, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    let mut b: BitVec<MmapBackend> = b.into();
+    b.flush()?;
+
+    let d = <BitVec<Vec<usize>>>::load_full(&tmp_file)?;
+    assert_eq!(d.count_ones(), len.div_ceil(3));
+    for i in 0..len {
+        assert_eq!(d[i], i % 3 == 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bit_field_vec() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let tmp_file = std::env::temp_dir().join("test_mmap_bit_field_vec.bin");
+    for bit_width in [0, 1, 7, 31, 63, 64] {
+        for len in [0, 1, 10, 1000] {
+            let mut b = <BitFieldVec<usize, MmapBackend>>::new_mmap(&tmp_file, bit_width, len)?;
+            let mut c = <BitFieldVec>::new(bit_width, len);
+            let mask = if bit_width == 0 {
+                0
+            } else {
+                usize::MAX >> (64 - bit_width)
+            };
+            for i in 0..len {
+                let value = rng.gen::<usize>() & mask;
+                b.set(i, value);
+                c.set(i, value);
+            }
+            b.flush()?;
+
+            let d = <BitFieldVec<usize, Vec<usize>>>::mmap(&tmp_file, Flags::empty())?;
+            assert_eq!(d.bit_width(), bit_width);
+            assert_eq!(d.len(), len);
+            for i in 0..len {
+                assert_eq!(d.get(i), c.get(i));
+            }
+        }
+    }
+
+    // Words other than usize
+    let mut b = <BitFieldVec<u16, MmapBackend<u16>>>::new_mmap(&tmp_file, 5, 101)?;
+    for i in 0..101 {
+        b.set(i, i as u16 % 32);
+    }
+    b.flush()?;
+    let d = <BitFieldVec<u16, Vec<u16>>>::load_full(&tmp_file)?;
+    for i in 0..101 {
+        assert_eq!(d.get(i), i as u16 % 32);
+    }
+    Ok(())
+}
+
+/// Checks that the file written by a flush is exactly the ε-serde
+/// serialization of the corresponding `Vec`-based structure.
+fn check_layout(path: &std::path::Path, expected: &impl Serialize) -> Result<()> {
+    let mut bytes = Vec::new();
+    expected.serialize(&mut bytes)?;
+    assert_eq!(std::fs::read(path)?, bytes);
+    Ok(())
+}
+
+fn check_bit_field_vec_layout<W: Word + MaxSizeOf + SerializeInner + CastableFrom<u64>>(
+    path: &std::path::Path,
+) -> Result<()>
+where
+    BitFieldVec<W>: Serialize,
+{
+    // Different bit widths and lengths yield different paddings
+    for len in [0, 1, 3, 10, 101] {
+        for bit_width in [0, 1, 5, 7] {
+            let mut b = <BitFieldVec<W, MmapBackend<W>>>::new_mmap(path, bit_width, len)?;
+            let mut c = <BitFieldVec<W>>::new(bit_width, len);
+            for i in 0..len {
+                let value = W::cast_from(i as u64 * 0x9E37_79B9) & c.mask();
+                b.set(i, value);
+                c.set(i, value);
+            }
+            b.flush()?;
+            check_layout(path, &c)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_layout() -> Result<()> {
+    let tmp_file = std::env::temp_dir().join("test_mmap_layout.bin");
+    for len in [0, 1, 3, 64, 1000] {
+        let mut b = BitVec::new_mmap(&tmp_file, len)?;
+        let mut c = BitVec::new(len);
+        for i in (0..len).step_by(3) {
+            b.set(i, true);
+            c.set(i, true);
+        }
+        b.flush()?;
+        check_layout(&tmp_file, &c)?;
+    }
+
+    // Different word types yield different headers and paddings
+    check_bit_field_vec_layout::<u8>(&tmp_file)?;
+    check_bit_field_vec_layout::<u16>(&tmp_file)?;
+    check_bit_field_vec_layout::<u32>(&tmp_file)?;
+    check_bit_field_vec_layout::<usize>(&tmp_file)?;
+    Ok(())
+}
+
+fn values(n: usize, rng: &mut SmallRng) -> Vec<usize> {
+    let mut values = (0..n)
+        .map(|_| rng.gen_range(0..n * 100))
+        .collect::<Vec<_>>();
+    values.sort();
+    values
+}
+
+#[test]
+fn test_elias_fano_builder() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let high_file = std::env::temp_dir().join("test_mmap_ef_high.bin");
+    let low_file = std::env::temp_dir().join("test_mmap_ef_low.bin");
+    for n in [1, 10, 10_000] {
+        let values = values(n, &mut rng);
+        let u = *values.last().unwrap();
+        let mut efb = EliasFanoBuilder::new_mmap(n, u, &high_file, &low_file)?;
+        let mut efb_mem = EliasFanoBuilder::new(n, u);
+        for &value in &values {
+            efb.push(value);
+            efb_mem.push(value);
+        }
+        let ef = efb.build()?;
+        let ef = unsafe { ef.map_high_bits(SelectAdaptConst::<_, _>::new) };
+        let ef_mem = efb_mem.build_with_seq();
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), value);
+            assert_eq!(ef_mem.get(i), value);
+        }
+
+        let high_bits = <BitVec<Vec<usize>>>::load_full(&high_file)?;
+        let low_bits = <BitFieldVec<usize, Vec<usize>>>::load_full(&low_file)?;
+        assert_eq!(high_bits.count_ones(), n);
+        let mask = (1 << low_bits.bit_width()) - 1;
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(low_bits.get(i), value & mask);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_elias_fano_concurrent_builder() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let high_file = std::env::temp_dir().join("test_mmap_efc_high.bin");
+    let low_file = std::env::temp_dir().join("test_mmap_efc_low.bin");
+    let n = 10_000;
+    let values = values(n, &mut rng);
+    let u = *values.last().unwrap();
+    let efcb = EliasFanoConcurrentBuilder::new_mmap(n, u, &high_file, &low_file)?;
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let (efcb, values) = (&efcb, &values);
+            s.spawn(move || {
+                for i in (t..n).step_by(4) {
+                    unsafe { efcb.set(i, values[i]) };
+                }
+            });
+        }
+    });
+    let ef = efcb.build()?;
+    let ef = unsafe { ef.map_high_bits(SelectAdaptConst::<_, _>::new) };
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(ef.get(i), value);
+    }
+
+    let high_bits = <BitVec<Vec<usize>>>::mmap(&high_file, Flags::empty())?;
+    assert_eq!(high_bits.count_ones(), n);
+    Ok(())
+}
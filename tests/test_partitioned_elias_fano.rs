@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use mem_dbg::*;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+/// Generates a sorted sequence made of clusters of different density.
+fn clustered(rng: &mut SmallRng, n: usize) -> Vec<usize> {
+    let mut values = Vec::with_capacity(n);
+    let mut value = 0;
+    while values.len() < n {
+        let len = rng.gen_range(1..1000).min(n - values.len());
+        match rng.gen_range(0..4) {
+            // A run
+            0 => values.extend(value..value + len),
+            // A dense cluster
+            1 => values.extend((0..len).map(|i| value + i * 2 + rng.gen_range(0..2))),
+            // A sparse cluster
+            2 => values.extend((0..len).map(|i| value + i * 1000 + rng.gen_range(0..1000))),
+            // Repeated values
+            _ => values.resize(values.len() + len, value),
+        }
+        value = *values.last().unwrap() + rng.gen_range(0..100_000);
+    }
+    values
+}
+
+fn build(values: &[usize], u: usize) -> PartitionedEliasFano {
+    let mut pefb = PartitionedEliasFanoBuilder::new(values.len(), u);
+    for &value in values {
+        pefb.push(value);
+    }
+    pefb.build()
+}
+
+#[test]
+fn test_partitioned_elias_fano() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [0, 1, 10, 127, 128, 129, 1000, 10_000] {
+        let values = clustered(&mut rng, n);
+        let u = values.last().copied().unwrap_or(0) + rng.gen_range(0..10);
+        let pef = build(&values, u);
+
+        assert_eq!(pef.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(pef.get(i), value);
+        }
+        assert_eq!(pef.iter().collect::<Vec<_>>(), values);
+        for from in [0, n / 3, n / 2, n] {
+            assert_eq!(pef.iter_from(from).collect::<Vec<_>>(), values[from..]);
+        }
+
+        // The index and the value at a given position, if any
+        let at = |i: usize| (i < n).then(|| (i, values[i]));
+        let mut queries = (0..1000).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        queries.extend(values.iter().step_by(7).copied());
+        for x in queries {
+            let succ = values.partition_point(|&v| v < x);
+            let succ_strict = values.partition_point(|&v| v <= x);
+            assert_eq!(pef.succ(x), at(succ));
+            assert_eq!(pef.succ_strict(x), at(succ_strict));
+            assert_eq!(pef.pred(x), succ_strict.checked_sub(1).and_then(at));
+            assert_eq!(pef.pred_strict(x), succ.checked_sub(1).and_then(at));
+            assert_eq!(
+                pef.index_of(x),
+                at(succ).filter(|&(_, v)| v == x).map(|(i, _)| i)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_partitioned_elias_fano_space() {
+    // A locally dense, globally sparse sequence
+    let values = (0..100_000)
+        .map(|i| (i / 1000) * 1_000_000_000 + i % 1000)
+        .collect::<Vec<_>>();
+    let u = *values.last().unwrap();
+    let pef = build(&values, u);
+
+    let mut efb = EliasFanoBuilder::new(values.len(), u);
+    for &value in &values {
+        efb.push(value);
+    }
+    let ef = efb.build_with_seq_and_dict();
+    assert!(pef.mem_size(SizeFlags::default()) * 4 < ef.mem_size(SizeFlags::default()));
+}
+
+#[test]
+#[should_panic]
+fn test_too_many_values() {
+    let mut pefb = PartitionedEliasFanoBuilder::new(2, 10);
+    pefb.push(0);
+    pefb.push(1);
+    pefb.push(2);
+}
+
+#[test]
+#[should_panic]
+fn test_non_monotone() {
+    let mut pefb = PartitionedEliasFanoBuilder::new(2, 10);
+    pefb.push(1);
+    pefb.push(0);
+}
+
+#[test]
+#[should_panic]
+fn test_too_large() {
+    let mut pefb = PartitionedEliasFanoBuilder::new(2, 10);
+    pefb.push(11);
+}
+
+#[test]
+fn test_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let values = clustered(&mut rng, 10_000);
+    let pef = build(&values, *values.last().unwrap());
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_pef.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    pef.serialize(&mut file)?;
+    drop(file);
+
+    let c = <PartitionedEliasFano>::mmap(&tmp_file, epserde::deser::Flags::empty())?;
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(c.get(i), value);
+    }
+    assert_eq!(c.succ(values[5000]), pef.succ(values[5000]));
+    Ok(())
+}
@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+#[test]
+fn test_prefix_sum_elias_fano() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, max) in [
+        (0, 10),
+        (1, 10),
+        (10, 1),
+        (100, 10),
+        (1000, 1000),
+        (10_000, 3),
+    ] {
+        let deltas = (0..n)
+            .map(|_| rng.gen_range(0..max))
+            .collect::<Vec<usize>>();
+        let mut prefix_sums = vec![0];
+        for &delta in &deltas {
+            prefix_sums.push(prefix_sums.last().unwrap() + delta);
+        }
+        let total = *prefix_sums.last().unwrap();
+
+        let mut psefb = PrefixSumEliasFanoBuilder::new(n, total + rng.gen_range(0..10));
+        for &delta in &deltas {
+            psefb.push(delta);
+        }
+        let psef = psefb.build();
+
+        assert_eq!(psef.len(), n);
+        assert_eq!(psef.total(), total);
+        for (i, &delta) in deltas.iter().enumerate() {
+            assert_eq!(psef.get_delta(i), delta);
+            assert_eq!(psef.get(i), delta);
+        }
+        for (i, &prefix_sum) in prefix_sums.iter().enumerate() {
+            assert_eq!(psef.prefix_sum(i), prefix_sum);
+        }
+        for value in (0..total + 10).step_by(1.max(total / 1000)) {
+            let expected = prefix_sums.partition_point(|&s| s <= value) - 1;
+            assert_eq!(psef.find(value), (expected < n).then_some(expected));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_signed_prefix_sum_elias_fano() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [0, 1, 10, 1000] {
+        let values = (0..n)
+            .map(|_| rng.gen_range(-1000..1000))
+            .collect::<Vec<isize>>();
+        let u = 2 * values.iter().map(|v| v.unsigned_abs()).sum::<usize>();
+
+        let mut spsefb = SignedPrefixSumEliasFanoBuilder::new(n, u);
+        for &value in &values {
+            spsefb.push(value);
+        }
+        let spsef = spsefb.build();
+
+        assert_eq!(spsef.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(spsef.get(i), value);
+        }
+    }
+
+    let mut spsefb = SignedPrefixSumEliasFanoBuilder::new(2, usize::MAX);
+    spsefb.push(isize::MIN);
+    spsefb.push(0);
+    let spsef = spsefb.build();
+    assert_eq!(spsef.get(0), isize::MIN);
+    assert_eq!(spsef.get(1), 0);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_too_many_values() {
+    let mut psefb = PrefixSumEliasFanoBuilder::new(2, 10);
+    psefb.push(0);
+    psefb.push(1);
+    psefb.push(2);
+}
+
+#[test]
+#[should_panic]
+fn test_sum_too_large() {
+    let mut psefb = PrefixSumEliasFanoBuilder::new(2, 10);
+    psefb.push(6);
+    psefb.push(5);
+}
+
+#[test]
+#[should_panic]
+fn test_too_few_values() {
+    let mut psefb = PrefixSumEliasFanoBuilder::new(2, 10);
+    psefb.push(6);
+    psefb.build();
+}
+
+#[test]
+fn test_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let deltas = (0..10_000)
+        .map(|_| rng.gen_range(0..100))
+        .collect::<Vec<usize>>();
+    let mut psefb = PrefixSumEliasFanoBuilder::new(deltas.len(), deltas.iter().sum());
+    for &delta in &deltas {
+        psefb.push(delta);
+    }
+    let psef = psefb.build();
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_psef.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    psef.serialize(&mut file)?;
+    drop(file);
+
+    let c = <PrefixSumEliasFano>::mmap(&tmp_file, epserde::deser::Flags::empty())?;
+    for (i, &delta) in deltas.iter().enumerate() {
+        assert_eq!(c.get_delta(i), delta);
+    }
+    assert_eq!(c.find(12345), psef.find(12345));
+    Ok(())
+}
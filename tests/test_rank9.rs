@@ -80,3 +80,23 @@ fn test_empty() {
     let inner = rank9.into_inner();
     assert_eq!(inner.len(), 0);
 }
+
+fn check_words<W: Word>() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 31, 32, 33, 511, 512, 513, 10_000] {
+        let bits = (0..len).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>();
+        let rank9 = Rank9::new(bits.iter().copied().collect::<BitVec>());
+        let rank9_w = Rank9::new(bits.iter().copied().collect::<BitVec<Vec<W>>>());
+        assert_eq!(rank9_w.num_ones(), rank9.num_ones());
+        for i in 0..=len + 1 {
+            assert_eq!(rank9_w.rank(i), rank9.rank(i));
+        }
+    }
+}
+
+#[test]
+fn test_words() {
+    check_words::<u8>();
+    check_words::<u32>();
+    check_words::<u128>();
+}
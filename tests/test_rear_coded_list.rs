@@ -150,3 +150,309 @@ fn test_rear_coded_list(path: impl AsRef<str>) -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_rear_coded_list_ranges() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut queries = words
+        .iter()
+        .flat_map(|w| (0..=w.len()).filter_map(move |i| str::get(w, ..i)))
+        .map(|w| w.to_string())
+        .collect::<Vec<_>>();
+    queries.extend(["", "~", "\u{1}", "zzzz"].map(String::from));
+    queries.extend(words.iter().map(|w| format!("{}~", w)));
+
+    for k in [1, 2, 4, 7, 16, 1000] {
+        let mut rclb = RearCodedListBuilder::new(k);
+        rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rcl = rclb.build();
+
+        for query in queries.choose_multiple(&mut rng, 500) {
+            let lower = words.partition_point(|w| w < query);
+            let upper = words.partition_point(|w| w <= query);
+            let end = lower + words[lower..].partition_point(|w| w.starts_with(query.as_str()));
+            assert_eq!(rcl.lower_bound(query), lower);
+            assert_eq!(rcl.upper_bound(query), upper);
+            assert_eq!(rcl.prefix_range(query), lower..end);
+
+            let mut lender = rcl.lend_range(lower..end);
+            assert_eq!(lender.len(), end - lower);
+            let mut i = lower;
+            while let Some(word) = lender.next() {
+                assert_eq!(word, words[i]);
+                i += 1;
+            }
+            assert_eq!(i, end);
+        }
+        assert_eq!(rcl.lend_range(words.len()..words.len()).count(), 0);
+    }
+
+    let rcl = RearCodedListBuilder::new(4).build();
+    assert_eq!(rcl.lower_bound("a"), 0);
+    assert_eq!(rcl.prefix_range("a"), 0..0);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_rear_coded_list_ranges_unsorted() {
+    let mut rclb = RearCodedListBuilder::new(4);
+    rclb.push("b");
+    rclb.push("a");
+    rclb.build().lower_bound("a");
+}
+
+#[test]
+fn test_rear_coded_list_bytes() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    // Small alphabet containing NUL, so that strings share many prefixes
+    let mut keys = (0..1000)
+        .map(|_| {
+            let len = rng.gen_range(0..10);
+            (0..len)
+                .map(|_| [0, 1, b'a', 0xFF][rng.gen_range(0..4)])
+                .collect::<Vec<u8>>()
+        })
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    for k in [1, 4, 16] {
+        let mut rclb = RearCodedListBuilder::new_bytes(k);
+        for key in &keys {
+            rclb.push(key);
+        }
+        let rcl = rclb.build();
+        assert_eq!(rcl.len(), keys.len());
+
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(&rcl.get(i), key);
+            assert_eq!(rcl.index_of(key.as_slice()), Some(i));
+            let mut key = key.clone();
+            key.push(0);
+            assert_eq!(rcl.index_of(key.as_slice()), keys.binary_search(&key).ok());
+        }
+        assert_eq!(rcl.iter().collect::<Vec<_>>(), keys);
+        for_![(i, key) in rcl.lend().enumerate() {
+            assert_eq!(key, keys[i]);
+        }];
+
+        for query in keys
+            .iter()
+            .flat_map(|key| [&key[..key.len() / 2], &key[..]])
+        {
+            let lower = keys.partition_point(|key| key.as_slice() < query);
+            let end = lower + keys[lower..].partition_point(|key| key.starts_with(query));
+            assert_eq!(rcl.prefix_range(query), lower..end);
+        }
+    }
+
+    // unsorted lists of byte strings
+    let mut shuffled = keys.clone();
+    shuffled.shuffle(&mut rng);
+    let mut rclb = RearCodedListBuilder::new_bytes(4);
+    rclb.extend(shuffled.iter().map(|s| s.as_slice()).into_lender());
+    let rcl = rclb.build();
+    for (i, key) in shuffled.iter().enumerate() {
+        assert_eq!(&rcl.get(i), key);
+        assert_eq!(rcl.index_of(key.as_slice()), Some(i));
+    }
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_rcl_bytes.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    rcl.serialize(&mut file)?;
+    drop(file);
+    let c = <RearCodedList<[u8]>>::load_full(&tmp_file)?;
+    for (i, key) in shuffled.iter().enumerate() {
+        assert_eq!(&c.get(i), key);
+    }
+    // Lists of strings and of byte strings have different types
+    assert!(<RearCodedList>::load_full(&tmp_file).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_par_extend() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 3, 4, 16] {
+        let mut rclb = RearCodedListBuilder::new(k);
+        rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let seq = rclb.build();
+        let mut seq_bytes = Vec::new();
+        seq.serialize(&mut seq_bytes)?;
+
+        for head in [0, 1, 5, 50] {
+            let mut rclb = RearCodedListBuilder::new(k);
+            rclb.extend(words[..head].iter().map(|s| s.as_str()).into_lender());
+            rclb.par_extend(&words[head..]);
+            let par = rclb.build();
+            assert_eq!(par.len(), words.len());
+            assert_eq!(par.iter().collect::<Vec<_>>(), words);
+            // the encoding must be exactly the same as the sequential one
+            let mut par_bytes = Vec::new();
+            par.serialize(&mut par_bytes)?;
+            assert_eq!(par_bytes, seq_bytes);
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(par.index_of(word.as_str()), Some(i));
+            }
+        }
+    }
+
+    // an unsorted boundary must be detected
+    let mut rclb = RearCodedListBuilder::new(4);
+    rclb.par_extend(&["c", "d", "e", "f", "a", "b", "c", "d"]);
+    let rcl = rclb.build();
+    assert_eq!(rcl.index_of("a"), Some(4));
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_concat() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for (k0, k1) in [(4, 4), (4, 3), (1, 7)] {
+        for split in [0, 1, 4, 13, 40, words.len()] {
+            let mut rclb = RearCodedListBuilder::new(k0);
+            rclb.extend(words[..split].iter().map(|s| s.as_str()).into_lender());
+            let first = rclb.build();
+            let mut rclb = RearCodedListBuilder::new(k1);
+            rclb.extend(words[split..].iter().map(|s| s.as_str()).into_lender());
+            let second = rclb.build();
+
+            let rcl = first.concat(&second);
+            assert_eq!(rcl.len(), words.len());
+            assert_eq!(rcl.iter().collect::<Vec<_>>(), words);
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(rcl.index_of(word.as_str()), Some(i));
+            }
+            assert_eq!(
+                rcl.prefix_range("a"),
+                0..words.partition_point(|w| w.starts_with('a'))
+            );
+
+            // the other way round the list is not sorted, unless one is empty
+            let rcl = second.concat(&first);
+            for (i, word) in words[split..].iter().chain(&words[..split]).enumerate() {
+                assert_eq!(rcl.index_of(word.as_str()), Some(i));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_cursor() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let mut rng = SmallRng::seed_from_u64(0);
+
+    for k in [1, 3, 8] {
+        let mut rclb = RearCodedListBuilder::new(k);
+        rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rcl = rclb.build();
+
+        let mut cursor = rcl.cursor();
+        assert_eq!(cursor.index(), None);
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(cursor.get(i), word);
+        }
+        // mostly short moves in both directions
+        let mut i = 0_usize;
+        for _ in 0..1000 {
+            i = (i + rng.gen_range(0..6))
+                .saturating_sub(2)
+                .min(words.len() - 1);
+            assert_eq!(cursor.get(i), words[i]);
+            assert_eq!(cursor.index(), Some(i));
+        }
+        for _ in 0..1000 {
+            let i = rng.gen_range(0..words.len());
+            assert_eq!(cursor.get(i), words[i]);
+        }
+    }
+
+    let mut rclb = RearCodedListBuilder::new_bytes(2);
+    for key in [b"\0".as_slice(), b"\0\0", b"\0\x01"] {
+        rclb.push(key);
+    }
+    let rcl = rclb.build();
+    let mut cursor = rcl.cursor();
+    assert_eq!(cursor.get(2), b"\0\x01");
+    assert_eq!(cursor.get(1), b"\0\0");
+    assert_eq!(cursor.get(0), b"\0");
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_rear_coded_list_cursor_out_of_bounds() {
+    let mut rclb = RearCodedListBuilder::new(4);
+    rclb.push("a");
+    rclb.build().cursor().get(1);
+}
+
+fn check_coder<C: RearLengthCoder>(words: &[String]) -> Result<()> {
+    for k in [1, 4, 16] {
+        let mut rclb = RearCodedListBuilder::<str, C>::with_block_size(k);
+        rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rcl = rclb.build();
+        assert_eq!(rcl.iter().collect::<Vec<_>>(), words);
+        let mut cursor = rcl.cursor();
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(&rcl.get(i), word);
+            assert_eq!(cursor.get(i), word);
+            assert_eq!(rcl.index_of(word.as_str()), Some(i));
+        }
+        assert_eq!(
+            rcl.prefix_range("b"),
+            words.partition_point(|w| w.as_str() < "b")
+                ..words.partition_point(|w| w.as_str() < "c")
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_coders() -> Result<()> {
+    let mut words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    // long strings, so that escapes are used
+    words.extend((0..100).map(|i| format!("{}{:03}", "z".repeat(300), i)));
+    words.extend((0..100).map(|i| format!("{:03}{}", i, "z".repeat(300))));
+    words.sort();
+
+    check_coder::<VByteCoder>(&words)?;
+    check_coder::<NibbleCoder>(&words)?;
+    check_coder::<ByteEscapeCoder>(&words)?;
+
+    let mut rclb = RearCodedListBuilder::<str, NibbleCoder>::with_block_size(8);
+    rclb.extend(words.iter().map(|s| s.as_str()).into_lender());
+    let rcl = rclb.build();
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_rcl_nibble.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    rcl.serialize(&mut file)?;
+    drop(file);
+    let c = <RearCodedList<str, NibbleCoder>>::load_full(&tmp_file)?;
+    for (i, word) in words.iter().enumerate() {
+        assert_eq!(&c.get(i), word);
+    }
+    // The coder is part of the type
+    assert!(<RearCodedList>::load_full(&tmp_file).is_err());
+    Ok(())
+}
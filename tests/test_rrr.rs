@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use mem_dbg::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+#[test]
+fn test_rrr() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let lens = (0..300).chain([10_000, 65_536, 100_017]);
+    for len in lens {
+        for density in [0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let rrr = Rrr::new(&bits);
+            let num_ones = bits.count_ones();
+
+            assert_eq!(rrr.len(), len);
+            assert_eq!(rrr.num_ones(), num_ones);
+            assert_eq!(rrr.num_zeros(), len - num_ones);
+
+            let mut ones = 0;
+            for (i, bit) in bits.into_iter().enumerate() {
+                assert_eq!(rrr[i], bit);
+                assert_eq!(rrr.rank(i), ones);
+                assert_eq!(rrr.rank_zero(i), i - ones);
+                if bit {
+                    assert_eq!(rrr.select(ones), Some(i));
+                    ones += 1;
+                } else {
+                    assert_eq!(rrr.select_zero(i - ones), Some(i));
+                }
+            }
+            assert_eq!(rrr.rank(len), num_ones);
+            assert_eq!(rrr.rank(len + 1), num_ones);
+            assert_eq!(rrr.select(num_ones), None);
+            assert_eq!(rrr.select_zero(len - num_ones), None);
+        }
+    }
+}
+
+#[test]
+fn test_rrr_space() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1 << 20;
+    let bits = (0..len).map(|_| rng.gen_bool(0.01)).collect::<BitVec>();
+    let rrr = Rrr::new(&bits);
+    // The zero-order entropy is ≈0.08 bits per bit
+    assert!(rrr.mem_size(SizeFlags::default()) * 8 < len / 4);
+}
+
+#[test]
+fn test_rrr_elias_fano() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let n = 10_000;
+    let mut values = (0..n)
+        .map(|_| rng.gen_range(0..1_000_000))
+        .collect::<Vec<_>>();
+    values.sort();
+    let mut efb = EliasFanoBuilder::new(n, 1_000_000);
+    for &value in &values {
+        efb.push(value);
+    }
+    let ef = unsafe { efb.build().map_high_bits(Rrr::new) };
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(ef.get(i), value);
+    }
+    assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+
+    for x in (0..1_000_100).step_by(7) {
+        let lt = values.partition_point(|&v| v < x);
+        let le = values.partition_point(|&v| v <= x);
+        assert_eq!(
+            ef.index_of(x),
+            (lt < n && values[lt] == x).then_some(lt),
+            "index_of({x})"
+        );
+        assert_eq!(ef.succ(x), (lt < n).then(|| (lt, values[lt])), "succ({x})");
+        assert_eq!(
+            ef.succ_strict(x),
+            (le < n).then(|| (le, values[le])),
+            "succ_strict({x})"
+        );
+        assert_eq!(
+            ef.pred(x),
+            (le > 0).then(|| (le - 1, values[le - 1])),
+            "pred({x})"
+        );
+        assert_eq!(
+            ef.pred_strict(x),
+            (lt > 0).then(|| (lt - 1, values[lt - 1])),
+            "pred_strict({x})"
+        );
+    }
+}
+
+#[test]
+fn test_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let bits = (0..100_000).map(|_| rng.gen_bool(0.1)).collect::<BitVec>();
+    let rrr = Rrr::new(&bits);
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_rrr.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    rrr.serialize(&mut file)?;
+    drop(file);
+
+    let c = <Rrr>::mmap(&tmp_file, epserde::deser::Flags::empty())?;
+    for i in 0..bits.len() {
+        assert_eq!(c[i], bits[i]);
+        assert_eq!(c.rank(i), rrr.rank(i));
+    }
+    Ok(())
+}
@@ -301,3 +301,33 @@ fn test_sub32s_last_small() {
         assert_eq!(simple.select(ones + 1), None);
     }
 }
+
+fn check_words<W: Word>() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 31, 32, 33, 1000, 100_000] {
+        let bits: AddNumBits<_> = (0..len)
+            .map(|_| rng.gen_bool(0.1))
+            .collect::<BitVec<Vec<W>>>()
+            .into();
+        let select = SelectAdapt::new(bits.clone(), 3);
+        let ones = (0..len).filter(|&i| bits[i]).collect::<Vec<_>>();
+        assert_eq!(select.num_ones(), ones.len());
+        for (i, &pos) in ones.iter().enumerate() {
+            assert_eq!(select.select(i), Some(pos));
+        }
+        assert_eq!(select.select(ones.len()), None);
+
+        let rank_sel = SelectAdapt::new(Rank9::new(bits), 3);
+        for (i, &pos) in ones.iter().enumerate() {
+            assert_eq!(rank_sel.rank(pos), i);
+            assert_eq!(rank_sel.select(i), Some(pos));
+        }
+    }
+}
+
+#[test]
+fn test_words() {
+    check_words::<u8>();
+    check_words::<u32>();
+    check_words::<u128>();
+}
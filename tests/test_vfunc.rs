@@ -0,0 +1,73 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_progress_logger::ProgressLogger;
+use epserde::prelude::*;
+use sux::func::{VFunc, VFuncBuilder};
+use sux::utils::{FromIntoIterator, LineLender};
+
+#[test]
+fn test_vfunc() -> Result<()> {
+    for n in [0, 1, 10, 1000, 100_000, 200_000] {
+        let func = VFuncBuilder::<usize, usize>::default().build(
+            FromIntoIterator::from(0..n),
+            FromIntoIterator::from((0..n).map(|x| x ^ 0xdead)),
+            &mut Option::<ProgressLogger>::None,
+        )?;
+        assert_eq!(func.len(), n);
+        for i in 0..n {
+            assert_eq!(func.get(&i), i ^ 0xdead);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_vfunc_zero_values() -> Result<()> {
+    let func = VFuncBuilder::<u8, usize>::default().build(
+        FromIntoIterator::from(0..1000),
+        FromIntoIterator::from(vec![0; 1000]),
+        &mut Option::<ProgressLogger>::None,
+    )?;
+    for i in 0..1000 {
+        assert_eq!(func.get(&i), 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_vfunc_strings() -> Result<()> {
+    let func = VFuncBuilder::<usize, str>::default().seed(42).build(
+        LineLender::from_path("tests/data/wordlist.10000")?,
+        FromIntoIterator::from(0..),
+        &mut Option::<ProgressLogger>::None,
+    )?;
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_vfunc.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    func.serialize(&mut file)?;
+    drop(file);
+    let mapped = <VFunc<usize, str>>::mmap(&tmp_file, epserde::deser::Flags::empty())?;
+
+    let words = std::fs::read_to_string("tests/data/wordlist.10000")?;
+    for (i, word) in words.lines().enumerate() {
+        assert_eq!(func.get(word), i);
+        assert_eq!(mapped.get(word), i);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_vfunc_duplicates() {
+    assert!(VFuncBuilder::<usize, usize>::default()
+        .build(
+            FromIntoIterator::from([0, 1, 2, 1]),
+            FromIntoIterator::from(0..),
+            &mut Option::<ProgressLogger>::None,
+        )
+        .is_err());
+}
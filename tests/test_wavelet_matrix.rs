@@ -0,0 +1,115 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use epserde::prelude::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+#[test]
+fn test_wavelet_matrix() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (len, bit_width) in [(0, 3), (1, 1), (100, 0), (1000, 1), (1000, 5), (10_000, 10)] {
+        let mut seq = BitFieldVec::<usize>::new(bit_width, len);
+        for i in 0..len {
+            seq.set(i, rng.gen_range(0..1 << bit_width));
+        }
+        let values = (0..len).map(|i| seq.get(i)).collect::<Vec<_>>();
+        let wm = WaveletMatrix::new(&seq);
+
+        assert_eq!(wm.len(), len);
+        assert_eq!(wm.bit_width(), bit_width);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(wm.get(i), value);
+        }
+
+        for symbol in 0..(1 << bit_width) + 1 {
+            let positions = (0..len)
+                .filter(|&i| values[i] == symbol)
+                .collect::<Vec<_>>();
+            for (rank, &pos) in positions.iter().enumerate() {
+                assert_eq!(wm.select(symbol, rank), Some(pos));
+                assert_eq!(wm.rank(symbol, pos), rank);
+            }
+            assert_eq!(wm.select(symbol, positions.len()), None);
+            assert_eq!(wm.rank(symbol, len), positions.len());
+            assert_eq!(wm.rank(symbol, len + 1), positions.len());
+        }
+
+        for _ in 0..100 {
+            let start = rng.gen_range(0..=len);
+            let end = rng.gen_range(start..=len);
+            let lo = rng.gen_range(0..=(1 << bit_width) + 1);
+            let hi = rng.gen_range(0..=(1 << bit_width) + 1);
+            assert_eq!(
+                wm.range_count(start..end, lo..hi),
+                values[start..end]
+                    .iter()
+                    .filter(|&&v| lo <= v && v < hi)
+                    .count()
+            );
+
+            let mut sorted = values[start..end].to_vec();
+            sorted.sort();
+            for k in 0..=sorted.len() {
+                assert_eq!(
+                    wm.range_quantile(start..end, k),
+                    sorted[k..].first().copied()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_wavelet_matrix_new_with() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut seq = BitFieldVec::<usize>::new(8, 1000);
+    for i in 0..1000 {
+        seq.set(i, rng.gen_range(0..256));
+    }
+    let wm = WaveletMatrix::new_with(&seq, |bits| {
+        SelectZeroAdapt::new(SelectAdapt::new(Rank9::new(bits), 3), 3)
+    });
+    for i in 0..1000 {
+        let value = seq.get(i);
+        assert_eq!(wm.get(i), value);
+        let rank = wm.rank(value, i);
+        assert_eq!(wm.select(value, rank), Some(i));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_range_out_of_bounds() {
+    let wm = WaveletMatrix::new(&bit_field_vec![2; 1, 2, 3]);
+    wm.range_count(0..4, 0..4);
+}
+
+#[test]
+fn test_epserde() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut seq = BitFieldVec::<usize>::new(12, 10_000);
+    for i in 0..10_000 {
+        seq.set(i, rng.gen_range(0..4096));
+    }
+    let wm = WaveletMatrix::new(&seq);
+
+    let tmp_file = std::env::temp_dir().join("test_serdes_wm.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    wm.serialize(&mut file)?;
+    drop(file);
+
+    let c = <WaveletMatrix>::load_full(&tmp_file)?;
+    for i in 0..10_000 {
+        assert_eq!(c.get(i), seq.get(i));
+    }
+    assert_eq!(
+        c.range_quantile(100..200, 50),
+        wm.range_quantile(100..200, 50)
+    );
+    Ok(())
+}